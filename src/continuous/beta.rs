@@ -1,5 +1,8 @@
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{
+  mean_var, DependentJoint, Distribution, IndependentJoint, MethodOfMoments, MethodOfMomentsError,
+  RandomVariable, Support,
+};
 use rand::prelude::*;
 use rand_distr::Beta as RandBeta;
 use special::Beta as BetaFunc;
@@ -26,10 +29,14 @@ impl Distribution for Beta {
     let alpha = theta.alpha();
     let beta = theta.beta();
 
+    if !self.in_support(x, theta) {
+      return Ok(0.0);
+    }
+
     Ok((x.powf(alpha - 1.0) * (1.0 - x).powf(beta - 1.0)) / BetaFunc::ln_beta(alpha, beta).exp())
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let alpha = theta.alpha();
     let beta = theta.beta();
 
@@ -55,7 +62,7 @@ impl BetaParams {
         BetaError::AlphaMustBePositive.into(),
       ));
     }
-    if alpha <= 0.0 {
+    if beta <= 0.0 {
       return Err(DistributionError::InvalidParameters(
         BetaError::BetaMustBePositive.into(),
       ));
@@ -73,6 +80,32 @@ impl BetaParams {
   }
 }
 
+impl Support for Beta {
+  fn in_support(&self, x: &Self::T, _theta: &Self::U) -> bool {
+    (0.0..=1.0).contains(x)
+  }
+
+  fn support_bounds(&self, _theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    (Some(0.0), Some(1.0))
+  }
+}
+
+impl MethodOfMoments for Beta {
+  fn fit_moments(samples: &[Self::T]) -> Result<Self::U, DistributionError> {
+    let (mean, var) = mean_var(samples)?;
+
+    // a Beta with mean m has variance below m (1 - m)
+    if !(0.0 < mean && mean < 1.0) || mean * (1.0 - mean) <= var {
+      return Err(DistributionError::InvalidParameters(
+        MethodOfMomentsError::InconsistentMoments.into(),
+      ));
+    }
+    let c = mean * (1.0 - mean) / var - 1.0;
+
+    BetaParams::new(mean * c, (1.0 - mean) * c)
+  }
+}
+
 impl<Rhs, TRhs> Mul<Rhs> for Beta
 where
   Rhs: Distribution<T = TRhs, U = BetaParams>,
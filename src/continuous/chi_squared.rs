@@ -29,7 +29,7 @@ impl Distribution for ChiSquared {
     )
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let k = theta.k();
 
     let chi_squared = match RandChiSquared::new(k) {
@@ -1,8 +1,11 @@
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{
+  check_not_empty, check_probability, CumulativeDistribution, DependentJoint, Distribution,
+  IndependentJoint, MaximumLikelihood, QuantileDistribution, RandomVariable, Support,
+};
 use rand::prelude::*;
 use rand_distr::Exp as RandExp;
-use std::{ops::BitAnd, ops::Mul};
+use std::{convert::TryFrom, ops::BitAnd, ops::Mul};
 
 /// # Exp
 /// ![tex](https://latex.codecogs.com/svg.latex?\mathcal%7BN%7D%28\mu%2C%20\sigma%5E2%29)
@@ -13,6 +16,8 @@ pub struct Exp;
 pub enum ExpError {
   #[error("Lambda must be positive")]
   LambdaMustBePositive,
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
 }
 
 impl Distribution for Exp {
@@ -22,10 +27,25 @@ impl Distribution for Exp {
   fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
     let lambda = theta.lambda();
 
+    if *x < 0.0 {
+      return Ok(0.0);
+    }
+
     Ok(lambda * (-lambda * x).exp())
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  /// Stays finite where `p` underflows to 0 for large `lambda * x`.
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let lambda = theta.lambda();
+
+    if *x < 0.0 {
+      return Ok(f64::NEG_INFINITY);
+    }
+
+    Ok(lambda.ln() - lambda * x)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let lambda = theta.lambda();
 
     let exp = match RandExp::new(lambda) {
@@ -58,6 +78,58 @@ impl ExpParams {
   }
 }
 
+impl TryFrom<Vec<f64>> for ExpParams {
+  type Error = DistributionError;
+
+  /// Reads `[lambda]`.
+  fn try_from(v: Vec<f64>) -> Result<Self, Self::Error> {
+    if v.len() != 1 {
+      return Err(DistributionError::InvalidParameters(
+        ExpError::DimensionMismatch.into(),
+      ));
+    }
+
+    Self::new(v[0])
+  }
+}
+
+impl Support for Exp {
+  fn in_support(&self, x: &Self::T, _theta: &Self::U) -> bool {
+    0.0 <= *x
+  }
+
+  fn support_bounds(&self, _theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    (Some(0.0), None)
+  }
+}
+
+impl MaximumLikelihood for Exp {
+  fn fit(samples: &[Self::T]) -> Result<Self::U, DistributionError> {
+    check_not_empty(samples)?;
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+
+    ExpParams::new(1.0 / mean)
+  }
+}
+
+impl CumulativeDistribution for Exp {
+  fn cdf(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    if *x < 0.0 {
+      return Ok(0.0);
+    }
+
+    Ok(-(-theta.lambda() * x).exp_m1())
+  }
+}
+
+impl QuantileDistribution for Exp {
+  fn quantile(&self, p: f64, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    check_probability(p)?;
+
+    Ok(-(1.0 - p).ln() / theta.lambda())
+  }
+}
+
 impl<Rhs, TRhs> Mul<Rhs> for Exp
 where
   Rhs: Distribution<T = TRhs, U = ExpParams>,
@@ -84,8 +156,30 @@ where
 
 #[cfg(test)]
 mod tests {
+  use crate::{Distribution, Exp, ExpParams};
+  use std::convert::TryFrom;
+
   #[test]
   fn it_works() {
     assert_eq!(2 + 2, 4);
   }
+
+  #[test]
+  fn ln_p() {
+    let params = ExpParams::new(10.0).unwrap();
+
+    assert_eq!(Exp.p(&1000.0, &params).unwrap().ln(), f64::NEG_INFINITY);
+    assert!((Exp.ln_p(&1000.0, &params).unwrap() - (10.0f64.ln() - 10000.0)).abs() < 1e-9);
+    assert_eq!(Exp.ln_p(&-1.0, &params).unwrap(), f64::NEG_INFINITY);
+  }
+
+  #[test]
+  fn try_from() {
+    let params = ExpParams::try_from(vec![2.5]).unwrap();
+    assert_eq!(params, ExpParams::new(2.5).unwrap());
+    assert_eq!(ExpParams::try_from(vec![params.lambda()]).unwrap(), params);
+
+    assert!(ExpParams::try_from(vec![-1.0]).is_err());
+    assert!(ExpParams::try_from(vec![]).is_err());
+  }
 }
@@ -1,5 +1,8 @@
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{
+  mean_var, DependentJoint, Distribution, IndependentJoint, MethodOfMoments, MethodOfMomentsError,
+  RandomVariable, Support,
+};
 use rand::prelude::*;
 use rand_distr::Gamma as RandGamma;
 use special::Gamma as GammaFunc;
@@ -26,6 +29,10 @@ impl Distribution for Gamma {
     let shape = theta.shape();
     let scale = theta.scale();
 
+    if !self.in_support(x, theta) {
+      return Ok(0.0);
+    }
+
     Ok(
       (1.0 / GammaFunc::gamma(shape) * scale.powf(shape))
         * x.powf(shape - 1.0)
@@ -33,7 +40,7 @@ impl Distribution for Gamma {
     )
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let shape = theta.shape();
     let scale = theta.scale();
 
@@ -77,6 +84,39 @@ impl GammaParams {
   }
 }
 
+/// Posterior over the rate `λ` of `Poisson(λ)` under the prior `Gamma(k, θ)`, given `counts`.
+/// ![tex](https://latex.codecogs.com/svg.latex?k_n%3Dk%2B\sum_i%20x_i%2C%5C%3B\frac%7B1%7D%7B\theta_n%7D%3D\frac%7B1%7D%7B\theta%7D%2Bn)
+pub fn gamma_poisson_update(prior: &GammaParams, counts: &[u64]) -> GammaParams {
+  GammaParams {
+    shape: prior.shape + counts.iter().sum::<u64>() as f64,
+    scale: prior.scale / (1.0 + counts.len() as f64 * prior.scale),
+  }
+}
+
+impl Support for Gamma {
+  fn in_support(&self, x: &Self::T, _theta: &Self::U) -> bool {
+    0.0 <= *x
+  }
+
+  fn support_bounds(&self, _theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    (Some(0.0), None)
+  }
+}
+
+impl MethodOfMoments for Gamma {
+  fn fit_moments(samples: &[Self::T]) -> Result<Self::U, DistributionError> {
+    let (mean, var) = mean_var(samples)?;
+
+    if mean <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        MethodOfMomentsError::InconsistentMoments.into(),
+      ));
+    }
+
+    GammaParams::new(mean.powi(2) / var, var / mean)
+  }
+}
+
 impl<Rhs, TRhs> Mul<Rhs> for Gamma
 where
   Rhs: Distribution<T = TRhs, U = GammaParams>,
@@ -103,8 +143,33 @@ where
 
 #[cfg(test)]
 mod tests {
+  use crate::{gamma_poisson_update, GammaParams};
+
   #[test]
   fn it_works() {
     assert_eq!(2 + 2, 4);
   }
+
+  #[test]
+  fn poisson_update() {
+    let prior = GammaParams::new(2.0, 0.5).unwrap();
+    let counts = [3u64, 5, 4, 2, 6, 4, 3, 5, 4, 4];
+
+    let posterior = gamma_poisson_update(&prior, &counts);
+    assert_eq!(posterior.shape(), 42.0);
+    assert!((1.0 / posterior.scale() - 12.0).abs() < 1e-12);
+    assert_eq!(gamma_poisson_update(&prior, &[]), prior);
+
+    // the posterior mean approaches the sample rate 4 while the variance shrinks
+    let mut variance = prior.shape() * prior.scale().powi(2);
+    for n in 1..=5 {
+      let repeated = counts.repeat(n);
+      let posterior = gamma_poisson_update(&prior, &repeated);
+      let v = posterior.shape() * posterior.scale().powi(2);
+      assert!(v < variance);
+      variance = v;
+    }
+    let posterior = gamma_poisson_update(&prior, &counts.repeat(100));
+    assert!((posterior.shape() * posterior.scale() - 4.0).abs() < 0.01);
+  }
 }
@@ -0,0 +1,241 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable, Support};
+use rand::prelude::*;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # GeneralizedExtremeValue
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7BGEV%7D%28\mu%2C%20\sigma%2C%20\xi%29)
+///
+/// Gumbel for ![tex](https://latex.codecogs.com/svg.latex?\xi%3D0), Fréchet for ![tex](https://latex.codecogs.com/svg.latex?\xi%3E0) and reversed Weibull for ![tex](https://latex.codecogs.com/svg.latex?\xi%3C0).
+#[derive(Clone, Debug)]
+pub struct GeneralizedExtremeValue;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GeneralizedExtremeValueError {
+  #[error("Scale must be positive")]
+  ScaleMustBePositive,
+}
+
+/// Below this `|shape|` the Gumbel limit is used.
+const SHAPE_EPS: f64 = 1e-12;
+
+impl Distribution for GeneralizedExtremeValue {
+  type T = f64;
+  type U = GevParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    if !self.in_support(x, theta) {
+      return Ok(f64::NEG_INFINITY);
+    }
+
+    let scale = theta.scale();
+    let shape = theta.shape();
+    let z = (x - theta.location()) / scale;
+
+    // t = (1 + shape * z)^{-1 / shape}, which tends to e^{-z}
+    let ln_t = if shape.abs() < SHAPE_EPS {
+      -z
+    } else {
+      -(shape * z).ln_1p() / shape
+    };
+    // the Fréchet density vanishes at its lower bound
+    if ln_t == f64::INFINITY {
+      return Ok(f64::NEG_INFINITY);
+    }
+
+    Ok(-scale.ln() + (shape + 1.0) * ln_t - ln_t.exp())
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let shape = theta.shape();
+
+    // u in (0, 1)
+    let u = loop {
+      let u = rng.gen::<f64>();
+      if 0.0 < u {
+        break u;
+      }
+    };
+    let ln_e = (-u.ln()).ln();
+
+    // ((-ln u)^{-shape} - 1) / shape, which tends to -ln(-ln u)
+    let z = if shape.abs() < SHAPE_EPS {
+      -ln_e
+    } else {
+      (-shape * ln_e).exp_m1() / shape
+    };
+
+    Ok(theta.location() + theta.scale() * z)
+  }
+}
+
+impl Support for GeneralizedExtremeValue {
+  fn in_support(&self, x: &Self::T, theta: &Self::U) -> bool {
+    let (lower, upper) = self.support_bounds(theta);
+
+    !matches!(lower, Some(l) if *x < l) && !matches!(upper, Some(u) if u < *x)
+  }
+
+  fn support_bounds(&self, theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    let shape = theta.shape();
+    let bound = theta.location() - theta.scale() / shape;
+
+    if shape.abs() < SHAPE_EPS {
+      (None, None)
+    } else if 0.0 < shape {
+      (Some(bound), None)
+    } else {
+      (None, Some(bound))
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GevParams {
+  location: f64,
+  scale: f64,
+  shape: f64,
+}
+
+impl GevParams {
+  pub fn new(location: f64, scale: f64, shape: f64) -> Result<Self, DistributionError> {
+    if scale <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        GeneralizedExtremeValueError::ScaleMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self {
+      location,
+      scale,
+      shape,
+    })
+  }
+
+  pub fn location(&self) -> f64 {
+    self.location
+  }
+
+  pub fn scale(&self) -> f64 {
+    self.scale
+  }
+
+  pub fn shape(&self) -> f64 {
+    self.shape
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for GeneralizedExtremeValue
+where
+  Rhs: Distribution<T = TRhs, U = GevParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, f64, TRhs, GevParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for GeneralizedExtremeValue
+where
+  Rhs: Distribution<T = GevParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, f64, GevParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Distribution, GeneralizedExtremeValue, GevParams, Gumbel, GumbelParams, Support};
+  use rand::prelude::*;
+
+  #[test]
+  fn gumbel() {
+    let params = GevParams::new(1.0, 2.0, 0.0).unwrap();
+    let gumbel = GumbelParams::new(1.0, 2.0).unwrap();
+    // the limit is continuous in the shape
+    let near = GevParams::new(1.0, 2.0, 1e-9).unwrap();
+
+    for &x in [-3.0, 0.0, 1.0, 4.0, 10.0].iter() {
+      let p = GeneralizedExtremeValue.p(&x, &params).unwrap();
+
+      assert!((p - Gumbel.p(&x, &gumbel).unwrap()).abs() < 1e-12);
+      assert!((p - GeneralizedExtremeValue.p(&x, &near).unwrap()).abs() < 1e-7);
+    }
+    assert_eq!(
+      GeneralizedExtremeValue.support_bounds(&params),
+      (None, None)
+    );
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let x = GeneralizedExtremeValue
+      .sample_n(10000, &params, &mut rng)
+      .unwrap();
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    assert!((mean - (1.0 + 2.0 * 0.5772156649)).abs() < 0.1);
+  }
+
+  #[test]
+  fn frechet() {
+    let (location, scale, shape) = (1.0, 2.0, 0.5);
+    let params = GevParams::new(location, scale, shape).unwrap();
+
+    // Fréchet with alpha = 1 / shape, s = scale / shape, m = location - scale / shape
+    let (alpha, s, m) = (1.0 / shape, scale / shape, location - scale / shape);
+    for &x in [-2.0, 0.0, 3.0, 10.0].iter() {
+      let y: f64 = (x - m) / s;
+      let expected = alpha / s * y.powf(-1.0 - alpha) * (-y.powf(-alpha)).exp();
+
+      assert!((GeneralizedExtremeValue.p(&x, &params).unwrap() - expected).abs() < 1e-12);
+    }
+
+    assert_eq!(
+      GeneralizedExtremeValue.support_bounds(&params),
+      (Some(m), None)
+    );
+    assert_eq!(GeneralizedExtremeValue.p(&(m - 0.1), &params).unwrap(), 0.0);
+    assert_eq!(GeneralizedExtremeValue.p(&m, &params).unwrap(), 0.0);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let x = GeneralizedExtremeValue
+      .sample_n(1000, &params, &mut rng)
+      .unwrap();
+    assert!(x.iter().all(|&xi| m <= xi));
+  }
+
+  #[test]
+  fn weibull() {
+    let (location, scale, shape) = (1.0, 2.0, -0.5);
+    let params = GevParams::new(location, scale, shape).unwrap();
+
+    // reversed Weibull with alpha = -1 / shape, s = -scale / shape, upper bound m = location - scale / shape
+    let (alpha, s, m) = (-1.0 / shape, -scale / shape, location - scale / shape);
+    for &x in [-5.0, 0.0, 2.0, 4.5].iter() {
+      let y: f64 = (m - x) / s;
+      let expected = alpha / s * y.powf(alpha - 1.0) * (-y.powf(alpha)).exp();
+
+      assert!((GeneralizedExtremeValue.p(&x, &params).unwrap() - expected).abs() < 1e-12);
+    }
+
+    assert_eq!(
+      GeneralizedExtremeValue.support_bounds(&params),
+      (None, Some(m))
+    );
+    assert_eq!(GeneralizedExtremeValue.p(&(m + 0.1), &params).unwrap(), 0.0);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let x = GeneralizedExtremeValue
+      .sample_n(1000, &params, &mut rng)
+      .unwrap();
+    assert!(x.iter().all(|&xi| xi <= m));
+  }
+}
@@ -0,0 +1,160 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use rand::prelude::*;
+use rand_distr::Gumbel as RandGumbel;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # Gumbel
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7BGumbel%7D%28\mu%2C%20\beta%29)
+#[derive(Clone, Debug)]
+pub struct Gumbel;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GumbelError {
+  #[error("Beta must be positive")]
+  BetaMustBePositive,
+  #[error("Logits must not be empty")]
+  EmptyLogits,
+}
+
+impl Distribution for Gumbel {
+  type T = f64;
+  type U = GumbelParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let mu = theta.mu();
+    let beta = theta.beta();
+
+    let z = (x - mu) / beta;
+
+    Ok((-(z + (-z).exp())).exp() / beta)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let mu = theta.mu();
+    let beta = theta.beta();
+
+    let gumbel = match RandGumbel::new(mu, beta) {
+      Ok(v) => Ok(v),
+      Err(e) => Err(DistributionError::Others(e.into())),
+    }?;
+
+    Ok(rng.sample(gumbel))
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GumbelParams {
+  mu: f64,
+  beta: f64,
+}
+
+impl GumbelParams {
+  pub fn new(mu: f64, beta: f64) -> Result<Self, DistributionError> {
+    if beta <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        GumbelError::BetaMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self { mu, beta })
+  }
+
+  pub fn mu(&self) -> f64 {
+    self.mu
+  }
+
+  pub fn beta(&self) -> f64 {
+    self.beta
+  }
+}
+
+/// Samples a category from unnormalized log-weights with the Gumbel-max trick.
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7Bargmax%7D_i%20%28l_i%20+%20g_i%29%2C%20g_i%20\sim%20\mathrm%7BGumbel%7D%280%2C%201%29)
+pub fn gumbel_max_sample(
+  logits: &[f64],
+  rng: &mut dyn RngCore,
+) -> Result<usize, DistributionError> {
+  if logits.is_empty() {
+    return Err(DistributionError::InvalidParameters(
+      GumbelError::EmptyLogits.into(),
+    ));
+  }
+
+  let params = GumbelParams::new(0.0, 1.0)?;
+
+  let mut argmax = 0;
+  let mut max = f64::NEG_INFINITY;
+  for (i, &li) in logits.iter().enumerate() {
+    let v = li + Gumbel.sample(&params, rng)?;
+    if max < v {
+      argmax = i;
+      max = v;
+    }
+  }
+
+  Ok(argmax)
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for Gumbel
+where
+  Rhs: Distribution<T = TRhs, U = GumbelParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, f64, TRhs, GumbelParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for Gumbel
+where
+  Rhs: Distribution<T = GumbelParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, f64, GumbelParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{gumbel_max_sample, Distribution, Gumbel, GumbelParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = GumbelParams::new(1.0, 2.0).unwrap();
+
+    assert!((Gumbel.p(&1.0, &params).unwrap() - (-1.0f64).exp() / 2.0).abs() < 1e-12);
+    assert!(GumbelParams::new(0.0, 0.0).is_err());
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let x = Gumbel.sample_n(10000, &params, &mut rng).unwrap();
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    // mu + beta * euler–mascheroni
+    assert!((mean - (1.0 + 2.0 * 0.5772156649)).abs() < 0.1);
+  }
+
+  #[test]
+  fn gumbel_max() {
+    let logits = vec![1.0, 2.0, 0.5, -1.0];
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let n = 20000;
+    let mut counts = vec![0usize; logits.len()];
+    for _ in 0..n {
+      counts[gumbel_max_sample(&logits, &mut rng).unwrap()] += 1;
+    }
+
+    let z = logits.iter().map(|li| li.exp()).sum::<f64>();
+    for (ci, li) in counts.iter().zip(logits.iter()) {
+      assert!((*ci as f64 / n as f64 - li.exp() / z).abs() < 0.01);
+    }
+
+    assert!(gumbel_max_sample(&[], &mut rng).is_err());
+  }
+}
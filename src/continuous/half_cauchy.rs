@@ -0,0 +1,154 @@
+use crate::DistributionError;
+use crate::{
+  check_probability, CumulativeDistribution, DependentJoint, Distribution, IndependentJoint,
+  QuantileDistribution, RandomVariable, Support,
+};
+use rand::prelude::*;
+use rand_distr::Cauchy as RandCauchy;
+use std::{f64::consts::PI, ops::BitAnd, ops::Mul};
+
+/// # HalfCauchy
+/// ![tex](https://latex.codecogs.com/svg.latex?\frac%7B2%7D%7B\pi%20s\left%281%2B%28x/s%29%5E2\right%29%7D%2C%5C%3Bx\geq0)
+///
+/// The distribution of `|x|` for a Cauchy `x` centered at 0 with scale `s`, a heavy-tailed prior on scales.
+#[derive(Clone, Debug)]
+pub struct HalfCauchy;
+
+#[derive(thiserror::Error, Debug)]
+pub enum HalfCauchyError {
+  #[error("'scale' must be positive")]
+  ScaleMustBePositive,
+}
+
+impl Distribution for HalfCauchy {
+  type T = f64;
+  type U = HalfCauchyParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    if *x < 0.0 {
+      return Ok(0.0);
+    }
+    let scale = theta.scale();
+
+    Ok(2.0 / (PI * scale * (1.0 + (x / scale).powi(2))))
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let cauchy = match RandCauchy::new(0.0, theta.scale()) {
+      Ok(v) => Ok(v),
+      Err(e) => Err(DistributionError::Others(e.into())),
+    }?;
+
+    Ok(rng.sample(cauchy).abs())
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HalfCauchyParams {
+  scale: f64,
+}
+
+impl HalfCauchyParams {
+  pub fn new(scale: f64) -> Result<Self, DistributionError> {
+    if scale <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        HalfCauchyError::ScaleMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self { scale })
+  }
+
+  pub fn scale(&self) -> f64 {
+    self.scale
+  }
+}
+
+impl Support for HalfCauchy {
+  fn in_support(&self, x: &Self::T, _theta: &Self::U) -> bool {
+    0.0 <= *x
+  }
+
+  fn support_bounds(&self, _theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    (Some(0.0), None)
+  }
+}
+
+impl CumulativeDistribution for HalfCauchy {
+  fn cdf(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    if *x < 0.0 {
+      return Ok(0.0);
+    }
+
+    Ok(2.0 / PI * (x / theta.scale()).atan())
+  }
+}
+
+impl QuantileDistribution for HalfCauchy {
+  fn quantile(&self, p: f64, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    check_probability(p)?;
+
+    Ok(theta.scale() * (PI * p / 2.0).tan())
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for HalfCauchy
+where
+  Rhs: Distribution<T = TRhs, U = HalfCauchyParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, f64, TRhs, HalfCauchyParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for HalfCauchy
+where
+  Rhs: Distribution<T = HalfCauchyParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, f64, HalfCauchyParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    CumulativeDistribution, Distribution, HalfCauchy, HalfCauchyParams, QuantileDistribution,
+  };
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = HalfCauchyParams::new(2.0).unwrap();
+
+    // ∫_0^∞ p(x) dx with x = t / (1 - t), by the midpoint rule over t in (0, 1)
+    let n = 100000;
+    let integral = (0..n)
+      .map(|i| {
+        let t = (i as f64 + 0.5) / n as f64;
+        HalfCauchy.p(&(t / (1.0 - t)), &params).unwrap() / (1.0 - t).powi(2) / n as f64
+      })
+      .sum::<f64>();
+    assert!((integral - 1.0).abs() < 1e-6);
+    assert_eq!(HalfCauchy.p(&-0.1, &params).unwrap(), 0.0);
+
+    let x = HalfCauchy.quantile(0.5, &params).unwrap();
+    assert!((x - 2.0).abs() < 1e-12);
+    assert!((HalfCauchy.cdf(&x, &params).unwrap() - 0.5).abs() < 1e-12);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let samples = HalfCauchy.sample_n(10000, &params, &mut rng).unwrap();
+    assert!(samples.iter().all(|&x| 0.0 <= x));
+    // the median of the half-Cauchy is its scale
+    let below = samples.iter().filter(|&&x| x < 2.0).count() as f64 / samples.len() as f64;
+    assert!((below - 0.5).abs() < 0.02);
+
+    assert!(HalfCauchyParams::new(-1.0).is_err());
+  }
+}
@@ -0,0 +1,177 @@
+use crate::DistributionError;
+use crate::{
+  check_probability, CumulativeDistribution, DependentJoint, Distribution, IndependentJoint,
+  QuantileDistribution, RandomVariable, Support,
+};
+use rand::prelude::*;
+use rand_distr::Normal as RandNormal;
+use special::Error;
+use std::{
+  f64::consts::{PI, SQRT_2},
+  ops::BitAnd,
+  ops::Mul,
+};
+
+/// # HalfNormal
+/// ![tex](https://latex.codecogs.com/svg.latex?\frac%7B\sqrt%7B2%7D%7D%7B\sigma\sqrt%7B\pi%7D%7D\exp\left%28-\frac%7Bx%5E2%7D%7B2\sigma%5E2%7D\right%29%2C%5C%3Bx\geq0)
+///
+/// The distribution of `|x|` for `x ~ N(0, σ^2)`, a common prior on scales.
+#[derive(Clone, Debug)]
+pub struct HalfNormal;
+
+#[derive(thiserror::Error, Debug)]
+pub enum HalfNormalError {
+  #[error("'σ' must be positive")]
+  SigmaMustBePositive,
+}
+
+impl Distribution for HalfNormal {
+  type T = f64;
+  type U = HalfNormalParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    if *x < 0.0 {
+      return Ok(0.0);
+    }
+    let sigma = theta.sigma();
+
+    Ok(2.0 / (2.0 * PI * sigma.powi(2)).sqrt() * (-x.powi(2) / (2.0 * sigma.powi(2))).exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    if *x < 0.0 {
+      return Ok(f64::NEG_INFINITY);
+    }
+    let sigma = theta.sigma();
+
+    Ok((2.0 / PI).ln() / 2.0 - sigma.ln() - x.powi(2) / (2.0 * sigma.powi(2)))
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let normal = match RandNormal::new(0.0, theta.sigma()) {
+      Ok(v) => Ok(v),
+      Err(e) => Err(DistributionError::Others(e.into())),
+    }?;
+
+    Ok(rng.sample(normal).abs())
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HalfNormalParams {
+  sigma: f64,
+}
+
+impl HalfNormalParams {
+  pub fn new(sigma: f64) -> Result<Self, DistributionError> {
+    if sigma <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        HalfNormalError::SigmaMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self { sigma })
+  }
+
+  pub fn sigma(&self) -> f64 {
+    self.sigma
+  }
+}
+
+impl Support for HalfNormal {
+  fn in_support(&self, x: &Self::T, _theta: &Self::U) -> bool {
+    0.0 <= *x
+  }
+
+  fn support_bounds(&self, _theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    (Some(0.0), None)
+  }
+}
+
+impl CumulativeDistribution for HalfNormal {
+  fn cdf(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    if *x < 0.0 {
+      return Ok(0.0);
+    }
+
+    Ok((x / (theta.sigma() * SQRT_2)).error())
+  }
+}
+
+impl QuantileDistribution for HalfNormal {
+  fn quantile(&self, p: f64, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    check_probability(p)?;
+
+    Ok(theta.sigma() * SQRT_2 * p.inv_error())
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for HalfNormal
+where
+  Rhs: Distribution<T = TRhs, U = HalfNormalParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, f64, TRhs, HalfNormalParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for HalfNormal
+where
+  Rhs: Distribution<T = HalfNormalParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, f64, HalfNormalParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    CumulativeDistribution, Distribution, HalfNormal, HalfNormalParams, Normal, NormalParams,
+    QuantileDistribution,
+  };
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = HalfNormalParams::new(1.5).unwrap();
+
+    // ∫_0^∞ p(x) dx with x = t / (1 - t), by the midpoint rule over t in (0, 1)
+    let n = 100000;
+    let integral = (0..n)
+      .map(|i| {
+        let t = (i as f64 + 0.5) / n as f64;
+        HalfNormal.p(&(t / (1.0 - t)), &params).unwrap() / (1.0 - t).powi(2) / n as f64
+      })
+      .sum::<f64>();
+    assert!((integral - 1.0).abs() < 1e-6);
+
+    let parent = NormalParams::new(0.0, 1.5).unwrap();
+    assert!(
+      (HalfNormal.p(&0.7, &params).unwrap() - 2.0 * Normal.p(&0.7, &parent).unwrap()).abs() < 1e-12
+    );
+    assert!(
+      (HalfNormal.ln_p(&0.7, &params).unwrap() - HalfNormal.p(&0.7, &params).unwrap().ln()).abs()
+        < 1e-12
+    );
+    assert_eq!(HalfNormal.p(&-0.1, &params).unwrap(), 0.0);
+
+    let x = HalfNormal.quantile(0.3, &params).unwrap();
+    assert!((HalfNormal.cdf(&x, &params).unwrap() - 0.3).abs() < 1e-9);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let samples = HalfNormal.sample_n(10000, &params, &mut rng).unwrap();
+    assert!(samples.iter().all(|&x| 0.0 <= x));
+    // E|x| = σ sqrt(2 / π)
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    assert!((mean - 1.5 * (2.0 / std::f64::consts::PI).sqrt()).abs() < 0.03);
+
+    assert!(HalfNormalParams::new(0.0).is_err());
+  }
+}
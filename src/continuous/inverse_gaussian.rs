@@ -0,0 +1,161 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable, Support};
+use rand::prelude::*;
+use rand_distr::StandardNormal;
+use std::{f64::consts::PI, ops::BitAnd, ops::Mul};
+
+/// # InverseGaussian
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7BIG%7D%28\mu%2C%20\lambda%29)
+#[derive(Clone, Debug)]
+pub struct InverseGaussian;
+
+#[derive(thiserror::Error, Debug)]
+pub enum InverseGaussianError {
+  #[error("'μ' must be positive")]
+  MuMustBePositive,
+  #[error("'λ' must be positive")]
+  LambdaMustBePositive,
+}
+
+impl Distribution for InverseGaussian {
+  type T = f64;
+  type U = InverseGaussianParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let mu = theta.mu();
+    let lambda = theta.lambda();
+
+    if !self.in_support(x, theta) {
+      return Ok(f64::NEG_INFINITY);
+    }
+
+    Ok(
+      0.5 * (lambda / (2.0 * PI * x.powi(3))).ln()
+        - lambda * (x - mu).powi(2) / (2.0 * mu.powi(2) * x),
+    )
+  }
+
+  /// Michael, Schucany and Haas (1976).
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let mu = theta.mu();
+    let lambda = theta.lambda();
+
+    let nu: f64 = rng.sample(StandardNormal);
+    let y = nu.powi(2);
+    let x = mu + mu.powi(2) * y / (2.0 * lambda)
+      - mu / (2.0 * lambda) * (4.0 * mu * lambda * y + mu.powi(2) * y.powi(2)).sqrt();
+
+    if rng.gen_range(0.0..1.0) <= mu / (mu + x) {
+      return Ok(x);
+    }
+
+    Ok(mu.powi(2) / x)
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InverseGaussianParams {
+  mu: f64,
+  lambda: f64,
+}
+
+impl InverseGaussianParams {
+  pub fn new(mu: f64, lambda: f64) -> Result<Self, DistributionError> {
+    if mu <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        InverseGaussianError::MuMustBePositive.into(),
+      ));
+    }
+    if lambda <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        InverseGaussianError::LambdaMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self { mu, lambda })
+  }
+
+  pub fn mu(&self) -> f64 {
+    self.mu
+  }
+
+  pub fn lambda(&self) -> f64 {
+    self.lambda
+  }
+
+  pub fn mean(&self) -> f64 {
+    self.mu
+  }
+}
+
+impl Support for InverseGaussian {
+  fn in_support(&self, x: &Self::T, _theta: &Self::U) -> bool {
+    0.0 < *x
+  }
+
+  fn support_bounds(&self, _theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    (Some(0.0), None)
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for InverseGaussian
+where
+  Rhs: Distribution<T = TRhs, U = InverseGaussianParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, f64, TRhs, InverseGaussianParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for InverseGaussian
+where
+  Rhs: Distribution<T = InverseGaussianParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, f64, InverseGaussianParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Distribution, InverseGaussian, InverseGaussianParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let (mu, lambda) = (2.0, 3.0);
+    let params = InverseGaussianParams::new(mu, lambda).unwrap();
+
+    // Riemann sum over (0, 60)
+    let h = 1e-3;
+    let p_sum = (1..60000)
+      .map(|i| InverseGaussian.p(&(i as f64 * h), &params).unwrap() * h)
+      .sum::<f64>();
+    assert!((p_sum - 1.0).abs() < 1e-4);
+    assert_eq!(InverseGaussian.p(&-1.0, &params).unwrap(), 0.0);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let n = 50000;
+    let x = InverseGaussian.sample_n(n, &params, &mut rng).unwrap();
+
+    let mean = x.iter().sum::<f64>() / n as f64;
+    let var = x.iter().map(|xi| (xi - mean).powi(2)).sum::<f64>() / n as f64;
+
+    assert_eq!(params.mean(), mu);
+    assert!((mean - mu).abs() < 0.05);
+    assert!((var - mu.powi(3) / lambda).abs() < 0.3);
+
+    assert!(InverseGaussianParams::new(0.0, 1.0).is_err());
+    assert!(InverseGaussianParams::new(1.0, 0.0).is_err());
+  }
+}
@@ -3,8 +3,14 @@ pub mod chi_squared;
 pub mod exp;
 pub mod fisher_f;
 pub mod gamma;
+pub mod generalized_extreme_value;
+pub mod gumbel;
+pub mod half_cauchy;
+pub mod half_normal;
+pub mod inverse_gaussian;
 pub mod multivariate;
 pub mod normal;
+pub mod pareto;
 pub mod student_t;
 
 pub use beta::*;
@@ -12,6 +18,12 @@ pub use chi_squared::*;
 pub use exp::*;
 pub use fisher_f::*;
 pub use gamma::*;
+pub use generalized_extreme_value::*;
+pub use gumbel::*;
+pub use half_cauchy::*;
+pub use half_normal::*;
+pub use inverse_gaussian::*;
 pub use multivariate::*;
 pub use normal::*;
+pub use pareto::*;
 pub use student_t::*;
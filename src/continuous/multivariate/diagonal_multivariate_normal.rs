@@ -0,0 +1,270 @@
+use super::elliptical::approx_eq_vec;
+use crate::DistributionError;
+use crate::{
+  DependentJoint, Distribution, EllipticalParams, IndependentJoint, RandomVariable,
+  ValueDifferentiableDistribution,
+};
+use rand::prelude::*;
+use rand_distr::StandardNormal;
+use std::{f64::consts::PI, ops::BitAnd, ops::Mul};
+
+/// # DiagonalMultivariateNormal
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathcal%7BN%7D%28\mu%2C%20\mathrm%7Bdiag%7D%28\sigma_1%5E2%2C\ldots%2C\sigma_n%5E2%29%29)
+///
+/// `MultivariateNormal` with independent dimensions, evaluated in `O(n)` without a Cholesky factor.
+#[derive(Clone, Debug)]
+pub struct DiagonalMultivariateNormal;
+
+#[derive(thiserror::Error, Debug)]
+pub enum DiagonalMultivariateNormalError {
+  #[error("dimension mismatch")]
+  DimensionMismatch,
+  #[error("Variances must be positive")]
+  VarianceMustBePositive,
+}
+
+impl Distribution for DiagonalMultivariateNormal {
+  type T = Vec<f64>;
+  type U = DiagonalMultivariateNormalParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let p = theta.mu().len() as f64;
+    let ln_det_sqrt = theta.variances().iter().map(|v| v.ln()).sum::<f64>() / 2.0;
+
+    Ok(-p / 2.0 * (2.0 * PI).ln() - ln_det_sqrt - 1.0 / 2.0 * theta.mahalanobis_squared(x)?)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    Ok(
+      theta
+        .mu()
+        .iter()
+        .zip(theta.variances().iter())
+        .map(|(mui, vi)| mui + vi.sqrt() * rng.sample::<f64, _>(StandardNormal))
+        .collect(),
+    )
+  }
+}
+
+impl ValueDifferentiableDistribution for DiagonalMultivariateNormal {
+  /// ![tex](https://latex.codecogs.com/svg.latex?-%28x_i-\mu_i%29/\sigma_i%5E2) in `O(n)`.
+  fn ln_diff_value(&self, x: &Self::T, theta: &Self::U) -> Result<Vec<f64>, DistributionError> {
+    if x.len() != theta.mu().len() {
+      return Err(DistributionError::InvalidParameters(
+        DiagonalMultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    Ok(
+      x.iter()
+        .zip(theta.mu().iter())
+        .zip(theta.variances().iter())
+        .map(|((xi, mui), vi)| -(xi - mui) / vi)
+        .collect(),
+    )
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagonalMultivariateNormalParams {
+  mu: Vec<f64>,
+  variances: Vec<f64>,
+}
+
+impl DiagonalMultivariateNormalParams {
+  pub fn new(mu: Vec<f64>, variances: Vec<f64>) -> Result<Self, DistributionError> {
+    if mu.len() != variances.len() {
+      return Err(DistributionError::InvalidParameters(
+        DiagonalMultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+    if variances.iter().any(|&v| v <= 0.0) {
+      return Err(DistributionError::InvalidParameters(
+        DiagonalMultivariateNormalError::VarianceMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self { mu, variances })
+  }
+
+  pub fn mu(&self) -> &Vec<f64> {
+    &self.mu
+  }
+
+  pub fn variances(&self) -> &Vec<f64> {
+    &self.variances
+  }
+
+  /// Elementwise comparison of `mu` and `variances` within `tol`.
+  pub fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+    approx_eq_vec(&self.mu, &other.mu, tol) && approx_eq_vec(&self.variances, &other.variances, tol)
+  }
+
+  pub fn eject(self) -> (Vec<f64>, Vec<f64>) {
+    (self.mu, self.variances)
+  }
+}
+
+impl EllipticalParams for DiagonalMultivariateNormalParams {
+  fn mu(&self) -> &Vec<f64> {
+    &self.mu
+  }
+
+  fn sigma_inv_mul(&self, v: Vec<f64>) -> Result<Vec<f64>, DistributionError> {
+    if v.len() != self.variances.len() {
+      return Err(DistributionError::InvalidParameters(
+        DiagonalMultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    Ok(
+      v.iter()
+        .zip(self.variances.iter())
+        .map(|(vi, var)| vi / var)
+        .collect(),
+    )
+  }
+
+  fn sigma_det_sqrt(&self) -> f64 {
+    self.variances.iter().map(|v| v.sqrt()).product()
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for DiagonalMultivariateNormal
+where
+  Rhs: Distribution<T = TRhs, U = DiagonalMultivariateNormalParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, Vec<f64>, TRhs, DiagonalMultivariateNormalParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for DiagonalMultivariateNormal
+where
+  Rhs: Distribution<T = DiagonalMultivariateNormalParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, Vec<f64>, DiagonalMultivariateNormalParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    DiagonalMultivariateNormal, DiagonalMultivariateNormalParams, Distribution, EllipticalParams,
+    MultivariateNormal, MultivariateNormalParams, ValueDifferentiableDistribution,
+  };
+  use opensrdk_linear_algebra::*;
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let mu = vec![1.0, -2.0, 0.5];
+    let variances = vec![0.5, 2.0, 4.0];
+    let params = DiagonalMultivariateNormalParams::new(mu.clone(), variances.clone()).unwrap();
+    let lsigma = Matrix::from(
+      3,
+      vec![
+        0.5f64.sqrt(),
+        0.0,
+        0.0,
+        0.0,
+        2.0f64.sqrt(),
+        0.0,
+        0.0,
+        0.0,
+        2.0,
+      ],
+    );
+    let dense = MultivariateNormalParams::new(mu, lsigma).unwrap();
+
+    let x = vec![0.3, -1.0, 2.0];
+    let expected = MultivariateNormal.p(&x, &dense).unwrap();
+    assert!((DiagonalMultivariateNormal.p(&x, &params).unwrap() - expected).abs() < 1e-12);
+    assert!((params.sigma_det_sqrt() - dense.sigma_det_sqrt()).abs() < 1e-12);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let samples = DiagonalMultivariateNormal
+      .sample_n(10000, &params, &mut rng)
+      .unwrap();
+    let var2 = samples.iter().map(|s| (s[2] - 0.5).powi(2)).sum::<f64>() / 10000.0;
+    assert!((var2 - 4.0).abs() < 0.2);
+
+    assert!(DiagonalMultivariateNormalParams::new(vec![0.0], vec![0.0]).is_err());
+    assert!(DiagonalMultivariateNormalParams::new(vec![0.0], vec![1.0, 1.0]).is_err());
+    assert!(DiagonalMultivariateNormal.p(&vec![0.0], &params).is_err());
+  }
+
+  #[test]
+  fn large() {
+    let n = 1000;
+    let mu = (0..n).map(|i| (i as f64).sin()).collect::<Vec<_>>();
+    let variances = (0..n).map(|i| 1.0 + (i % 7) as f64).collect::<Vec<_>>();
+    let x = vec![0.0; n];
+
+    let params = DiagonalMultivariateNormalParams::new(mu.clone(), variances.clone()).unwrap();
+    let diagonal = DiagonalMultivariateNormal.ln_p(&x, &params).unwrap();
+
+    let mut sigma = Matrix::new(n, n);
+    for i in 0..n {
+      sigma[(i, i)] = variances[i];
+    }
+    let dense_params = MultivariateNormalParams::new(mu, sigma.potrf().unwrap()).unwrap();
+    let dense = MultivariateNormal.ln_p(&x, &dense_params).unwrap();
+
+    assert!((diagonal - dense).abs() < 1e-8 * dense.abs());
+
+    // a dense n x n factor would take 8 TB here
+    let n = 1_000_000;
+    let params = DiagonalMultivariateNormalParams::new(vec![0.0; n], vec![1.0; n]).unwrap();
+    let expected = -(n as f64) / 2.0 * (2.0 * std::f64::consts::PI).ln() - n as f64 / 2.0;
+    let ln_p = DiagonalMultivariateNormal
+      .ln_p(&vec![1.0; n], &params)
+      .unwrap();
+    assert!((ln_p - expected).abs() < 1e-8 * expected.abs());
+  }
+
+  #[test]
+  fn ln_diff_value() {
+    let mu = vec![1.0, -2.0, 0.5];
+    let variances = vec![0.5, 2.0, 4.0];
+    let params = DiagonalMultivariateNormalParams::new(mu.clone(), variances.clone()).unwrap();
+    let lsigma = Matrix::from(
+      3,
+      vec![
+        0.5f64.sqrt(),
+        0.0,
+        0.0,
+        0.0,
+        2.0f64.sqrt(),
+        0.0,
+        0.0,
+        0.0,
+        2.0,
+      ],
+    );
+    let dense = MultivariateNormalParams::new(mu, lsigma).unwrap();
+
+    let x = vec![0.3, -1.0, 2.0];
+    let expected = MultivariateNormal.ln_diff_value(&x, &dense).unwrap();
+    let gradient = DiagonalMultivariateNormal
+      .ln_diff_value(&x, &params)
+      .unwrap();
+    for (a, b) in gradient.iter().zip(expected.iter()) {
+      assert!((a - b).abs() < 1e-12);
+    }
+    assert!(DiagonalMultivariateNormal
+      .ln_diff_value(&vec![0.0], &params)
+      .is_err());
+  }
+}
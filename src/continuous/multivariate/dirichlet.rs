@@ -54,7 +54,7 @@ impl Distribution for Dirichlet {
     )
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let alpha = theta.alpha();
 
     let dirichlet = match RandDirichlet::new(alpha) {
@@ -0,0 +1,126 @@
+use crate::DistributionError;
+use opensrdk_linear_algebra::*;
+
+#[derive(thiserror::Error, Debug)]
+pub enum EllipticalParamsError {
+  #[error("dimension mismatch")]
+  DimensionMismatch,
+}
+
+/// Parameters of an elliptical distribution with location `mu` and scale `Sigma`.
+/// Implementors choose how `Sigma` is stored, e.g. by its Cholesky factor or by its diagonal.
+pub trait EllipticalParams {
+  fn mu(&self) -> &Vec<f64>;
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?\Sigma%5E%7B-1%7Dv)
+  fn sigma_inv_mul(&self, v: Vec<f64>) -> Result<Vec<f64>, DistributionError>;
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?%7C\Sigma%7C%5E%7B1/2%7D)
+  fn sigma_det_sqrt(&self) -> f64;
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?%28x-\mu%29%5ET\Sigma%5E%7B-1%7D%28x-\mu%29)
+  fn mahalanobis_squared(&self, x: &[f64]) -> Result<f64, DistributionError> {
+    let mu = self.mu();
+    if x.len() != mu.len() {
+      return Err(DistributionError::InvalidParameters(
+        EllipticalParamsError::DimensionMismatch.into(),
+      ));
+    }
+
+    let x_mu = x
+      .iter()
+      .zip(mu.iter())
+      .map(|(&xi, &mui)| xi - mui)
+      .collect::<Vec<_>>();
+    let sigma_inv_x_mu = self.sigma_inv_mul(x_mu.clone())?;
+
+    Ok(
+      x_mu
+        .iter()
+        .zip(sigma_inv_x_mu.iter())
+        .map(|(a, b)| a * b)
+        .sum(),
+    )
+  }
+}
+
+pub(crate) fn approx_eq_vec(a: &[f64], b: &[f64], tol: f64) -> bool {
+  a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() <= tol)
+}
+
+pub(crate) fn approx_eq_mat(a: &Matrix, b: &Matrix, tol: f64) -> bool {
+  a.rows() == b.rows() && a.cols() == b.cols() && approx_eq_vec(a.slice(), b.slice(), tol)
+}
+
+/// Zeros the upper triangle of a Cholesky factor, since `potrf` leaves it as it was.
+pub(crate) fn lower_triangle(mut l: Matrix) -> Matrix {
+  for c in 0..l.cols() {
+    for r in 0..c {
+      l[(r, c)] = 0.0;
+    }
+  }
+
+  l
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    DiagonalMultivariateNormalParams, EllipticalParams, MultivariateNormalParams,
+    MultivariateStudentTParams,
+  };
+  use opensrdk_linear_algebra::*;
+
+  #[test]
+  fn mahalanobis_squared() {
+    let lsigma = mat!(
+      2.0, 0.0;
+      0.5, 1.0
+    );
+    let mu = vec![1.0, -1.0];
+    let normal = MultivariateNormalParams::new(mu.clone(), lsigma.clone()).unwrap();
+    let student_t = MultivariateStudentTParams::new(mu.clone(), lsigma, 3.0).unwrap();
+
+    assert_eq!(normal.mahalanobis_squared(&mu).unwrap(), 0.0);
+    assert_eq!(student_t.mahalanobis_squared(&mu).unwrap(), 0.0);
+
+    // Sigma = [[4, 1], [1, 1.25]], so Sigma^{-1} = [[1.25, -1], [-1, 4]] / 4 and x - mu = (1, 1)
+    let x = vec![2.0, 0.0];
+    assert!((normal.mahalanobis_squared(&x).unwrap() - 3.25 / 4.0).abs() < 1e-12);
+    assert!(student_t.mahalanobis_squared(&[0.0, 0.0]).unwrap() > 0.0);
+    assert!(normal.mahalanobis_squared(&[0.0]).is_err());
+  }
+
+  #[test]
+  fn approx_eq() {
+    let sigma = mat!(
+      4.0, 1.0;
+      1.0, 3.0
+    );
+    let mu = vec![1.0, -1.0];
+    let params = MultivariateNormalParams::new(mu.clone(), sigma.potrf().unwrap()).unwrap();
+
+    // the refactorized covariance differs from the original factor only by rounding
+    let refit =
+      MultivariateNormalParams::new(mu.clone(), params.covariance().potrf().unwrap()).unwrap();
+    assert!(params.approx_eq(&refit, 1e-12));
+    assert!(!params.approx_eq(
+      &MultivariateNormalParams::new(vec![1.0, -1.1], params.lsigma().clone()).unwrap(),
+      1e-3
+    ));
+    assert!(!params.approx_eq(
+      &MultivariateNormalParams::isotropic(vec![0.0], 1.0).unwrap(),
+      1.0
+    ));
+
+    let student_t =
+      MultivariateStudentTParams::new(mu.clone(), params.lsigma().clone(), 3.0).unwrap();
+    let heavier = MultivariateStudentTParams::new(mu.clone(), refit.lsigma().clone(), 2.0).unwrap();
+    assert!(!student_t.approx_eq(&heavier, 1e-12));
+    assert!(student_t.approx_eq(&heavier, 1.0));
+
+    let diagonal = DiagonalMultivariateNormalParams::new(mu.clone(), vec![1.0, 2.0]).unwrap();
+    let noisy = DiagonalMultivariateNormalParams::new(mu, vec![1.0, 2.0 + 1e-14]).unwrap();
+    assert!(diagonal.approx_eq(&noisy, 1e-12));
+  }
+}
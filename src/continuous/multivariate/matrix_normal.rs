@@ -0,0 +1,185 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use opensrdk_linear_algebra::*;
+use rand::prelude::*;
+use rand_distr::StandardNormal;
+use std::{f64::consts::PI, ops::BitAnd, ops::Mul};
+
+/// # MatrixNormal
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathcal%7BMN%7D_%7Bn%2Cp%7D%28M%2C%20U%2C%20V%29)
+///
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7Bvec%7D%28X%29\sim\mathcal%7BN%7D%28\mathrm%7Bvec%7D%28M%29%2C%20V\otimes%20U%29)
+#[derive(Clone, Debug)]
+pub struct MatrixNormal;
+
+#[derive(thiserror::Error, Debug)]
+pub enum MatrixNormalError {
+  #[error("dimension mismatch")]
+  DimensionMismatch,
+}
+
+impl Distribution for MatrixNormal {
+  type T = Matrix;
+  type U = MatrixNormalParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let mean = theta.mean();
+    let lu = theta.lu();
+    let lv = theta.lv();
+
+    let n = mean.rows();
+    let p = mean.cols();
+
+    if x.rows() != n || x.cols() != p {
+      return Err(DistributionError::InvalidParameters(
+        MatrixNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    let x_m = x.clone() - mean.clone();
+    let v_inv_x_mt = lv.potrs(x_m.t())?;
+    let u_inv_x_m = lu.potrs(x_m)?;
+
+    let n = n as f64;
+    let p = p as f64;
+
+    // |U| = trdet(lu)^2, |V| = trdet(lv)^2
+    Ok(
+      -n * p / 2.0 * (2.0 * PI).ln()
+        - p * lu.trdet().ln()
+        - n * lv.trdet().ln()
+        - 0.5 * (v_inv_x_mt * u_inv_x_m).tr(),
+    )
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let mean = theta.mean();
+    let n = mean.rows();
+    let p = mean.cols();
+
+    let z = (0..n * p)
+      .map(|_| rng.sample(StandardNormal))
+      .collect::<Vec<_>>();
+    let z = Matrix::from(n, z);
+
+    // M + lu * Z * lvt
+    Ok(mean.clone() + theta.lu() * z * theta.lv().t())
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatrixNormalParams {
+  mean: Matrix,
+  lu: Matrix,
+  lv: Matrix,
+}
+
+impl MatrixNormalParams {
+  /// `lu` and `lv` are the lower Cholesky factors of the row covariance `U` and the column covariance `V`.
+  pub fn new(mean: Matrix, lu: Matrix, lv: Matrix) -> Result<Self, DistributionError> {
+    let n = mean.rows();
+    let p = mean.cols();
+
+    if lu.rows() != n || lu.cols() != n || lv.rows() != p || lv.cols() != p {
+      return Err(DistributionError::InvalidParameters(
+        MatrixNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    Ok(Self { mean, lu, lv })
+  }
+
+  pub fn mean(&self) -> &Matrix {
+    &self.mean
+  }
+
+  pub fn lu(&self) -> &Matrix {
+    &self.lu
+  }
+
+  pub fn lv(&self) -> &Matrix {
+    &self.lv
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for MatrixNormal
+where
+  Rhs: Distribution<T = TRhs, U = MatrixNormalParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, Matrix, TRhs, MatrixNormalParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for MatrixNormal
+where
+  Rhs: Distribution<T = MatrixNormalParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, Matrix, MatrixNormalParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    Distribution, MatrixNormal, MatrixNormalParams, MultivariateNormal, MultivariateNormalParams,
+  };
+  use opensrdk_linear_algebra::*;
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let mean = Matrix::from(3, vec![1.0, 2.0, 3.0, -1.0, 0.0, 1.0]);
+    let lu = Matrix::from(3, vec![1.0, 0.5, 0.2, 0.0, 1.2, -0.3, 0.0, 0.0, 0.8]);
+    let lv = Matrix::from(2, vec![0.7, 0.4, 0.0, 1.1]);
+    let params = MatrixNormalParams::new(mean.clone(), lu.clone(), lv.clone()).unwrap();
+
+    // vec(X) ~ N(vec(M), V ⊗ U), and lv ⊗ lu is the lower Cholesky factor of V ⊗ U
+    let vec_params =
+      MultivariateNormalParams::new(mean.vec(), KroneckerMatrices::new(vec![lv, lu]).prod())
+        .unwrap();
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let x = MatrixNormal.sample_n(20000, &params, &mut rng).unwrap();
+
+    for xi in x.iter().take(5) {
+      let p = MatrixNormal.p(xi, &params).unwrap();
+      let vec_p = MultivariateNormal
+        .p(&xi.clone().vec(), &vec_params)
+        .unwrap();
+
+      assert!((p - vec_p).abs() < 1e-10 * vec_p.max(1.0));
+    }
+
+    // sample covariance of vec(X) against V ⊗ U
+    let sigma = vec_params.lsigma() * vec_params.lsigma().t();
+    let vec_mu = vec_params.mu();
+    let len = vec_mu.len();
+    for i in 0..len {
+      for j in 0..len {
+        let cov = x
+          .iter()
+          .map(|xk| (xk.slice()[i] - vec_mu[i]) * (xk.slice()[j] - vec_mu[j]))
+          .sum::<f64>()
+          / x.len() as f64;
+
+        assert!((cov - sigma[(i, j)]).abs() < 0.05);
+      }
+    }
+
+    assert!(
+      MatrixNormalParams::new(Matrix::new(3, 2), Matrix::new(2, 2), Matrix::new(2, 2)).is_err()
+    );
+  }
+}
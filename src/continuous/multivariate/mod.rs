@@ -1,12 +1,19 @@
+pub mod diagonal_multivariate_normal;
 pub mod dirichlet;
+pub mod elliptical;
 pub mod inverse_wishart;
+pub mod matrix_normal;
 pub mod multivariate_normal;
 pub mod multivariate_student_t;
 pub mod normal_inverse_wishart;
+mod sobol;
 pub mod wishart;
 
+pub use diagonal_multivariate_normal::*;
 pub use dirichlet::*;
+pub use elliptical::*;
 pub use inverse_wishart::*;
+pub use matrix_normal::*;
 pub use multivariate_normal::*;
 pub use multivariate_student_t::*;
 pub use normal_inverse_wishart::*;
@@ -1,10 +1,22 @@
+use super::{
+  elliptical::{approx_eq_mat, approx_eq_vec, lower_triangle},
+  sobol,
+};
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{
+  DependentJoint, Distribution, EllipticalParams, IndependentJoint, NormalParams, RandomVariable,
+  ValueDifferentiableDistribution,
+};
 use opensrdk_linear_algebra::*;
 use rand::prelude::*;
 use rand_distr::StandardNormal;
-use rayon::prelude::*;
-use std::{f64::consts::PI, ops::BitAnd, ops::Mul};
+use special::Error;
+use std::{
+  convert::TryFrom,
+  f64::consts::{PI, SQRT_2},
+  ops::BitAnd,
+  ops::Mul,
+};
 
 /// # MultivariateNormal
 /// ![tex](https://latex.codecogs.com/svg.latex?\mathcal%7BN%7D%28\mu%2C%20\Sigma%29)
@@ -15,6 +27,10 @@ pub struct MultivariateNormal;
 pub enum MultivariateNormalError {
   #[error("dimension mismatch")]
   DimensionMismatch,
+  #[error("'σ' must be positive")]
+  SigmaMustBePositive,
+  #[error("Sobol sequence supports up to {0} dimensions")]
+  TooManyDimensionsForSobol(usize),
 }
 
 impl Distribution for MultivariateNormal {
@@ -34,33 +50,149 @@ impl Distribution for MultivariateNormal {
     }
     let p = p as f64;
 
-    let x_mu = x
-      .par_iter()
-      .zip(mu.par_iter())
-      .map(|(&xi, &mui)| xi - mui)
-      .collect::<Vec<_>>()
-      .col_mat();
-
     Ok(
       1.0 / ((2.0 * PI).powf(p / 2.0) * lsigma.trdet())
-        * (-1.0 / 2.0 * (x_mu.t() * lsigma.potrs(x_mu)?)[0][0]).exp(),
+        * (-1.0 / 2.0 * theta.mahalanobis_squared(x)?).exp(),
     )
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  /// Stays finite where `p` underflows to 0 in high dimensions.
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
     let mu = theta.mu();
     let lsigma = theta.lsigma();
-    let p = mu.len();
 
-    let z = (0..p)
-      .into_iter()
+    let p = x.len();
+
+    if p != mu.len() {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    let ln_det = (0..p).map(|i| lsigma[(i, i)].ln()).sum::<f64>();
+
+    Ok(-(p as f64) / 2.0 * (2.0 * PI).ln() - ln_det - 1.0 / 2.0 * theta.mahalanobis_squared(x)?)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let z = (0..theta.lsigma().cols())
       .map(|_| rng.sample(StandardNormal))
       .collect::<Vec<_>>();
 
-    let y = mu.clone().col_mat().gemm(lsigma, &z.col_mat(), 1.0, 1.0)?;
+    self.sample_from_z(theta, &z)
+  }
+}
+
+impl ValueDifferentiableDistribution for MultivariateNormal {
+  /// ![tex](https://latex.codecogs.com/svg.latex?-\Sigma%5E%7B-1%7D%28x-\mu%29) by substitution on the Cholesky factor the params already hold, so each call costs ![tex](https://latex.codecogs.com/svg.latex?O%28n%5E2%29) and nothing is refactorized.
+  /// Use `DiagonalMultivariateNormal` for the `O(n)` diagonal case.
+  fn ln_diff_value(&self, x: &Self::T, theta: &Self::U) -> Result<Vec<f64>, DistributionError> {
+    let mu = theta.mu();
+    if x.len() != mu.len() {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    let x_mu = x.iter().zip(mu.iter()).map(|(xi, mui)| xi - mui).collect();
+
+    Ok(theta.sigma_inv_mul(x_mu)?.into_iter().map(|v| -v).collect())
+  }
+}
+
+impl MultivariateNormal {
+  /// ![tex](https://latex.codecogs.com/svg.latex?\mu%2BL\mathbf%7Bz%7D) for a given white noise `z`, the draw `sample` makes internally.
+  pub fn sample_from_z(
+    &self,
+    theta: &MultivariateNormalParams,
+    z: &[f64],
+  ) -> Result<Vec<f64>, DistributionError> {
+    let lsigma = theta.lsigma();
+    if z.len() != lsigma.cols() {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    let y = theta
+      .mu()
+      .clone()
+      .col_mat()
+      .gemm(lsigma, &z.to_vec().col_mat(), 1.0, 1.0)?;
 
     Ok(y.vec())
   }
+
+  /// Draws the antithetic pair `(mu + L z, mu - L z)` from a single `z`.
+  pub fn sample_antithetic(
+    &self,
+    theta: &MultivariateNormalParams,
+    rng: &mut dyn RngCore,
+  ) -> Result<(Vec<f64>, Vec<f64>), DistributionError> {
+    let mu = theta.mu();
+    let lsigma = theta.lsigma();
+
+    let z = (0..mu.len())
+      .map(|_| rng.sample(StandardNormal))
+      .collect::<Vec<f64>>();
+    let lz = (lsigma * z.col_mat()).vec();
+
+    Ok((
+      mu.iter()
+        .zip(lz.iter())
+        .map(|(mui, lzi)| mui + lzi)
+        .collect(),
+      mu.iter()
+        .zip(lz.iter())
+        .map(|(mui, lzi)| mui - lzi)
+        .collect(),
+    ))
+  }
+
+  /// Draws `n` points of a randomly shifted Sobol sequence mapped through the inverse normal CDF.
+  /// The random shift keeps each point marginally distributed as the target.
+  pub fn sample_sobol(
+    &self,
+    n: usize,
+    theta: &MultivariateNormalParams,
+    rng: &mut dyn RngCore,
+  ) -> Result<Vec<Vec<f64>>, DistributionError> {
+    let mu = theta.mu();
+    let lsigma = theta.lsigma();
+    let p = mu.len();
+
+    if sobol::MAX_DIM < p {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateNormalError::TooManyDimensionsForSobol(sobol::MAX_DIM).into(),
+      ));
+    }
+
+    let shift = (0..p)
+      .map(|_| rng.gen_range(0.0..1.0))
+      .collect::<Vec<f64>>();
+
+    sobol::sobol(n, p)
+      .into_iter()
+      .map(|u| {
+        let z = u
+          .iter()
+          .zip(shift.iter())
+          .map(|(ui, si)| {
+            // Φ⁻¹, kept away from 0 and 1
+            let ui = ((ui + si) % 1.0).clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+            SQRT_2 * (2.0 * ui - 1.0).inv_error()
+          })
+          .collect::<Vec<_>>();
+
+        Ok(
+          mu.clone()
+            .col_mat()
+            .gemm(lsigma, &z.col_mat(), 1.0, 1.0)?
+            .vec(),
+        )
+      })
+      .collect()
+  }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -73,6 +205,7 @@ impl MultivariateNormalParams {
   /// # Multivariate normal
   /// `L` is needed as second argument under decomposition `Sigma = L * L^T`
   /// l_sigma = sigma.potrf()?;
+  /// Only the lower triangle of `lsigma` is read.
   pub fn new(mu: Vec<f64>, lsigma: Matrix) -> Result<Self, DistributionError> {
     let p = mu.len();
     if p != lsigma.rows() || p != lsigma.cols() {
@@ -81,7 +214,24 @@ impl MultivariateNormalParams {
       ));
     }
 
-    Ok(Self { mu, lsigma })
+    Ok(Self {
+      mu,
+      lsigma: lower_triangle(lsigma),
+    })
+  }
+
+  /// # Isotropic multivariate normal
+  /// `Sigma = sigma^2 * I`
+  pub fn isotropic(mu: Vec<f64>, sigma: f64) -> Result<Self, DistributionError> {
+    if sigma <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateNormalError::SigmaMustBePositive.into(),
+      ));
+    }
+
+    let lsigma = sigma * Matrix::identity(mu.len());
+
+    Self::new(mu, lsigma)
   }
 
   pub fn mu(&self) -> &Vec<f64> {
@@ -92,11 +242,107 @@ impl MultivariateNormalParams {
     &self.lsigma
   }
 
+  /// `Sigma = L * L^T`
+  pub fn covariance(&self) -> Matrix {
+    &self.lsigma * self.lsigma.t()
+  }
+
+  /// `Sigma^{-1}` from the Cholesky factor.
+  pub fn precision(&self) -> Result<Matrix, DistributionError> {
+    let p = self.lsigma.rows();
+    let mut precision = self.lsigma.clone().potri()?;
+
+    // potri only fills the lower triangle
+    for c in 0..p {
+      for r in 0..c {
+        precision[(r, c)] = precision[(c, r)];
+      }
+    }
+
+    Ok(precision)
+  }
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?L%5E%7B-1%7D%28f%20-%20\mu%29) by forward substitution on the lower triangle of `lsigma`.
+  pub fn whiten(&self, f: &[f64]) -> Result<Vec<f64>, DistributionError> {
+    let p = self.mu.len();
+    if f.len() != p {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    let mut eta = vec![0.0; p];
+    for r in 0..p {
+      let l_eta = (0..r).map(|c| self.lsigma[(r, c)] * eta[c]).sum::<f64>();
+      eta[r] = (f[r] - self.mu[r] - l_eta) / self.lsigma[(r, r)];
+    }
+
+    Ok(eta)
+  }
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?\mu%20%2B%20L\eta), the inverse of `whiten`.
+  pub fn unwhiten(&self, eta: &[f64]) -> Result<Vec<f64>, DistributionError> {
+    let p = self.mu.len();
+    if eta.len() != p {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    Ok(
+      (0..p)
+        .map(|r| self.mu[r] + (0..=r).map(|c| self.lsigma[(r, c)] * eta[c]).sum::<f64>())
+        .collect(),
+    )
+  }
+
+  /// Elementwise comparison of `mu` and `lsigma` within `tol`.
+  pub fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+    approx_eq_vec(&self.mu, &other.mu, tol) && approx_eq_mat(&self.lsigma, &other.lsigma, tol)
+  }
+
   pub fn eject(self) -> (Vec<f64>, Matrix) {
     (self.mu, self.lsigma)
   }
 }
 
+impl EllipticalParams for MultivariateNormalParams {
+  fn mu(&self) -> &Vec<f64> {
+    &self.mu
+  }
+
+  fn sigma_inv_mul(&self, v: Vec<f64>) -> Result<Vec<f64>, DistributionError> {
+    Ok(self.lsigma.potrs(v.col_mat())?.vec())
+  }
+
+  fn sigma_det_sqrt(&self) -> f64 {
+    self.lsigma.trdet()
+  }
+}
+
+impl From<NormalParams> for MultivariateNormalParams {
+  fn from(params: NormalParams) -> Self {
+    Self {
+      mu: vec![params.mu()],
+      lsigma: Matrix::from(1, vec![params.sigma()]),
+    }
+  }
+}
+
+impl TryFrom<MultivariateNormalParams> for NormalParams {
+  type Error = DistributionError;
+
+  fn try_from(params: MultivariateNormalParams) -> Result<Self, Self::Error> {
+    if params.mu.len() != 1 {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateNormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    NormalParams::new(params.mu[0], params.lsigma[0][0])
+  }
+}
+
 impl<Rhs, TRhs> Mul<Rhs> for MultivariateNormal
 where
   Rhs: Distribution<T = TRhs, U = MultivariateNormalParams>,
@@ -123,9 +369,13 @@ where
 
 #[cfg(test)]
 mod tests {
-  use crate::{Distribution, MultivariateNormal, MultivariateNormalParams};
+  use crate::{
+    Distribution, MultivariateNormal, MultivariateNormalParams, Normal, NormalParams,
+    ValueDifferentiableDistribution,
+  };
   use opensrdk_linear_algebra::*;
   use rand::prelude::*;
+  use std::convert::TryFrom;
   #[test]
   fn it_works() {
     let n = MultivariateNormal;
@@ -152,4 +402,195 @@ mod tests {
 
     println!("{:#?}", x);
   }
+
+  #[test]
+  fn from_normal_params() {
+    let params = NormalParams::new(2.0, 3.0).unwrap();
+    let multivariate_params = MultivariateNormalParams::from(params.clone());
+
+    for &x in [-4.0, 0.0, 2.0, 7.5].iter() {
+      let p = Normal.p(&x, &params).unwrap();
+      let multivariate_p = MultivariateNormal
+        .p(&vec![x], &multivariate_params)
+        .unwrap();
+
+      assert!((p - multivariate_p).abs() < 1e-12);
+    }
+
+    assert_eq!(NormalParams::try_from(multivariate_params).unwrap(), params);
+  }
+
+  #[test]
+  fn isotropic() {
+    let mu = vec![1.0, -2.0, 0.5];
+    let sigma = 1.5;
+    let params = MultivariateNormalParams::isotropic(mu.clone(), sigma).unwrap();
+
+    let x = vec![0.0, -1.0, 2.0];
+    let p = MultivariateNormal.p(&x, &params).unwrap();
+    let expected = x
+      .iter()
+      .zip(mu.iter())
+      .map(|(&xi, &mui)| {
+        Normal
+          .p(&xi, &NormalParams::new(mui, sigma).unwrap())
+          .unwrap()
+      })
+      .product::<f64>();
+
+    assert!((p - expected).abs() < 1e-12);
+    assert!((MultivariateNormal.ln_p(&x, &params).unwrap() - expected.ln()).abs() < 1e-10);
+    assert!(MultivariateNormalParams::isotropic(mu, 0.0).is_err());
+  }
+
+  #[test]
+  fn covariance() {
+    let sigma = mat!(
+      4.0, 1.0, 0.5;
+      1.0, 3.0, 0.2;
+      0.5, 0.2, 2.0
+    );
+    let params =
+      MultivariateNormalParams::new(vec![0.0; 3], sigma.clone().potrf().unwrap()).unwrap();
+
+    // potrf leaves the upper triangle of sigma in the factor
+    assert!((0..3).all(|c| (0..c).all(|r| params.lsigma()[(r, c)] == 0.0)));
+
+    let covariance = params.covariance();
+    for r in 0..3 {
+      for c in 0..3 {
+        assert!((covariance[(r, c)] - covariance[(c, r)]).abs() < 1e-12);
+        assert!((covariance[(r, c)] - sigma[(r, c)]).abs() < 1e-12);
+      }
+    }
+    assert!(covariance.clone().potrf().is_ok());
+
+    let identity = &params.precision().unwrap() * covariance;
+    for r in 0..3 {
+      for c in 0..3 {
+        let expected = if r == c { 1.0 } else { 0.0 };
+        assert!((identity[(r, c)] - expected).abs() < 1e-12);
+      }
+    }
+  }
+
+  #[test]
+  fn whiten() {
+    let lsigma = mat!(
+      2.0, 0.0, 0.0;
+      0.5, 1.0, 0.0;
+      -0.3, 0.2, 0.7
+    );
+    let params = MultivariateNormalParams::new(vec![1.0, -1.0, 0.5], lsigma).unwrap();
+    let eta = vec![0.3, -1.2, 2.0];
+
+    let f = params.unwhiten(&eta).unwrap();
+    let whitened = params.whiten(&f).unwrap();
+    for (a, b) in eta.iter().zip(whitened.iter()) {
+      assert!((a - b).abs() < 1e-12);
+    }
+    assert!(params.whiten(&[0.0]).is_err());
+    assert!(params.unwhiten(&[0.0]).is_err());
+  }
+
+  #[test]
+  fn sample_from_z() {
+    let lsigma = mat!(
+      2.0, 0.0;
+      0.5, 1.0
+    );
+    let params = MultivariateNormalParams::new(vec![1.0, -1.0], lsigma).unwrap();
+    let z = vec![0.3, -1.2];
+
+    let x = MultivariateNormal.sample_from_z(&params, &z).unwrap();
+    assert_eq!(x, MultivariateNormal.sample_from_z(&params, &z).unwrap());
+    assert!((x[0] - 1.6).abs() < 1e-12);
+    assert!((x[1] + 2.05).abs() < 1e-12);
+    assert_eq!(
+      MultivariateNormal
+        .sample_from_z(&params, &[0.0, 0.0])
+        .unwrap(),
+      *params.mu()
+    );
+    assert!(MultivariateNormal.sample_from_z(&params, &[0.0]).is_err());
+  }
+
+  #[test]
+  fn variance_reduction() {
+    let lsigma = Matrix::from(2, vec![1.0, 0.5, 0.0, 0.8]);
+    let params = MultivariateNormalParams::new(vec![0.5, -0.5], lsigma).unwrap();
+    let f = |x: &Vec<f64>| (0.5 * x[0]).exp() + x[1];
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let n = 64;
+    let reps = 200;
+    let var = |estimates: Vec<f64>| {
+      let mean = estimates.iter().sum::<f64>() / estimates.len() as f64;
+      estimates.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / estimates.len() as f64
+    };
+
+    let iid = (0..reps)
+      .map(|_| {
+        let x = MultivariateNormal.sample_n(n, &params, &mut rng).unwrap();
+        x.iter().map(f).sum::<f64>() / n as f64
+      })
+      .collect::<Vec<_>>();
+    let antithetic = (0..reps)
+      .map(|_| {
+        (0..n / 2)
+          .map(|_| {
+            let (a, b) = MultivariateNormal
+              .sample_antithetic(&params, &mut rng)
+              .unwrap();
+            f(&a) + f(&b)
+          })
+          .sum::<f64>()
+          / n as f64
+      })
+      .collect::<Vec<_>>();
+    let sobol = (0..reps)
+      .map(|_| {
+        let x = MultivariateNormal
+          .sample_sobol(n, &params, &mut rng)
+          .unwrap();
+        x.iter().map(f).sum::<f64>() / n as f64
+      })
+      .collect::<Vec<_>>();
+
+    let iid = var(iid);
+    assert!(var(antithetic) < iid);
+    assert!(var(sobol) < iid);
+
+    let high = MultivariateNormalParams::isotropic(vec![0.0; 11], 1.0).unwrap();
+    assert!(MultivariateNormal.sample_sobol(1, &high, &mut rng).is_err());
+  }
+
+  #[test]
+  fn ln_diff_value() {
+    let n = 300;
+    let a = Matrix::from(n, (0..n * n).map(|i| (i as f64 * 0.37).sin()).collect());
+    let sigma = &a * a.t() + n as f64 * Matrix::identity(n);
+    let mu = (0..n).map(|i| (i as f64).cos()).collect::<Vec<_>>();
+    let params = MultivariateNormalParams::new(mu.clone(), sigma.potrf().unwrap()).unwrap();
+    let x = (0..n).map(|i| (i as f64 * 0.1).sin()).collect::<Vec<_>>();
+
+    let x_mu = x
+      .iter()
+      .zip(mu.iter())
+      .map(|(xi, mui)| xi - mui)
+      .collect::<Vec<_>>();
+    let expected = (-1.0 * (params.precision().unwrap() * x_mu.clone().col_mat())).vec();
+
+    // two triangular solves with the stored factor, without forming the inverse
+    let solved = params.lsigma().potrs(x_mu.col_mat()).unwrap().vec();
+    let gradient = MultivariateNormal.ln_diff_value(&x, &params).unwrap();
+    for ((g, s), e) in gradient.iter().zip(solved.iter()).zip(expected.iter()) {
+      assert_eq!(*g, -s);
+      assert!((g - e).abs() < 1e-10);
+    }
+
+    assert!(MultivariateNormal
+      .ln_diff_value(&vec![0.0], &params)
+      .is_err());
+  }
 }
@@ -1,9 +1,12 @@
+use super::elliptical::{approx_eq_mat, approx_eq_vec, lower_triangle};
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{
+  check_not_empty, ConditionDifferentiableDistribution, DependentJoint, Distribution,
+  EllipticalParams, IndependentJoint, RandomVariable, ValueDifferentiableDistribution,
+};
 use opensrdk_linear_algebra::*;
 use rand::prelude::*;
 use rand_distr::StudentT as RandStudentT;
-use rayon::prelude::*;
 use special::Gamma;
 use std::f64::consts::PI;
 use std::{ops::BitAnd, ops::Mul};
@@ -35,23 +38,15 @@ impl Distribution for MultivariateStudentT {
       ));
     }
     let p = p as f64;
-    let nu = nu;
-
-    let x_mu = x
-      .par_iter()
-      .zip(mu.par_iter())
-      .map(|(&xi, &mui)| xi - mui)
-      .collect::<Vec<_>>()
-      .col_mat();
 
     Ok(
       (Gamma::gamma((nu + p) / 2.0)
         / (Gamma::gamma(nu / 2.0) * nu.powf(p / 2.0) * PI.powf(p / 2.0) * lsigma.trdet()))
-        * (1.0 + (x_mu.t() * lsigma.potrs(x_mu)?)[0][0] / nu).powf(-(nu + p) / 2.0),
+        * (1.0 + theta.mahalanobis_squared(x)? / nu).powf(-(nu + p) / 2.0),
     )
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let mu = theta.mu();
     let lsigma = theta.lsigma();
     let nu = theta.nu();
@@ -72,6 +67,85 @@ impl Distribution for MultivariateStudentT {
   }
 }
 
+impl ValueDifferentiableDistribution for MultivariateStudentT {
+  fn ln_diff_value(&self, x: &Self::T, theta: &Self::U) -> Result<Vec<f64>, DistributionError> {
+    let mu = theta.mu();
+    let nu = theta.nu();
+
+    if x.len() != mu.len() {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateStudentTError::DimensionMismatch.into(),
+      ));
+    }
+    let p = x.len() as f64;
+
+    let x_mu = x.iter().zip(mu.iter()).map(|(xi, mui)| xi - mui).collect();
+    let coefficient = -(nu + p) / (nu + theta.mahalanobis_squared(x)?);
+
+    Ok(
+      theta
+        .sigma_inv_mul(x_mu)?
+        .into_iter()
+        .map(|v| coefficient * v)
+        .collect(),
+    )
+  }
+}
+
+impl ConditionDifferentiableDistribution for MultivariateStudentT {
+  /// Gradient with respect to `mu`, then the lower triangle of `lsigma` in column-major order, then `nu`.
+  fn ln_diff_condition(&self, x: &Self::T, theta: &Self::U) -> Result<Vec<f64>, DistributionError> {
+    let mu = theta.mu();
+    let lsigma = theta.lsigma();
+    let nu = theta.nu();
+
+    let n = x.len();
+    if n != mu.len() {
+      return Err(DistributionError::InvalidParameters(
+        MultivariateStudentTError::DimensionMismatch.into(),
+      ));
+    }
+    let p = n as f64;
+
+    let x_mu = x
+      .iter()
+      .zip(mu.iter())
+      .map(|(&xi, &mui)| xi - mui)
+      .collect::<Vec<_>>();
+    // a = Sigma^{-1} (x - mu)
+    let a = theta.sigma_inv_mul(x_mu.clone())?;
+    let q = x_mu.iter().zip(a.iter()).map(|(r, a)| r * a).sum::<f64>();
+    let w = (nu + p) / (nu + q);
+
+    let d_mu = a.iter().map(|ai| w * ai);
+
+    // ∂q/∂L = -2 a a^T L, so ∂ln p/∂L = w a a^T L - diag(1 / L_ii)
+    let a_t_l = (0..n)
+      .map(|c| (c..n).map(|r| a[r] * lsigma[(r, c)]).sum::<f64>())
+      .collect::<Vec<_>>();
+    let d_lsigma = (0..n).flat_map(|c| {
+      let a = &a;
+      let a_t_l = &a_t_l;
+      (c..n).map(move |r| {
+        let d = w * a[r] * a_t_l[c];
+        if r == c {
+          d - 1.0 / lsigma[(r, r)]
+        } else {
+          d
+        }
+      })
+    });
+
+    let d_nu = ((nu + p) / 2.0).digamma() / 2.0
+      - (nu / 2.0).digamma() / 2.0
+      - p / (2.0 * nu)
+      - (q / nu).ln_1p() / 2.0
+      + (nu + p) * q / (2.0 * nu * (nu + q));
+
+    Ok(d_mu.chain(d_lsigma).chain(std::iter::once(d_nu)).collect())
+  }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct MultivariateStudentTParams {
   mu: Vec<f64>,
@@ -83,6 +157,7 @@ impl MultivariateStudentTParams {
   /// # Multivariate student t
   /// `L` is needed as second argument under decomposition `Sigma = L * L^T`
   /// lsigma = sigma.potrf()?;
+  /// Only the lower triangle of `lsigma` is read.
   pub fn new(mu: Vec<f64>, lsigma: Matrix, nu: f64) -> Result<Self, DistributionError> {
     let n = mu.len();
     if n != lsigma.rows() || n != lsigma.cols() {
@@ -91,7 +166,11 @@ impl MultivariateStudentTParams {
       ));
     }
 
-    Ok(Self { mu, lsigma, nu })
+    Ok(Self {
+      mu,
+      lsigma: lower_triangle(lsigma),
+      nu,
+    })
   }
 
   pub fn mu(&self) -> &Vec<f64> {
@@ -105,6 +184,143 @@ impl MultivariateStudentTParams {
   pub fn nu(&self) -> f64 {
     self.nu
   }
+
+  /// Elementwise comparison of `mu`, `lsigma` and `nu` within `tol`.
+  pub fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+    approx_eq_vec(&self.mu, &other.mu, tol)
+      && approx_eq_mat(&self.lsigma, &other.lsigma, tol)
+      && (self.nu - other.nu).abs() <= tol
+  }
+}
+
+impl EllipticalParams for MultivariateStudentTParams {
+  fn mu(&self) -> &Vec<f64> {
+    &self.mu
+  }
+
+  fn sigma_inv_mul(&self, v: Vec<f64>) -> Result<Vec<f64>, DistributionError> {
+    Ok(self.lsigma.potrs(v.col_mat())?.vec())
+  }
+
+  fn sigma_det_sqrt(&self) -> f64 {
+    self.lsigma.trdet()
+  }
+}
+
+/// Maximum likelihood `mu`, `Sigma` and `nu` by EM over the latent precision scales `w_i`, with expectations
+/// ![tex](https://latex.codecogs.com/svg.latex?w_i%3D\frac%7B\nu%2Bp%7D%7B\nu%2B%28x_i-\mu%29%5ET\Sigma%5E%7B-1%7D%28x_i-\mu%29%7D)
+///
+/// `nu` is updated by bisecting its M-step equation in `[1e-2, 1e3]` and stays at the upper end when the data are not heavy-tailed enough to have a root.
+/// The iteration stops after `max_iter` steps or once all parameters move by less than `1e-8`.
+pub fn fit_student_t(
+  data: &[Vec<f64>],
+  max_iter: usize,
+) -> Result<MultivariateStudentTParams, DistributionError> {
+  check_not_empty(data)?;
+  let n = data.len() as f64;
+  let p = data[0].len();
+  if data.iter().any(|xi| xi.len() != p) {
+    return Err(DistributionError::InvalidParameters(
+      MultivariateStudentTError::DimensionMismatch.into(),
+    ));
+  }
+
+  let mut mu = weighted_mean(data, &vec![1.0; data.len()]);
+  let mut sigma = weighted_scatter(data, &mu, &vec![1.0; data.len()]);
+  let mut nu = 10.0f64;
+
+  for _ in 0..max_iter {
+    let lsigma = sigma.clone().potrf()?;
+    let w = data
+      .iter()
+      .map(|xi| {
+        let d = xi
+          .iter()
+          .zip(mu.iter())
+          .map(|(a, b)| a - b)
+          .collect::<Vec<_>>();
+        let delta = d
+          .iter()
+          .zip(lsigma.potrs(d.clone().col_mat())?.vec().iter())
+          .map(|(a, b)| a * b)
+          .sum::<f64>();
+        Ok((nu + p as f64) / (nu + delta))
+      })
+      .collect::<Result<Vec<_>, DistributionError>>()?;
+
+    let new_mu = weighted_mean(data, &w);
+    let new_sigma = weighted_scatter(data, &new_mu, &w);
+
+    // ln(ν/2) - ψ(ν/2) + 1 + c = 0 decreases in ν
+    let c = w.iter().map(|wi| wi.ln() - wi).sum::<f64>() / n + ((nu + p as f64) / 2.0).digamma()
+      - ((nu + p as f64) / 2.0).ln();
+    let f = |nu: f64| (nu / 2.0).ln() - (nu / 2.0).digamma() + 1.0 + c;
+    let (mut lower, mut upper) = (1e-2f64, 1e3f64);
+    let new_nu = if 0.0 < f(upper) {
+      upper
+    } else {
+      for _ in 0..100 {
+        let mid = (lower * upper).sqrt();
+        if 0.0 < f(mid) {
+          lower = mid;
+        } else {
+          upper = mid;
+        }
+      }
+      (lower * upper).sqrt()
+    };
+
+    let change = mu
+      .iter()
+      .zip(new_mu.iter())
+      .map(|(a, b)| (a - b).abs())
+      .chain(
+        sigma
+          .slice()
+          .iter()
+          .zip(new_sigma.slice().iter())
+          .map(|(a, b)| (a - b).abs()),
+      )
+      .fold((nu - new_nu).abs(), f64::max);
+    mu = new_mu;
+    sigma = new_sigma;
+    nu = new_nu;
+    if change < 1e-8 {
+      break;
+    }
+  }
+
+  MultivariateStudentTParams::new(mu, sigma.potrf()?, nu)
+}
+
+fn weighted_mean(data: &[Vec<f64>], w: &[f64]) -> Vec<f64> {
+  let total = w.iter().sum::<f64>();
+
+  (0..data[0].len())
+    .map(|d| {
+      data
+        .iter()
+        .zip(w.iter())
+        .map(|(xi, wi)| wi * xi[d])
+        .sum::<f64>()
+        / total
+    })
+    .collect()
+}
+
+/// ![tex](https://latex.codecogs.com/svg.latex?\frac%7B1%7D%7Bn%7D\sum_iw_i%28x_i-\mu%29%28x_i-\mu%29%5ET)
+fn weighted_scatter(data: &[Vec<f64>], mu: &[f64], w: &[f64]) -> Matrix {
+  let p = mu.len();
+  let mut scatter = Matrix::new(p, p);
+  for (xi, wi) in data.iter().zip(w.iter()) {
+    for r in 0..p {
+      for c in 0..p {
+        scatter[(r, c)] += wi * (xi[r] - mu[r]) * (xi[c] - mu[c]);
+      }
+    }
+  }
+
+  (1.0 / data.len() as f64) * scatter
 }
 
 impl<Rhs, TRhs> Mul<Rhs> for MultivariateStudentT
@@ -133,8 +349,132 @@ where
 
 #[cfg(test)]
 mod tests {
+  use crate::{
+    fit_student_t, ConditionDifferentiableDistribution, Distribution, MultivariateStudentT,
+    MultivariateStudentTParams, ValueDifferentiableDistribution,
+  };
+  use opensrdk_linear_algebra::*;
+  use rand::prelude::*;
+  use rand_distr::{Gamma, StandardNormal};
+
   #[test]
   fn it_works() {
     assert_eq!(2 + 2, 4);
   }
+
+  #[test]
+  fn ln_diff_value() {
+    let sigma = mat!(
+      2.0, 0.5;
+      0.5, 1.0
+    );
+    let params =
+      MultivariateStudentTParams::new(vec![1.0, -1.0], sigma.potrf().unwrap(), 3.0).unwrap();
+    let x = vec![0.3, 0.4];
+
+    let grad = MultivariateStudentT.ln_diff_value(&x, &params).unwrap();
+
+    let h = 1e-6;
+    for i in 0..x.len() {
+      let mut x_plus = x.clone();
+      let mut x_minus = x.clone();
+      x_plus[i] += h;
+      x_minus[i] -= h;
+      let numerical = (MultivariateStudentT.ln_p(&x_plus, &params).unwrap()
+        - MultivariateStudentT.ln_p(&x_minus, &params).unwrap())
+        / (2.0 * h);
+
+      assert!((grad[i] - numerical).abs() < 1e-6);
+    }
+  }
+
+  #[test]
+  fn ln_diff_condition() {
+    let mu = vec![1.0, -1.0];
+    let lsigma = mat!(
+      1.4, 0.0;
+      0.3, 0.9
+    );
+    let nu = 3.0;
+    let x = vec![0.3, 0.4];
+    let ln_p = |mu: &Vec<f64>, lsigma: &Matrix, nu: f64| {
+      MultivariateStudentT
+        .ln_p(
+          &x,
+          &MultivariateStudentTParams::new(mu.clone(), lsigma.clone(), nu).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let grad = MultivariateStudentT
+      .ln_diff_condition(
+        &x,
+        &MultivariateStudentTParams::new(mu.clone(), lsigma.clone(), nu).unwrap(),
+      )
+      .unwrap();
+    assert_eq!(grad.len(), 2 + 3 + 1);
+
+    let h = 1e-6;
+    let central = |plus: f64, minus: f64| (plus - minus) / (2.0 * h);
+
+    for i in 0..2 {
+      let (mut plus, mut minus) = (mu.clone(), mu.clone());
+      plus[i] += h;
+      minus[i] -= h;
+      let numerical = central(ln_p(&plus, &lsigma, nu), ln_p(&minus, &lsigma, nu));
+      assert!((grad[i] - numerical).abs() < 1e-6);
+    }
+
+    for (k, &(r, c)) in [(0, 0), (1, 0), (1, 1)].iter().enumerate() {
+      let (mut plus, mut minus) = (lsigma.clone(), lsigma.clone());
+      plus[(r, c)] += h;
+      minus[(r, c)] -= h;
+      let numerical = central(ln_p(&mu, &plus, nu), ln_p(&mu, &minus, nu));
+      assert!((grad[2 + k] - numerical).abs() < 1e-6);
+    }
+
+    let numerical = central(ln_p(&mu, &lsigma, nu + h), ln_p(&mu, &lsigma, nu - h));
+    assert!((grad[5] - numerical).abs() < 1e-6);
+  }
+
+  #[test]
+  fn fit() {
+    let mu = [1.0, -2.0];
+    let sigma = mat!(
+      2.0, 0.6;
+      0.6, 1.0
+    );
+    let lsigma = sigma.clone().potrf().unwrap();
+    let nu = 4.0;
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    // x = mu + L z / sqrt(w) with z ~ N(0, I) and w ~ Gamma(ν/2, 2/ν) shared across dimensions
+    let gamma = Gamma::new(nu / 2.0, 2.0 / nu).unwrap();
+    let data = (0..5000)
+      .map(|_| {
+        let z = (0..2)
+          .map(|_| rng.sample(StandardNormal))
+          .collect::<Vec<f64>>();
+        let scale = 1.0 / rng.sample::<f64, _>(gamma).sqrt();
+        (0..2)
+          .map(|r| mu[r] + scale * (0..=r).map(|c| lsigma[(r, c)] * z[c]).sum::<f64>())
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+
+    let fitted = fit_student_t(&data, 200).unwrap();
+    assert!((fitted.nu() - nu).abs() < 0.8);
+    for (fitted_mu, mu) in fitted.mu().iter().zip(mu.iter()) {
+      assert!((fitted_mu - mu).abs() < 0.1);
+    }
+    let fitted_sigma = fitted.lsigma() * fitted.lsigma().t();
+    for r in 0..2 {
+      for c in 0..2 {
+        assert!((fitted_sigma[(r, c)] - sigma[(r, c)]).abs() < 0.2);
+      }
+    }
+
+    assert!(fit_student_t(&[], 10).is_err());
+    assert!(fit_student_t(&[vec![0.0, 1.0], vec![0.0]], 10).is_err());
+  }
 }
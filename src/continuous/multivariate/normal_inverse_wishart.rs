@@ -47,7 +47,7 @@ impl Distribution for NormalInverseWishart {
     )
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let mu0 = theta.mu0().clone();
     let lambda = theta.lambda();
     let lpsi = theta.lpsi().clone();
@@ -0,0 +1,60 @@
+/// `(s, a, m)` of the primitive polynomials and initial direction numbers by Joe and Kuo, for dimensions 2 and above.
+const DIRECTIONS: [(u32, u32, &[u32]); 9] = [
+  (1, 0, &[1]),
+  (2, 1, &[1, 3]),
+  (3, 1, &[1, 3, 1]),
+  (3, 2, &[1, 1, 1]),
+  (4, 1, &[1, 1, 3, 3]),
+  (4, 4, &[1, 3, 5, 13]),
+  (5, 2, &[1, 1, 5, 5, 17]),
+  (5, 4, &[1, 1, 5, 5, 5]),
+  (5, 7, &[1, 1, 7, 11, 19]),
+];
+
+const BITS: usize = 32;
+
+pub(crate) const MAX_DIM: usize = DIRECTIONS.len() + 1;
+
+fn direction_numbers(d: usize) -> Vec<u32> {
+  if d == 0 {
+    return (0..BITS).map(|k| 1 << (BITS - 1 - k)).collect();
+  }
+
+  let (s, a, m) = DIRECTIONS[d - 1];
+  let s = s as usize;
+  let mut v = vec![0u32; BITS];
+  for k in 0..BITS {
+    v[k] = if k < s {
+      m[k] << (BITS - 1 - k)
+    } else {
+      let mut vk = v[k - s] ^ (v[k - s] >> s);
+      for j in 1..s {
+        if (a >> (s - 1 - j)) & 1 == 1 {
+          vk ^= v[k - j];
+        }
+      }
+      vk
+    };
+  }
+
+  v
+}
+
+/// Points `1..=n` of the `dim`-dimensional Sobol sequence in Gray code order, skipping the origin.
+pub(crate) fn sobol(n: usize, dim: usize) -> Vec<Vec<f64>> {
+  let v = (0..dim).map(direction_numbers).collect::<Vec<_>>();
+  let mut x = vec![0u32; dim];
+
+  (1..=n)
+    .map(|i| {
+      let c = (i - 1).trailing_ones() as usize;
+      x.iter_mut()
+        .zip(v.iter())
+        .map(|(xd, vd)| {
+          *xd ^= vd[c];
+          *xd as f64 / 2f64.powi(BITS as i32)
+        })
+        .collect()
+    })
+    .collect()
+}
@@ -1,8 +1,17 @@
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{
+  check_not_empty, check_probability, CumulativeDistribution, DependentJoint, Distribution,
+  IndependentJoint, MaximumLikelihood, QuantileDistribution, RandomVariable,
+};
 use rand::prelude::*;
 use rand_distr::Normal as RandNormal;
-use std::{f64::consts::PI, ops::BitAnd, ops::Mul};
+use special::Error;
+use std::{
+  convert::TryFrom,
+  f64::consts::{PI, SQRT_2},
+  ops::BitAnd,
+  ops::Mul,
+};
 
 /// # Normal
 /// ![tex](https://latex.codecogs.com/svg.latex?\mathcal%7BN%7D%28\mu%2C%20\sigma%5E2%29)
@@ -13,6 +22,8 @@ pub struct Normal;
 pub enum NormalError {
   #[error("'σ' must be positive")]
   SigmaMustBePositive,
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
 }
 
 impl Distribution for Normal {
@@ -26,7 +37,7 @@ impl Distribution for Normal {
     Ok(1.0 / (2.0 * PI * sigma.powi(2)).sqrt() * (-(x - mu).powi(2) / (2.0 * sigma.powi(2))).exp())
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let mu = theta.mu();
     let sigma = theta.sigma();
 
@@ -67,6 +78,79 @@ impl NormalParams {
   pub fn sigma(&self) -> f64 {
     self.sigma
   }
+
+  /// Flattens into `[mu, sigma]`.
+  pub fn transform_vec(&self) -> Vec<f64> {
+    vec![self.mu, self.sigma]
+  }
+
+  /// Restores from `[mu, sigma]` with the same validation as `new`.
+  pub fn restore(v: &[f64]) -> Result<Self, DistributionError> {
+    if v.len() != 2 {
+      return Err(DistributionError::InvalidParameters(
+        NormalError::DimensionMismatch.into(),
+      ));
+    }
+
+    Self::new(v[0], v[1])
+  }
+}
+
+impl TryFrom<Vec<f64>> for NormalParams {
+  type Error = DistributionError;
+
+  /// Reads `[mu, sigma]`, the order of `transform_vec`.
+  fn try_from(v: Vec<f64>) -> Result<Self, Self::Error> {
+    Self::restore(&v)
+  }
+}
+
+impl MaximumLikelihood for Normal {
+  fn fit(samples: &[Self::T]) -> Result<Self::U, DistributionError> {
+    check_not_empty(samples)?;
+    let n = samples.len() as f64;
+
+    let mu = samples.iter().sum::<f64>() / n;
+    let sigma = (samples.iter().map(|xi| (xi - mu).powi(2)).sum::<f64>() / n).sqrt();
+
+    NormalParams::new(mu, sigma)
+  }
+}
+
+/// Posterior over the mean of `N(μ, σ_l^2)` with known `σ_l` under the prior `N(μ_0, σ_0^2)`, given `data`.
+/// ![tex](https://latex.codecogs.com/svg.latex?\frac%7B1%7D%7B\sigma_n%5E2%7D%3D\frac%7B1%7D%7B\sigma_0%5E2%7D%2B\frac%7Bn%7D%7B\sigma_l%5E2%7D%2C%5C%3B\mu_n%3D\sigma_n%5E2\left%28\frac%7B\mu_0%7D%7B\sigma_0%5E2%7D%2B\frac%7B\sum_i%20x_i%7D%7B\sigma_l%5E2%7D\right%29)
+pub fn normal_conjugate_update(
+  prior: &NormalParams,
+  likelihood_sigma: f64,
+  data: &[f64],
+) -> Result<NormalParams, DistributionError> {
+  if likelihood_sigma <= 0.0 {
+    return Err(DistributionError::InvalidParameters(
+      NormalError::SigmaMustBePositive.into(),
+    ));
+  }
+
+  let prior_precision = prior.sigma().powi(-2);
+  let likelihood_precision = likelihood_sigma.powi(-2);
+  let precision = prior_precision + data.len() as f64 * likelihood_precision;
+  let mu =
+    (prior_precision * prior.mu() + likelihood_precision * data.iter().sum::<f64>()) / precision;
+
+  NormalParams::new(mu, precision.powf(-0.5))
+}
+
+impl CumulativeDistribution for Normal {
+  fn cdf(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(0.5 * (-(x - theta.mu()) / (theta.sigma() * SQRT_2)).compl_error())
+  }
+}
+
+impl QuantileDistribution for Normal {
+  fn quantile(&self, p: f64, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    check_probability(p)?;
+
+    Ok(theta.mu() + theta.sigma() * SQRT_2 * (2.0 * p - 1.0).inv_error())
+  }
 }
 
 impl<Rhs, TRhs> Mul<Rhs> for Normal
@@ -95,8 +179,11 @@ where
 
 #[cfg(test)]
 mod tests {
-  use crate::{Distribution, Normal, NormalParams};
+  use crate::{
+    normal_conjugate_update, ConditionableDistribution, Distribution, Normal, NormalParams,
+  };
   use rand::prelude::*;
+  use std::convert::TryFrom;
 
   #[test]
   fn it_works() {
@@ -112,4 +199,42 @@ mod tests {
 
     println!("{}", x);
   }
+
+  #[test]
+  fn transform_vec() {
+    let params = NormalParams::new(2.0, 3.0).unwrap();
+    let v = params.transform_vec();
+
+    assert_eq!(v, vec![2.0, 3.0]);
+    assert_eq!(NormalParams::restore(&v).unwrap(), params);
+    assert!(NormalParams::restore(&[2.0, -3.0]).is_err());
+    assert!(NormalParams::restore(&[2.0]).is_err());
+    assert_eq!(NormalParams::try_from(v.clone()).unwrap(), params);
+
+    // the flattened params as the condition of a model
+    let model = Normal.condition(&|v: &Vec<f64>| NormalParams::restore(v));
+    assert_eq!(model.p(&1.0, &v).unwrap(), Normal.p(&1.0, &params).unwrap());
+    assert!(model.p(&1.0, &vec![2.0, 0.0]).is_err());
+  }
+
+  #[test]
+  fn conjugate_update() {
+    let prior = NormalParams::new(1.0, 2.0).unwrap();
+    let data = vec![3.1, 2.4, 2.9, 3.6];
+    let sigma = 0.5;
+
+    let posterior = normal_conjugate_update(&prior, sigma, &data).unwrap();
+
+    // precision-weighted average of the prior mean and the data mean
+    let prior_precision = 1.0 / 4.0;
+    let data_precision = data.len() as f64 / 0.25;
+    let data_mean = data.iter().sum::<f64>() / data.len() as f64;
+    let expected =
+      (prior_precision * 1.0 + data_precision * data_mean) / (prior_precision + data_precision);
+    assert!((posterior.mu() - expected).abs() < 1e-12);
+    assert!((posterior.sigma().powi(-2) - (prior_precision + data_precision)).abs() < 1e-10);
+
+    assert_eq!(normal_conjugate_update(&prior, sigma, &[]).unwrap(), prior);
+    assert!(normal_conjugate_update(&prior, 0.0, &data).is_err());
+  }
 }
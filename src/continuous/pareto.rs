@@ -0,0 +1,148 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use rand::prelude::*;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # Pareto
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7BPareto%7D%28x_m%2C%20\alpha%29)
+#[derive(Clone, Debug)]
+pub struct Pareto;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParetoError {
+  #[error("Scale must be positive")]
+  ScaleMustBePositive,
+  #[error("Shape must be positive")]
+  ShapeMustBePositive,
+  #[error("Mean is infinite for shape <= 1")]
+  InfiniteMean,
+}
+
+impl Distribution for Pareto {
+  type T = f64;
+  type U = ParetoParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let scale = theta.scale();
+    let shape = theta.shape();
+
+    if *x < scale {
+      return Ok(0.0);
+    }
+
+    Ok(shape * scale.powf(shape) / x.powf(shape + 1.0))
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let scale = theta.scale();
+    let shape = theta.shape();
+
+    // u in (0, 1]
+    let u = 1.0 - rng.gen::<f64>();
+
+    Ok(scale / u.powf(1.0 / shape))
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParetoParams {
+  scale: f64,
+  shape: f64,
+}
+
+impl ParetoParams {
+  pub fn new(scale: f64, shape: f64) -> Result<Self, DistributionError> {
+    if scale <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        ParetoError::ScaleMustBePositive.into(),
+      ));
+    }
+    if shape <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        ParetoError::ShapeMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self { scale, shape })
+  }
+
+  pub fn scale(&self) -> f64 {
+    self.scale
+  }
+
+  pub fn shape(&self) -> f64 {
+    self.shape
+  }
+
+  pub fn tail_index(&self) -> f64 {
+    self.shape
+  }
+
+  pub fn mean(&self) -> Result<f64, DistributionError> {
+    if self.shape <= 1.0 {
+      return Err(DistributionError::InvalidParameters(
+        ParetoError::InfiniteMean.into(),
+      ));
+    }
+
+    Ok(self.shape * self.scale / (self.shape - 1.0))
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for Pareto
+where
+  Rhs: Distribution<T = TRhs, U = ParetoParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, f64, TRhs, ParetoParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for Pareto
+where
+  Rhs: Distribution<T = ParetoParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, f64, ParetoParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Distribution, Pareto, ParetoParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = ParetoParams::new(2.0, 3.0).unwrap();
+
+    assert_eq!(Pareto.p(&1.9, &params).unwrap(), 0.0);
+    assert!((Pareto.p(&2.0, &params).unwrap() - 1.5).abs() < 1e-12);
+    assert_eq!(params.tail_index(), 3.0);
+    assert!((params.mean().unwrap() - 3.0).abs() < 1e-12);
+    assert!(ParetoParams::new(2.0, 1.0).unwrap().mean().is_err());
+    assert!(ParetoParams::new(0.0, 1.0).is_err());
+  }
+
+  #[test]
+  fn tail() {
+    let params = ParetoParams::new(1.0, 2.0).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let x = Pareto.sample_n(10000, &params, &mut rng).unwrap();
+    assert!(x.iter().all(|&xi| xi >= 1.0));
+
+    // P(X > t) = (scale / t)^shape
+    for &t in [2.0, 5.0].iter() {
+      let tail = x.iter().filter(|&&xi| xi > t).count() as f64 / x.len() as f64;
+
+      assert!((tail - (1.0_f64 / t).powi(2)).abs() < 0.01);
+    }
+  }
+}
@@ -4,7 +4,7 @@ use rand::prelude::*;
 use rand_distr::StudentT as RandStudentT;
 use special::Gamma;
 use std::f64::consts::PI;
-use std::{ops::BitAnd, ops::Mul};
+use std::{convert::TryFrom, ops::BitAnd, ops::Mul};
 
 /// # StudentT
 /// ![tex](https://latex.codecogs.com/svg.latex?\mathcal%7BN%7D%28\mu%2C%20\sigma%5E2%29)
@@ -12,7 +12,12 @@ use std::{ops::BitAnd, ops::Mul};
 pub struct StudentT;
 
 #[derive(thiserror::Error, Debug)]
-pub enum StudentTError {}
+pub enum StudentTError {
+  #[error("'nu' must be positive")]
+  NuMustBePositive,
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+}
 
 impl Distribution for StudentT {
   type T = f64;
@@ -27,7 +32,7 @@ impl Distribution for StudentT {
     )
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let nu = theta.nu();
 
     let student_t = match RandStudentT::new(nu) {
@@ -46,6 +51,12 @@ pub struct StudentTParams {
 
 impl StudentTParams {
   pub fn new(nu: f64) -> Result<Self, DistributionError> {
+    if nu <= 0.0 || nu.is_nan() {
+      return Err(DistributionError::InvalidParameters(
+        StudentTError::NuMustBePositive.into(),
+      ));
+    }
+
     Ok(Self { nu })
   }
 
@@ -54,6 +65,21 @@ impl StudentTParams {
   }
 }
 
+impl TryFrom<Vec<f64>> for StudentTParams {
+  type Error = DistributionError;
+
+  /// Reads `[nu]`.
+  fn try_from(v: Vec<f64>) -> Result<Self, Self::Error> {
+    if v.len() != 1 {
+      return Err(DistributionError::InvalidParameters(
+        StudentTError::DimensionMismatch.into(),
+      ));
+    }
+
+    Self::new(v[0])
+  }
+}
+
 impl<Rhs, TRhs> Mul<Rhs> for StudentT
 where
   Rhs: Distribution<T = TRhs, U = StudentTParams>,
@@ -80,8 +106,21 @@ where
 
 #[cfg(test)]
 mod tests {
+  use crate::StudentTParams;
+  use std::convert::TryFrom;
+
   #[test]
   fn it_works() {
     assert_eq!(2 + 2, 4);
   }
+
+  #[test]
+  fn try_from() {
+    let params = StudentTParams::try_from(vec![3.0]).unwrap();
+    assert_eq!(params, StudentTParams::new(3.0).unwrap());
+    assert_eq!(StudentTParams::try_from(vec![params.nu()]).unwrap(), params);
+
+    assert!(StudentTParams::try_from(vec![0.0]).is_err());
+    assert!(StudentTParams::try_from(vec![3.0, 1.0]).is_err());
+  }
 }
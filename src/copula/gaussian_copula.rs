@@ -0,0 +1,247 @@
+use crate::DistributionError;
+use crate::{
+  CumulativeDistribution, DependentJoint, Distribution, IndependentJoint, MultivariateNormal,
+  MultivariateNormalParams, Normal, NormalParams, QuantileDistribution, RandomVariable,
+};
+use opensrdk_linear_algebra::*;
+use rand::prelude::*;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # GaussianCopula
+/// ![tex](https://latex.codecogs.com/svg.latex?x_i%3DF_i%5E%7B-1%7D%28\Phi%28z_i%29%29%2C%20\mathbf%7Bz%7D\sim\mathcal%7BN%7D%28\mathbf%7B0%7D%2C%20R%29)
+///
+/// Couples marginals of the family `D`, each with its own parameters, through the correlation matrix `R`.
+#[derive(Clone, Debug)]
+pub struct GaussianCopula<D>
+where
+  D: CumulativeDistribution<T = f64> + QuantileDistribution,
+{
+  distribution: D,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GaussianCopulaError {
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+  #[error("Diagonal of 'R' must be 1")]
+  NotCorrelation,
+}
+
+impl<D> GaussianCopula<D>
+where
+  D: CumulativeDistribution<T = f64> + QuantileDistribution,
+{
+  pub fn new(distribution: D) -> Self {
+    Self { distribution }
+  }
+}
+
+impl<D> Distribution for GaussianCopula<D>
+where
+  D: CumulativeDistribution<T = f64> + QuantileDistribution,
+{
+  type T = Vec<f64>;
+  type U = GaussianCopulaParams<D::U>;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let marginals = theta.marginals();
+    if x.len() != marginals.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianCopulaError::DimensionMismatch.into(),
+      ));
+    }
+
+    let standard = NormalParams::new(0.0, 1.0)?;
+    let z = x
+      .iter()
+      .zip(marginals.iter())
+      .map(|(xi, ui)| Normal.quantile(self.distribution.cdf(xi, ui)?, &standard))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    // ln c(u) = ln N(z; 0, R) - Σ ln N(z_i; 0, 1)
+    let ln_c = MultivariateNormal.ln_p(
+      &z,
+      &MultivariateNormalParams::new(vec![0.0; z.len()], theta.lcorrelation().clone())?,
+    )? - z
+      .iter()
+      .map(|zi| Normal.ln_p(zi, &standard))
+      .sum::<Result<f64, _>>()?;
+    let ln_marginals = x
+      .iter()
+      .zip(marginals.iter())
+      .map(|(xi, ui)| self.distribution.ln_p(xi, ui))
+      .sum::<Result<f64, _>>()?;
+
+    Ok(ln_c + ln_marginals)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let marginals = theta.marginals();
+    let standard = NormalParams::new(0.0, 1.0)?;
+
+    let z = MultivariateNormal.sample(
+      &MultivariateNormalParams::new(vec![0.0; marginals.len()], theta.lcorrelation().clone())?,
+      rng,
+    )?;
+
+    z.iter()
+      .zip(marginals.iter())
+      .map(|(zi, ui)| self.distribution.quantile(Normal.cdf(zi, &standard)?, ui))
+      .collect()
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianCopulaParams<U>
+where
+  U: RandomVariable,
+{
+  lcorrelation: Matrix,
+  marginals: Vec<U>,
+}
+
+impl<U> GaussianCopulaParams<U>
+where
+  U: RandomVariable,
+{
+  /// `L` is needed as first argument under decomposition `R = L * L^T`, with `R` a correlation matrix.
+  /// Only the lower triangle of `L` is read.
+  pub fn new(lcorrelation: Matrix, marginals: Vec<U>) -> Result<Self, DistributionError> {
+    let n = marginals.len();
+    if n != lcorrelation.rows() || n != lcorrelation.cols() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianCopulaError::DimensionMismatch.into(),
+      ));
+    }
+
+    for r in 0..n {
+      let diag = (0..=r).map(|c| lcorrelation[(r, c)].powi(2)).sum::<f64>();
+      if (diag - 1.0).abs() > 1e-8 {
+        return Err(DistributionError::InvalidParameters(
+          GaussianCopulaError::NotCorrelation.into(),
+        ));
+      }
+    }
+
+    Ok(Self {
+      lcorrelation,
+      marginals,
+    })
+  }
+
+  pub fn lcorrelation(&self) -> &Matrix {
+    &self.lcorrelation
+  }
+
+  pub fn marginals(&self) -> &[U] {
+    &self.marginals
+  }
+}
+
+impl<D, Rhs, TRhs> Mul<Rhs> for GaussianCopula<D>
+where
+  D: CumulativeDistribution<T = f64> + QuantileDistribution,
+  Rhs: Distribution<T = TRhs, U = GaussianCopulaParams<D::U>>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, Vec<f64>, TRhs, GaussianCopulaParams<D::U>>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<D, Rhs, URhs> BitAnd<Rhs> for GaussianCopula<D>
+where
+  D: CumulativeDistribution<T = f64> + QuantileDistribution,
+  Rhs: Distribution<T = GaussianCopulaParams<D::U>, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, Vec<f64>, GaussianCopulaParams<D::U>, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::copula::{GaussianCopula, GaussianCopulaParams};
+  use crate::{CumulativeDistribution, Distribution, Exp, ExpParams};
+  use opensrdk_linear_algebra::*;
+  use rand::prelude::*;
+  use std::f64::consts::PI;
+
+  fn ranks(x: &[f64]) -> Vec<f64> {
+    let mut indices = (0..x.len()).collect::<Vec<_>>();
+    indices.sort_by(|&a, &b| x[a].partial_cmp(&x[b]).unwrap());
+
+    let mut ranks = vec![0.0; x.len()];
+    for (rank, &i) in indices.iter().enumerate() {
+      ranks[i] = rank as f64;
+    }
+
+    ranks
+  }
+
+  #[test]
+  fn it_works() {
+    let rho = 0.7f64;
+    let lcorrelation = mat!(
+      1.0, 0.0;
+      rho, (1.0 - rho * rho).sqrt()
+    );
+    let marginals = vec![ExpParams::new(1.0).unwrap(), ExpParams::new(3.0).unwrap()];
+    let params = GaussianCopulaParams::new(lcorrelation, marginals.clone()).unwrap();
+    let copula = GaussianCopula::new(Exp);
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let n = 5000;
+    let x = copula.sample_n(n, &params, &mut rng).unwrap();
+
+    // each margin follows its own exponential
+    for (d, marginal) in marginals.iter().enumerate() {
+      let xd = x.iter().map(|xi| xi[d]).collect::<Vec<_>>();
+      let mean = xd.iter().sum::<f64>() / n as f64;
+      assert!((mean - 1.0 / marginal.lambda()).abs() < 0.05 / marginal.lambda());
+
+      let u = xd
+        .iter()
+        .map(|xdi| Exp.cdf(xdi, marginal).unwrap())
+        .collect::<Vec<_>>();
+      let below_median = u.iter().filter(|&&ui| ui < 0.5).count() as f64 / n as f64;
+      assert!((below_median - 0.5).abs() < 0.03);
+    }
+
+    // Spearman's rho of a Gaussian copula is 6 / π asin(ρ / 2)
+    let r0 = ranks(&x.iter().map(|xi| xi[0]).collect::<Vec<_>>());
+    let r1 = ranks(&x.iter().map(|xi| xi[1]).collect::<Vec<_>>());
+    let mean = (n - 1) as f64 / 2.0;
+    let cov = r0
+      .iter()
+      .zip(r1.iter())
+      .map(|(a, b)| (a - mean) * (b - mean))
+      .sum::<f64>();
+    let var = r0.iter().map(|a| (a - mean).powi(2)).sum::<f64>();
+    let spearman = cov / var;
+    let expected = 6.0 / PI * (rho / 2.0).asin();
+    assert!((spearman - expected).abs() < 0.03);
+  }
+
+  #[test]
+  fn p() {
+    let marginals = vec![ExpParams::new(1.0).unwrap(), ExpParams::new(3.0).unwrap()];
+    let independent = GaussianCopulaParams::new(Matrix::identity(2), marginals.clone()).unwrap();
+    let x = vec![0.4, 0.2];
+
+    let expected = Exp.p(&x[0], &marginals[0]).unwrap() * Exp.p(&x[1], &marginals[1]).unwrap();
+    assert!((GaussianCopula::new(Exp).p(&x, &independent).unwrap() - expected).abs() < 1e-10);
+
+    assert!(GaussianCopulaParams::new(2.0 * Matrix::identity(2), marginals.clone()).is_err());
+    assert!(GaussianCopulaParams::new(Matrix::identity(3), marginals).is_err());
+  }
+}
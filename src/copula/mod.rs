@@ -0,0 +1,3 @@
+pub mod gaussian_copula;
+
+pub use gaussian_copula::*;
@@ -0,0 +1,135 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use rand::prelude::*;
+use rand_distr::Binomial as RandBinominal;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # Bernoulli
+/// ![tex](https://latex.codecogs.com/svg.latex?p%5Ex%281-p%29%5E%7B1-x%7D)
+///
+/// The number of successes over `k` independent trials follows `Binominal` with `n = k`.
+#[derive(Clone, Debug)]
+pub struct Bernoulli;
+
+#[derive(thiserror::Error, Debug)]
+pub enum BernoulliError {
+  #[error("'p' must be probability.")]
+  PMustBeProbability,
+}
+
+impl Distribution for Bernoulli {
+  type T = bool;
+  type U = BernoulliParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let p = theta.p();
+
+    Ok(if *x { p } else { 1.0 - p })
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    Ok(rng.gen_bool(theta.p()))
+  }
+}
+
+impl Bernoulli {
+  /// Outcomes of `k` independent trials.
+  pub fn sample_trials(
+    &self,
+    theta: &BernoulliParams,
+    k: usize,
+    rng: &mut dyn RngCore,
+  ) -> Vec<bool> {
+    (0..k).map(|_| rng.gen_bool(theta.p())).collect()
+  }
+
+  /// Number of successes in `k` trials, drawn at once from `Binominal(k, p)`.
+  pub fn sample_count(
+    &self,
+    theta: &BernoulliParams,
+    k: u64,
+    rng: &mut dyn RngCore,
+  ) -> Result<u64, DistributionError> {
+    let binominal = match RandBinominal::new(k, theta.p()) {
+      Ok(v) => Ok(v),
+      Err(e) => Err(DistributionError::Others(e.into())),
+    }?;
+
+    Ok(rng.sample(binominal))
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BernoulliParams {
+  p: f64,
+}
+
+impl BernoulliParams {
+  pub fn new(p: f64) -> Result<Self, DistributionError> {
+    if !(0.0..=1.0).contains(&p) {
+      return Err(DistributionError::InvalidParameters(
+        BernoulliError::PMustBeProbability.into(),
+      ));
+    }
+
+    Ok(Self { p })
+  }
+
+  pub fn p(&self) -> f64 {
+    self.p
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for Bernoulli
+where
+  Rhs: Distribution<T = TRhs, U = BernoulliParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, bool, TRhs, BernoulliParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for Bernoulli
+where
+  Rhs: Distribution<T = BernoulliParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, bool, BernoulliParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Bernoulli, BernoulliParams, Distribution};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = BernoulliParams::new(0.3).unwrap();
+
+    assert_eq!(Bernoulli.p(&true, &params).unwrap(), 0.3);
+    assert_eq!(Bernoulli.p(&false, &params).unwrap(), 0.7);
+    assert!(BernoulliParams::new(1.5).is_err());
+  }
+
+  #[test]
+  fn sample_trials() {
+    let params = BernoulliParams::new(0.3).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+    let k = 10000;
+
+    let trials = Bernoulli.sample_trials(&params, k, &mut rng);
+    assert_eq!(trials.len(), k);
+    let fraction = trials.iter().filter(|&&x| x).count() as f64 / k as f64;
+    assert!((fraction - 0.3).abs() < 0.02);
+
+    let count = Bernoulli.sample_count(&params, k as u64, &mut rng).unwrap();
+    assert!((count as f64 / k as f64 - 0.3).abs() < 0.02);
+  }
+}
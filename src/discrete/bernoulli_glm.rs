@@ -0,0 +1,193 @@
+use crate::DistributionError;
+use crate::{
+  sigmoid, ConditionDifferentiableDistribution, DependentJoint, Distribution, IndependentJoint,
+  RandomVariable,
+};
+use rand::prelude::*;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # BernoulliGLM
+/// ![tex](https://latex.codecogs.com/svg.latex?y%5Csim%5Cmathrm%7BBernoulli%7D%28%5Csigma%28%5Cmathbf%7Bx%7D%5E%5Ctop%5Cboldsymbol%7B%5Cbeta%7D%29%29)
+///
+/// Logistic regression of a label on the covariates `x` with coefficients `beta`.
+/// The value is `(x, y)` and the condition is `beta`, so `ln_diff_condition` is the gradient a sampler over `beta` moves along.
+/// Include a constant `1.0` in `x` for an intercept.
+/// The covariates are observed rather than modeled, so `sample` returns an error.
+#[derive(Clone, Debug)]
+pub struct BernoulliGLM;
+
+#[derive(thiserror::Error, Debug)]
+pub enum BernoulliGLMError {
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+  #[error("Covariates are observed, so they cannot be sampled")]
+  CovariatesNotModeled,
+}
+
+/// ![tex](https://latex.codecogs.com/svg.latex?%5Cln%5Csigma%28z%29) without overflowing for a large `|z|`.
+fn ln_sigmoid(z: f64) -> f64 {
+  if 0.0 < z {
+    -(-z).exp().ln_1p()
+  } else {
+    z - z.exp().ln_1p()
+  }
+}
+
+/// ![tex](https://latex.codecogs.com/svg.latex?%5Cmathbf%7Bx%7D%5E%5Ctop%5Cboldsymbol%7B%5Cbeta%7D)
+fn linear_predictor(x: &[f64], beta: &[f64]) -> Result<f64, DistributionError> {
+  if x.len() != beta.len() {
+    return Err(DistributionError::InvalidParameters(
+      BernoulliGLMError::DimensionMismatch.into(),
+    ));
+  }
+
+  Ok(x.iter().zip(beta.iter()).map(|(x, b)| x * b).sum())
+}
+
+impl Distribution for BernoulliGLM {
+  type T = (Vec<f64>, bool);
+  type U = Vec<f64>;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let (covariates, label) = x;
+    let z = linear_predictor(covariates, theta)?;
+
+    Ok(if *label {
+      ln_sigmoid(z)
+    } else {
+      ln_sigmoid(-z)
+    })
+  }
+
+  fn sample(&self, _: &Self::U, _: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    Err(DistributionError::InvalidParameters(
+      BernoulliGLMError::CovariatesNotModeled.into(),
+    ))
+  }
+}
+
+impl ConditionDifferentiableDistribution for BernoulliGLM {
+  /// ![tex](https://latex.codecogs.com/svg.latex?%5Cmathbf%7Bx%7D%28y-%5Csigma%28%5Cmathbf%7Bx%7D%5E%5Ctop%5Cboldsymbol%7B%5Cbeta%7D%29%29)
+  fn ln_diff_condition(&self, x: &Self::T, theta: &Self::U) -> Result<Vec<f64>, DistributionError> {
+    let (covariates, label) = x;
+    let y = if *label { 1.0 } else { 0.0 };
+    let residual = y - sigmoid(linear_predictor(covariates, theta)?);
+
+    Ok(covariates.iter().map(|xi| xi * residual).collect())
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for BernoulliGLM
+where
+  Rhs: Distribution<T = TRhs, U = Vec<f64>>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, (Vec<f64>, bool), TRhs, Vec<f64>>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for BernoulliGLM
+where
+  Rhs: Distribution<T = Vec<f64>, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, (Vec<f64>, bool), Vec<f64>, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{BernoulliGLM, ConditionDifferentiableDistribution, Distribution};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let x = vec![1.0, 2.0];
+    let beta = vec![0.5, -1.0];
+    let p = 1.0 / (1.0 + 1.5f64.exp());
+
+    assert!((BernoulliGLM.p(&(x.clone(), true), &beta).unwrap() - p).abs() < 1e-12);
+    assert!((BernoulliGLM.p(&(x, false), &beta).unwrap() - (1.0 - p)).abs() < 1e-12);
+
+    // far in the tail p underflows but ln_p does not
+    let tail = BernoulliGLM
+      .ln_p(&(vec![1000.0], false), &vec![1.0])
+      .unwrap();
+    assert!((tail + 1000.0).abs() < 1e-9);
+
+    assert!(BernoulliGLM
+      .ln_p(&(vec![1.0], true), &vec![1.0, 2.0])
+      .is_err());
+    assert!(BernoulliGLM
+      .sample(&vec![1.0], &mut StdRng::from_seed([1; 32]))
+      .is_err());
+  }
+
+  #[test]
+  fn ln_diff_condition() {
+    let x = vec![1.0, -0.7, 2.3];
+    let beta = vec![0.2, 0.5, -0.4];
+    let h = 1e-6;
+
+    for &y in [true, false].iter() {
+      let value = (x.clone(), y);
+      let gradient = BernoulliGLM.ln_diff_condition(&value, &beta).unwrap();
+
+      for j in 0..beta.len() {
+        let mut forward = beta.clone();
+        forward[j] += h;
+        let mut backward = beta.clone();
+        backward[j] -= h;
+        let numerical = (BernoulliGLM.ln_p(&value, &forward).unwrap()
+          - BernoulliGLM.ln_p(&value, &backward).unwrap())
+          / (2.0 * h);
+
+        assert!((gradient[j] - numerical).abs() < 1e-6);
+      }
+    }
+  }
+
+  #[test]
+  fn fit() {
+    // labels split by the line 1 - 2 x = 0
+    let truth = [1.0, -2.0];
+    let data = (0..100)
+      .map(|i| {
+        let x = vec![1.0, (i as f64 + 0.5) / 50.0 - 1.0];
+        let z = x.iter().zip(truth.iter()).map(|(a, b)| a * b).sum::<f64>();
+        (x, 0.0 < z)
+      })
+      .collect::<Vec<_>>();
+
+    // gradient ascent on the log likelihood; the data are separable, so the norm of beta keeps growing and only its direction converges
+    let mut beta = vec![0.0, 0.0];
+    for _ in 0..2000 {
+      let mut gradient = [0.0; 2];
+      for value in data.iter() {
+        let g = BernoulliGLM.ln_diff_condition(value, &beta).unwrap();
+        for (gj, dj) in gradient.iter_mut().zip(g.iter()) {
+          *gj += dj;
+        }
+      }
+      for (bj, gj) in beta.iter_mut().zip(gradient.iter()) {
+        *bj += 0.1 * gj;
+      }
+    }
+
+    let ratio = beta[1] / beta[0];
+    assert!((ratio - truth[1] / truth[0]).abs() < 0.1);
+    assert!(data
+      .iter()
+      .all(|value| BernoulliGLM.p(value, &beta).unwrap() > 0.5));
+  }
+}
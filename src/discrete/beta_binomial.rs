@@ -0,0 +1,152 @@
+use crate::DistributionError;
+use crate::{
+  ln_gamma, Beta, BetaParams, DependentJoint, Distribution, IndependentJoint, RandomVariable,
+};
+use rand::prelude::*;
+use rand_distr::Binomial as RandBinominal;
+use special::Beta as BetaFunc;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # BetaBinomial
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7BBetaBin%7D%28n%2C%20\alpha%2C%20\beta%29)
+#[derive(Clone, Debug)]
+pub struct BetaBinomial;
+
+#[derive(thiserror::Error, Debug)]
+pub enum BetaBinomialError {
+  #[error("'α' must be positive")]
+  AlphaMustBePositive,
+  #[error("'β' must be positive")]
+  BetaMustBePositive,
+}
+
+impl Distribution for BetaBinomial {
+  type T = u64;
+  type U = BetaBinomialParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let n = theta.n();
+    let alpha = theta.alpha();
+    let beta = theta.beta();
+
+    if n < *x {
+      return Ok(f64::NEG_INFINITY);
+    }
+
+    let x = *x as f64;
+    let n = n as f64;
+    let ln_binom = ln_gamma(n + 1.0) - ln_gamma(x + 1.0) - ln_gamma(n - x + 1.0);
+
+    Ok(ln_binom + (x + alpha).ln_beta(n - x + beta) - alpha.ln_beta(beta))
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let p = Beta.sample(&BetaParams::new(theta.alpha(), theta.beta())?, rng)?;
+
+    let binominal = match RandBinominal::new(theta.n(), p) {
+      Ok(v) => Ok(v),
+      Err(e) => Err(DistributionError::Others(e.into())),
+    }?;
+
+    Ok(rng.sample(binominal))
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetaBinomialParams {
+  n: u64,
+  alpha: f64,
+  beta: f64,
+}
+
+impl BetaBinomialParams {
+  pub fn new(n: u64, alpha: f64, beta: f64) -> Result<Self, DistributionError> {
+    if alpha <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        BetaBinomialError::AlphaMustBePositive.into(),
+      ));
+    }
+    if beta <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        BetaBinomialError::BetaMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self { n, alpha, beta })
+  }
+
+  pub fn n(&self) -> u64 {
+    self.n
+  }
+
+  pub fn alpha(&self) -> f64 {
+    self.alpha
+  }
+
+  pub fn beta(&self) -> f64 {
+    self.beta
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for BetaBinomial
+where
+  Rhs: Distribution<T = TRhs, U = BetaBinomialParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, u64, TRhs, BetaBinomialParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for BetaBinomial
+where
+  Rhs: Distribution<T = BetaBinomialParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, u64, BetaBinomialParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{BetaBinomial, BetaBinomialParams, Distribution};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let (n, alpha, beta) = (10, 2.0, 3.0);
+    let params = BetaBinomialParams::new(n, alpha, beta).unwrap();
+
+    let p_sum = (0..=n)
+      .map(|x| BetaBinomial.p(&x, &params).unwrap())
+      .sum::<f64>();
+    assert!((p_sum - 1.0).abs() < 1e-10);
+    assert_eq!(BetaBinomial.p(&(n + 1), &params).unwrap(), 0.0);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let samples = 20000;
+    let x = BetaBinomial.sample_n(samples, &params, &mut rng).unwrap();
+
+    let mean = x.iter().sum::<u64>() as f64 / samples as f64;
+    let var = x.iter().map(|&xi| (xi as f64 - mean).powi(2)).sum::<f64>() / samples as f64;
+
+    let p = alpha / (alpha + beta);
+    let expected = n as f64 * p;
+    let binomial_var = n as f64 * p * (1.0 - p);
+
+    assert!((mean - expected).abs() < 0.1);
+    assert!(binomial_var * 1.5 < var);
+
+    assert!(BetaBinomialParams::new(n, 0.0, 1.0).is_err());
+    assert!(BetaBinomialParams::new(n, 1.0, 0.0).is_err());
+  }
+}
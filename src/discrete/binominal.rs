@@ -1,9 +1,9 @@
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{DependentJoint, DiscreteDistribution, Distribution, IndependentJoint, RandomVariable};
 use num_integer::binomial;
 use rand::prelude::*;
 use rand_distr::Binomial as RandBinominal;
-use std::{error::Error, ops::BitAnd, ops::Mul};
+use std::{convert::TryFrom, ops::BitAnd, ops::Mul};
 
 /// # Binominal
 /// ![tex](https://latex.codecogs.com/svg.latex?\mathcal%7BN%7D%28\mu%2C%20\sigma%5E2%29)
@@ -14,6 +14,12 @@ pub struct Binominal;
 pub enum BinominalError {
   #[error("'p' must be probability.")]
   PMustBeProbability,
+  #[error("'q' must be probability.")]
+  QMustBeProbability,
+  #[error("'n' must be a non-negative integer.")]
+  NMustBeNonNegativeInteger,
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
   #[error("Unknown error")]
   Unknown,
 }
@@ -29,7 +35,7 @@ impl Distribution for Binominal {
     Ok(binomial(n, *x) as f64 * p.powi(*x as i32) * (1.0 - p).powi((n - x) as i32))
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let n = theta.n();
     let p = theta.p();
 
@@ -42,6 +48,17 @@ impl Distribution for Binominal {
   }
 }
 
+impl DiscreteDistribution for Binominal {
+  fn max_value(&self, theta: &Self::U) -> Option<u64> {
+    Some(theta.n())
+  }
+
+  fn mode(&self, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    // (n + 1) p - 1 and (n + 1) p tie when (n + 1) p is an integer, and the smaller one is the mode
+    Ok((((theta.n() + 1) as f64 * theta.p()).ceil() as u64).saturating_sub(1))
+  }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct BinominalParams {
   n: u64,
@@ -49,14 +66,27 @@ pub struct BinominalParams {
 }
 
 impl BinominalParams {
-  pub fn new(n: u64, p: f64) -> Result<Self, Box<dyn Error>> {
+  pub fn new(n: u64, p: f64) -> Result<Self, DistributionError> {
     if p < 0.0 || 1.0 < p {
-      return Err(BinominalError::PMustBeProbability.into());
+      return Err(DistributionError::InvalidParameters(
+        BinominalError::PMustBeProbability.into(),
+      ));
     }
 
     Ok(Self { n, p })
   }
 
+  /// Parameterized by the failure probability `q = 1 - p`.
+  pub fn from_failure_prob(n: u64, q: f64) -> Result<Self, DistributionError> {
+    if !(0.0..=1.0).contains(&q) {
+      return Err(DistributionError::InvalidParameters(
+        BinominalError::QMustBeProbability.into(),
+      ));
+    }
+
+    Self::new(n, 1.0 - q)
+  }
+
   pub fn n(&self) -> u64 {
     self.n
   }
@@ -66,6 +96,27 @@ impl BinominalParams {
   }
 }
 
+impl TryFrom<Vec<f64>> for BinominalParams {
+  type Error = DistributionError;
+
+  /// Reads `[n, p]`, where `n` must hold an integral value.
+  fn try_from(v: Vec<f64>) -> Result<Self, Self::Error> {
+    if v.len() != 2 {
+      return Err(DistributionError::InvalidParameters(
+        BinominalError::DimensionMismatch.into(),
+      ));
+    }
+    let (n, p) = (v[0], v[1]);
+    if !(0.0..=u64::MAX as f64).contains(&n) || n.fract() != 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        BinominalError::NMustBeNonNegativeInteger.into(),
+      ));
+    }
+
+    Self::new(n as u64, p)
+  }
+}
+
 impl<Rhs, TRhs> Mul<Rhs> for Binominal
 where
   Rhs: Distribution<T = TRhs, U = BinominalParams>,
@@ -92,8 +143,36 @@ where
 
 #[cfg(test)]
 mod tests {
+  use crate::BinominalParams;
+  use std::convert::TryFrom;
+
   #[test]
   fn it_works() {
     assert_eq!(2 + 2, 4);
   }
+
+  #[test]
+  fn from_failure_prob() {
+    assert_eq!(
+      BinominalParams::from_failure_prob(10, 0.3).unwrap(),
+      BinominalParams::new(10, 0.7).unwrap()
+    );
+    assert!(BinominalParams::from_failure_prob(10, -0.1).is_err());
+    assert!(BinominalParams::from_failure_prob(10, 1.1).is_err());
+  }
+
+  #[test]
+  fn try_from() {
+    let params = BinominalParams::try_from(vec![10.0, 0.3]).unwrap();
+    assert_eq!(params, BinominalParams::new(10, 0.3).unwrap());
+    assert_eq!(
+      BinominalParams::try_from(vec![params.n() as f64, params.p()]).unwrap(),
+      params
+    );
+
+    assert!(BinominalParams::try_from(vec![10.5, 0.3]).is_err());
+    assert!(BinominalParams::try_from(vec![-1.0, 0.3]).is_err());
+    assert!(BinominalParams::try_from(vec![10.0, 1.3]).is_err());
+    assert!(BinominalParams::try_from(vec![10.0]).is_err());
+  }
 }
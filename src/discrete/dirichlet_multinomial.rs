@@ -0,0 +1,200 @@
+use crate::DistributionError;
+use crate::{
+  ln_gamma, DependentJoint, Dirichlet, DirichletParams, Distribution, IndependentJoint,
+  RandomVariable,
+};
+use rand::prelude::*;
+use rand_distr::Binomial as RandBinominal;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # DirichletMultinomial
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7BDirMult%7D%28n%2C%20\boldsymbol%7B\alpha%7D%29)
+#[derive(Clone, Debug)]
+pub struct DirichletMultinomial;
+
+#[derive(thiserror::Error, Debug)]
+pub enum DirichletMultinomialError {
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+  #[error("Length of 'α' must be >= 2")]
+  AlphaLenMustBeGTE2,
+  #[error("'α' must be positive")]
+  AlphaMustBePositive,
+  #[error("Sum of 'x' must be 'n'")]
+  SumMustBeN,
+}
+
+impl Distribution for DirichletMultinomial {
+  type T = Vec<u64>;
+  type U = DirichletMultinomialParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let alpha = theta.alpha();
+    let n = theta.n();
+
+    if x.len() != alpha.len() {
+      return Err(DistributionError::InvalidParameters(
+        DirichletMultinomialError::DimensionMismatch.into(),
+      ));
+    }
+    if x.iter().sum::<u64>() != n {
+      return Err(DistributionError::InvalidParameters(
+        DirichletMultinomialError::SumMustBeN.into(),
+      ));
+    }
+
+    let alpha_sum = alpha.iter().sum::<f64>();
+    let n = n as f64;
+
+    Ok(
+      ln_gamma(alpha_sum) + ln_gamma(n + 1.0) - ln_gamma(n + alpha_sum)
+        + x
+          .iter()
+          .zip(alpha.iter())
+          .map(|(&xi, &alphai)| {
+            let xi = xi as f64;
+            ln_gamma(xi + alphai) - ln_gamma(alphai) - ln_gamma(xi + 1.0)
+          })
+          .sum::<f64>(),
+    )
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let n = theta.n();
+
+    let p = Dirichlet.sample(&DirichletParams::new(theta.alpha().to_vec())?, rng)?;
+
+    // multinomial draw as a sequence of conditional binomial draws
+    let mut rest_n = n;
+    let mut rest_p = 1.0;
+    let x = p
+      .iter()
+      .map(|&pi| {
+        if rest_n == 0 || rest_p <= 0.0 {
+          return Ok(0);
+        }
+
+        let binominal = match RandBinominal::new(rest_n, (pi / rest_p).min(1.0)) {
+          Ok(v) => Ok(v),
+          Err(e) => Err(DistributionError::Others(e.into())),
+        }?;
+        let xi = rng.sample(binominal);
+
+        rest_n -= xi;
+        rest_p -= pi;
+
+        Ok(xi)
+      })
+      .collect::<Result<Vec<_>, DistributionError>>()?;
+
+    Ok(x)
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirichletMultinomialParams {
+  alpha: Vec<f64>,
+  n: u64,
+}
+
+impl DirichletMultinomialParams {
+  pub fn new(alpha: Vec<f64>, n: u64) -> Result<Self, DistributionError> {
+    if alpha.len() < 2 {
+      return Err(DistributionError::InvalidParameters(
+        DirichletMultinomialError::AlphaLenMustBeGTE2.into(),
+      ));
+    }
+    for &alpha_i in alpha.iter() {
+      if alpha_i <= 0.0 {
+        return Err(DistributionError::InvalidParameters(
+          DirichletMultinomialError::AlphaMustBePositive.into(),
+        ));
+      }
+    }
+
+    Ok(Self { alpha, n })
+  }
+
+  pub fn alpha(&self) -> &[f64] {
+    &self.alpha
+  }
+
+  pub fn n(&self) -> u64 {
+    self.n
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for DirichletMultinomial
+where
+  Rhs: Distribution<T = TRhs, U = DirichletMultinomialParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, Vec<u64>, TRhs, DirichletMultinomialParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for DirichletMultinomial
+where
+  Rhs: Distribution<T = DirichletMultinomialParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, Vec<u64>, DirichletMultinomialParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{DirichletMultinomial, DirichletMultinomialParams, Distribution};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = DirichletMultinomialParams::new(vec![1.0, 2.0, 3.0], 4).unwrap();
+
+    let mut p_sum = 0.0;
+    for x0 in 0..=4u64 {
+      for x1 in 0..=(4 - x0) {
+        p_sum += DirichletMultinomial
+          .p(&vec![x0, x1, 4 - x0 - x1], &params)
+          .unwrap();
+      }
+    }
+    assert!((p_sum - 1.0).abs() < 1e-10);
+
+    assert!(DirichletMultinomial.p(&vec![1, 1, 1], &params).is_err());
+    assert!(DirichletMultinomialParams::new(vec![1.0, 0.0], 4).is_err());
+  }
+
+  #[test]
+  fn mean() {
+    let alpha = vec![1.0, 2.0, 5.0];
+    let n = 20;
+    let params = DirichletMultinomialParams::new(alpha.clone(), n).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let samples = 10000;
+    let x = DirichletMultinomial
+      .sample_n(samples, &params, &mut rng)
+      .unwrap();
+
+    assert!(x.iter().all(|xi| xi.iter().sum::<u64>() == n));
+
+    let alpha_sum = alpha.iter().sum::<f64>();
+    for k in 0..alpha.len() {
+      let mean = x.iter().map(|xi| xi[k] as f64).sum::<f64>() / samples as f64;
+      let expected = n as f64 * alpha[k] / alpha_sum;
+
+      assert!((mean - expected).abs() < 0.2);
+    }
+  }
+}
@@ -0,0 +1,211 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use rand::prelude::*;
+use std::{collections::HashMap, hash::Hash, marker::PhantomData, ops::BitAnd, ops::Mul};
+
+/// # EmpiricalDiscrete
+/// ![tex](https://latex.codecogs.com/svg.latex?p%28x%29%3D\frac%7Bn_x%7D%7B\sum_%7Bx%27%7Dn_%7Bx%27%7D%7D)
+#[derive(Clone, Debug)]
+pub struct EmpiricalDiscrete<T>
+where
+  T: RandomVariable + Eq + Hash + Ord,
+{
+  phantom: PhantomData<T>,
+}
+
+impl<T> EmpiricalDiscrete<T>
+where
+  T: RandomVariable + Eq + Hash + Ord,
+{
+  pub fn new() -> Self {
+    Self {
+      phantom: PhantomData,
+    }
+  }
+}
+
+impl<T> Default for EmpiricalDiscrete<T>
+where
+  T: RandomVariable + Eq + Hash + Ord,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum EmpiricalDiscreteError {
+  #[error("Counts must not be all zero")]
+  CountsAreEmpty,
+}
+
+impl<T> Distribution for EmpiricalDiscrete<T>
+where
+  T: RandomVariable + Eq + Hash + Ord,
+{
+  type T = T;
+  type U = EmpiricalDiscreteParams<T>;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let count = match theta.counts.binary_search_by(|(xi, _)| xi.cmp(x)) {
+      Ok(i) => theta.counts[i].1,
+      Err(_) => 0,
+    };
+
+    Ok(count as f64 / theta.total as f64)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    let mut rest = rng.gen_range(0..theta.total);
+
+    for (xi, ni) in theta.counts.iter() {
+      if rest < *ni {
+        return Ok(xi.clone());
+      }
+      rest -= ni;
+    }
+
+    unreachable!()
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmpiricalDiscreteParams<T>
+where
+  T: RandomVariable + Eq + Hash + Ord,
+{
+  counts: Vec<(T, u64)>,
+  total: u64,
+}
+
+impl<T> EmpiricalDiscreteParams<T>
+where
+  T: RandomVariable + Eq + Hash + Ord,
+{
+  /// Categories are kept in sorted order so that sampling with a seeded `rng` is reproducible.
+  pub fn new(counts: HashMap<T, u64>) -> Result<Self, DistributionError> {
+    let mut counts = counts
+      .into_iter()
+      .filter(|(_, ni)| 0 < *ni)
+      .collect::<Vec<_>>();
+    counts.sort_by(|a, b| a.0.cmp(&b.0));
+    let total = counts.iter().map(|(_, ni)| ni).sum::<u64>();
+
+    if total == 0 {
+      return Err(DistributionError::InvalidParameters(
+        EmpiricalDiscreteError::CountsAreEmpty.into(),
+      ));
+    }
+
+    Ok(Self { counts, total })
+  }
+
+  /// Counts the occurrences in `samples`.
+  pub fn from_samples(samples: &[T]) -> Result<Self, DistributionError> {
+    let mut counts = HashMap::new();
+    for xi in samples.iter() {
+      *counts.entry(xi.clone()).or_insert(0) += 1;
+    }
+
+    Self::new(counts)
+  }
+
+  /// Most frequent value, the smallest one on ties.
+  pub fn mode(&self) -> &T {
+    let mut mode = &self.counts[0];
+    for c in self.counts.iter() {
+      if mode.1 < c.1 {
+        mode = c;
+      }
+    }
+
+    &mode.0
+  }
+
+  pub fn counts(&self) -> &[(T, u64)] {
+    &self.counts
+  }
+
+  pub fn total(&self) -> u64 {
+    self.total
+  }
+}
+
+impl<T, Rhs, TRhs> Mul<Rhs> for EmpiricalDiscrete<T>
+where
+  T: RandomVariable + Eq + Hash + Ord,
+  Rhs: Distribution<T = TRhs, U = EmpiricalDiscreteParams<T>>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, T, TRhs, EmpiricalDiscreteParams<T>>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<T, Rhs, URhs> BitAnd<Rhs> for EmpiricalDiscrete<T>
+where
+  T: RandomVariable + Eq + Hash + Ord,
+  Rhs: Distribution<T = EmpiricalDiscreteParams<T>, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, T, EmpiricalDiscreteParams<T>, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Distribution, EmpiricalDiscrete, EmpiricalDiscreteParams};
+  use rand::prelude::*;
+  use std::collections::HashMap;
+
+  #[test]
+  fn it_works() {
+    let counts = vec![("a", 1), ("b", 3), ("c", 6), ("d", 0)]
+      .into_iter()
+      .map(|(k, v)| (k.to_string(), v))
+      .collect::<HashMap<_, _>>();
+    let params = EmpiricalDiscreteParams::new(counts.clone()).unwrap();
+    let distribution = EmpiricalDiscrete::new();
+
+    assert_eq!(distribution.p(&"b".to_string(), &params).unwrap(), 0.3);
+    assert_eq!(distribution.p(&"d".to_string(), &params).unwrap(), 0.0);
+    assert_eq!(distribution.p(&"e".to_string(), &params).unwrap(), 0.0);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let n = 10000;
+    let x = distribution.sample_n(n, &params, &mut rng).unwrap();
+
+    for (k, v) in counts.iter() {
+      let freq = x.iter().filter(|&xi| xi == k).count() as f64 / n as f64;
+      assert!((freq - *v as f64 / 10.0).abs() < 0.02);
+    }
+
+    assert!(EmpiricalDiscreteParams::<u64>::new(HashMap::new()).is_err());
+  }
+
+  #[test]
+  fn from_samples() {
+    let samples = vec![2u64, 5, 2, 7, 5, 2, 7, 7, 9, 2];
+    let params = EmpiricalDiscreteParams::from_samples(&samples).unwrap();
+    let distribution = EmpiricalDiscrete::new();
+
+    assert_eq!(params.total(), 10);
+    assert_eq!(params.counts(), &[(2, 4), (5, 2), (7, 3), (9, 1)]);
+    for xi in samples.iter() {
+      let freq = samples.iter().filter(|&x| x == xi).count() as f64 / 10.0;
+      assert_eq!(distribution.p(xi, &params).unwrap(), freq);
+    }
+    assert_eq!(*params.mode(), 2);
+
+    // ties go to the smallest value
+    let tie = EmpiricalDiscreteParams::from_samples(&[3u64, 1, 3, 1]).unwrap();
+    assert_eq!(*tie.mode(), 1);
+
+    assert!(EmpiricalDiscreteParams::<u64>::from_samples(&[]).is_err());
+  }
+}
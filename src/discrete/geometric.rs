@@ -1,5 +1,5 @@
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{DependentJoint, DiscreteDistribution, Distribution, IndependentJoint, RandomVariable};
 use rand::prelude::*;
 use rand_distr::Geometric as RandGeometric;
 use std::{ops::BitAnd, ops::Mul};
@@ -25,7 +25,7 @@ impl Distribution for Geometric {
     Ok((1.0 - p).powi((x - 1) as i32) * p)
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let p = theta.p();
 
     let geometric = match RandGeometric::new(p) {
@@ -37,15 +37,29 @@ impl Distribution for Geometric {
   }
 }
 
+impl DiscreteDistribution for Geometric {
+  /// `p` counts the trials up to and including the first success.
+  fn min_value(&self, _theta: &Self::U) -> u64 {
+    1
+  }
+
+  /// The mass `p (1 - p)^(k - 1)` never grows with `k`.
+  fn mode(&self, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    Ok(self.min_value(theta))
+  }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct GeometricParams {
   p: f64,
 }
 
 impl GeometricParams {
-  pub fn new(p: f64) -> Result<Self, GeometricError> {
+  pub fn new(p: f64) -> Result<Self, DistributionError> {
     if p < 0.0 || 1.0 < p {
-      return Err(GeometricError::PMustBeProbability.into());
+      return Err(DistributionError::InvalidParameters(
+        GeometricError::PMustBeProbability.into(),
+      ));
     }
 
     Ok(Self { p })
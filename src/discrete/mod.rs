@@ -1,7 +1,21 @@
+pub mod bernoulli;
+pub mod bernoulli_glm;
+pub mod beta_binomial;
 pub mod binominal;
+pub mod dirichlet_multinomial;
+pub mod empirical_discrete;
 pub mod geometric;
 pub mod poisson;
+pub mod reservoir_sampling;
+pub mod zero_inflated_poisson;
 
+pub use bernoulli::*;
+pub use bernoulli_glm::*;
+pub use beta_binomial::*;
 pub use binominal::*;
+pub use dirichlet_multinomial::*;
+pub use empirical_discrete::*;
 pub use geometric::*;
 pub use poisson::*;
+pub use reservoir_sampling::*;
+pub use zero_inflated_poisson::*;
@@ -1,5 +1,8 @@
 use crate::DistributionError;
-use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use crate::{
+  check_not_empty, check_probability, ln_gamma, DependentJoint, DiscreteDistribution, Distribution,
+  IndependentJoint, MaximumLikelihood, RandomVariable, Support,
+};
 use rand::prelude::*;
 use rand_distr::Poisson as RandPoisson;
 use std::{ops::BitAnd, ops::Mul};
@@ -13,13 +16,8 @@ pub struct Poisson;
 pub enum PoissonError {
   #[error("'λ' must be positive")]
   LambdaMustBePositive,
-}
-
-fn factorial(num: u64) -> u64 {
-  match num {
-    0 | 1 => 1,
-    _ => factorial(num - 1) * num,
-  }
+  #[error("No count reaches the cumulative mass")]
+  CumulativeMassNotReached,
 }
 
 impl Distribution for Poisson {
@@ -27,12 +25,18 @@ impl Distribution for Poisson {
   type U = PoissonParams;
 
   fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?x\ln\lambda-\lambda-\ln%20x!), which stays finite where `x!` overflows.
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
     let lambda = theta.lambda();
+    let x = *x as f64;
 
-    Ok(lambda.powi(*x as i32) / factorial(*x) as f64 * (-lambda).exp())
+    Ok(x * lambda.ln() - lambda - ln_gamma(x + 1.0))
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let lambda = theta.lambda();
 
     let poisson = match RandPoisson::new(lambda) {
@@ -44,15 +48,81 @@ impl Distribution for Poisson {
   }
 }
 
+/// Masses `(k, p(k))` in increasing `k`, from the first one that does not underflow.
+/// They are walked from the mode `m` of ![tex](https://latex.codecogs.com/svg.latex?\ln%20p%28m%29%3Dm\ln\lambda-\lambda-\ln%20m!) by the recurrence ![tex](https://latex.codecogs.com/svg.latex?p%28k%29%3Dp%28k-1%29\lambda/k) in log space, so the bulk stays representable where ![tex](https://latex.codecogs.com/svg.latex?e%5E%7B-\lambda%7D) underflows.
+fn masses(lambda: f64) -> impl Iterator<Item = (u64, f64)> {
+  let ln_lambda = lambda.ln();
+  let m = lambda.floor();
+  let ln_pm = m * ln_lambda - lambda - ln_gamma(m + 1.0);
+  let m = m as u64;
+
+  let mut below = vec![];
+  let mut ln_pk = ln_pm;
+  for k in (0..m).rev() {
+    ln_pk += ((k + 1) as f64).ln() - ln_lambda;
+    let pk = ln_pk.exp();
+    if pk == 0.0 {
+      break;
+    }
+    below.push((k, pk));
+  }
+  below.reverse();
+
+  let above = (m..).scan(ln_pm, move |ln_pk, k| {
+    if k > m {
+      *ln_pk += ln_lambda - (k as f64).ln();
+    }
+    Some((k, ln_pk.exp()))
+  });
+
+  below.into_iter().chain(above)
+}
+
+impl DiscreteDistribution for Poisson {
+  fn cmf(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(
+      masses(theta.lambda())
+        .take_while(|&(k, _)| k <= *x)
+        .map(|(_, pk)| pk)
+        .sum(),
+    )
+  }
+
+  fn inverse_cmf(&self, u: f64, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    check_probability(u)?;
+    let lambda = theta.lambda();
+
+    let mut cumulative = 0.0;
+    for (k, pk) in masses(lambda) {
+      cumulative += pk;
+      // past the mode the masses only shrink, so no further k can reach `u`
+      if u <= cumulative || (k as f64 > lambda && pk == 0.0) {
+        return Ok(k);
+      }
+    }
+
+    Err(DistributionError::Others(
+      PoissonError::CumulativeMassNotReached.into(),
+    ))
+  }
+
+  fn mode(&self, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    // λ - 1 and λ tie when λ is an integer, and the smaller one is the mode
+    Ok(theta.lambda().ceil() as u64 - 1)
+  }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct PoissonParams {
   lambda: f64,
 }
 
 impl PoissonParams {
-  pub fn new(lambda: f64) -> Result<Self, PoissonError> {
+  pub fn new(lambda: f64) -> Result<Self, DistributionError> {
     if lambda <= 0.0 {
-      return Err(PoissonError::LambdaMustBePositive.into());
+      return Err(DistributionError::InvalidParameters(
+        PoissonError::LambdaMustBePositive.into(),
+      ));
     }
 
     Ok(Self { lambda })
@@ -63,6 +133,26 @@ impl PoissonParams {
   }
 }
 
+impl Support for Poisson {
+  /// Every `u64` is a count.
+  fn in_support(&self, _x: &Self::T, _theta: &Self::U) -> bool {
+    true
+  }
+
+  fn support_bounds(&self, _theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    (Some(0.0), None)
+  }
+}
+
+impl MaximumLikelihood for Poisson {
+  fn fit(samples: &[Self::T]) -> Result<Self::U, DistributionError> {
+    check_not_empty(samples)?;
+    let mean = samples.iter().sum::<u64>() as f64 / samples.len() as f64;
+
+    PoissonParams::new(mean)
+  }
+}
+
 impl<Rhs, TRhs> Mul<Rhs> for Poisson
 where
   Rhs: Distribution<T = TRhs, U = PoissonParams>,
@@ -89,8 +179,20 @@ where
 
 #[cfg(test)]
 mod tests {
+  use crate::{Distribution, Poisson, PoissonParams};
+
   #[test]
   fn it_works() {
-    assert_eq!(2 + 2, 4);
+    let params = PoissonParams::new(2.0).unwrap();
+    let expected = 8.0 / 6.0 * (-2.0f64).exp();
+    assert!((Poisson.p(&3, &params).unwrap() - expected).abs() < 1e-12);
+    assert!((Poisson.ln_p(&3, &params).unwrap() - expected.ln()).abs() < 1e-12);
+
+    // 30! overflows u64
+    let params = PoissonParams::new(100.0).unwrap();
+    let total = (0..400)
+      .map(|x| Poisson.p(&x, &params).unwrap())
+      .sum::<f64>();
+    assert!((total - 1.0).abs() < 1e-10);
   }
 }
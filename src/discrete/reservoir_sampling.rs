@@ -0,0 +1,72 @@
+use rand::prelude::*;
+
+/// Draws one element uniformly from a stream of unknown length by Algorithm R.
+/// Returns `None` for an empty stream.
+pub fn reservoir_sample<T, I>(iter: I, rng: &mut dyn RngCore) -> Option<T>
+where
+  I: Iterator<Item = T>,
+{
+  let mut selected = None;
+
+  for (i, xi) in iter.enumerate() {
+    // keep the (i + 1)-th element with probability 1 / (i + 1)
+    if rng.gen_range(0..=i) == 0 {
+      selected = Some(xi);
+    }
+  }
+
+  selected
+}
+
+/// Draws `k` elements uniformly without replacement from a stream of unknown length by Algorithm R.
+/// Returns all the elements if the stream has fewer than `k`.
+pub fn reservoir_sample_k<T, I>(iter: I, k: usize, rng: &mut dyn RngCore) -> Vec<T>
+where
+  I: Iterator<Item = T>,
+{
+  let mut reservoir = Vec::with_capacity(k);
+
+  for (i, xi) in iter.enumerate() {
+    if i < k {
+      reservoir.push(xi);
+      continue;
+    }
+
+    let j = rng.gen_range(0..=i);
+    if j < k {
+      reservoir[j] = xi;
+    }
+  }
+
+  reservoir
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{reservoir_sample, reservoir_sample_k};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let n = 5;
+    let runs = 50000;
+
+    let mut counts = vec![0; n];
+    let mut counts_k = vec![0; n];
+    for _ in 0..runs {
+      counts[reservoir_sample(0..n, &mut rng).unwrap()] += 1;
+      for i in reservoir_sample_k(0..n, 2, &mut rng) {
+        counts_k[i] += 1;
+      }
+    }
+
+    for i in 0..n {
+      assert!((counts[i] as f64 / runs as f64 - 0.2).abs() < 0.01);
+      assert!((counts_k[i] as f64 / runs as f64 - 0.4).abs() < 0.01);
+    }
+
+    assert_eq!(reservoir_sample(0..0, &mut rng), None::<usize>);
+    assert_eq!(reservoir_sample_k(0..3, 5, &mut rng), vec![0, 1, 2]);
+  }
+}
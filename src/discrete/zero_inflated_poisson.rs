@@ -0,0 +1,148 @@
+use crate::DistributionError;
+use crate::{
+  ln_gamma, DependentJoint, Distribution, IndependentJoint, Poisson, PoissonParams, RandomVariable,
+  Support,
+};
+use rand::prelude::*;
+use std::{ops::BitAnd, ops::Mul};
+
+/// # ZeroInflatedPoisson
+/// ![tex](https://latex.codecogs.com/svg.latex?\pi%5Cdelta_0%28x%29%2B%281-\pi%29\mathrm%7BPoisson%7D%28x%7C\lambda%29)
+#[derive(Clone, Debug)]
+pub struct ZeroInflatedPoisson;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ZeroInflatedPoissonError {
+  #[error("'π' must be probability.")]
+  PiMustBeProbability,
+}
+
+impl Distribution for ZeroInflatedPoisson {
+  type T = u64;
+  type U = ZeroInflatedPoissonParams;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let pi = theta.pi();
+    let lambda = theta.lambda();
+    // in log space, as x! overflows u64 above 20
+    let x_f64 = *x as f64;
+    let poisson = (1.0 - pi) * (x_f64 * lambda.ln() - lambda - ln_gamma(x_f64 + 1.0)).exp();
+
+    if *x == 0 {
+      return Ok(pi + poisson);
+    }
+
+    Ok(poisson)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    // structural zero with probability π
+    if rng.gen_bool(theta.pi()) {
+      return Ok(0);
+    }
+
+    Poisson.sample(theta.poisson(), rng)
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZeroInflatedPoissonParams {
+  pi: f64,
+  poisson: PoissonParams,
+}
+
+impl ZeroInflatedPoissonParams {
+  pub fn new(pi: f64, lambda: f64) -> Result<Self, DistributionError> {
+    if !(0.0..=1.0).contains(&pi) {
+      return Err(DistributionError::InvalidParameters(
+        ZeroInflatedPoissonError::PiMustBeProbability.into(),
+      ));
+    }
+    let poisson = PoissonParams::new(lambda)?;
+
+    Ok(Self { pi, poisson })
+  }
+
+  pub fn pi(&self) -> f64 {
+    self.pi
+  }
+
+  pub fn lambda(&self) -> f64 {
+    self.poisson.lambda()
+  }
+
+  pub fn poisson(&self) -> &PoissonParams {
+    &self.poisson
+  }
+}
+
+impl Support for ZeroInflatedPoisson {
+  /// Every `u64` is a count.
+  fn in_support(&self, _x: &Self::T, _theta: &Self::U) -> bool {
+    true
+  }
+
+  fn support_bounds(&self, _theta: &Self::U) -> (Option<f64>, Option<f64>) {
+    (Some(0.0), None)
+  }
+}
+
+impl<Rhs, TRhs> Mul<Rhs> for ZeroInflatedPoisson
+where
+  Rhs: Distribution<T = TRhs, U = ZeroInflatedPoissonParams>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, u64, TRhs, ZeroInflatedPoissonParams>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<Rhs, URhs> BitAnd<Rhs> for ZeroInflatedPoisson
+where
+  Rhs: Distribution<T = ZeroInflatedPoissonParams, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, u64, ZeroInflatedPoissonParams, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Distribution, ZeroInflatedPoisson, ZeroInflatedPoissonParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = ZeroInflatedPoissonParams::new(0.3, 2.0).unwrap();
+
+    let p_sum = (0..=20)
+      .map(|x| ZeroInflatedPoisson.p(&x, &params).unwrap())
+      .sum::<f64>();
+    assert!((p_sum - 1.0).abs() < 1e-10);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let n = 10000;
+    let x = ZeroInflatedPoisson.sample_n(n, &params, &mut rng).unwrap();
+
+    let zeros = x.iter().filter(|&&xi| xi == 0).count() as f64 / n as f64;
+    let poisson_zeros = (-2.0f64).exp();
+    let expected = 0.3 + 0.7 * poisson_zeros;
+
+    assert!(poisson_zeros + 0.1 < zeros);
+    assert!((zeros - expected).abs() < 0.02);
+
+    // p(30) = (1 - π) λ^30 e^-λ / 30!, by the recurrence p(k) = p(k - 1) λ / k
+    let params = ZeroInflatedPoissonParams::new(0.3, 25.0).unwrap();
+    let expected = (1..=30).fold(0.7 * (-25.0f64).exp(), |pk, k| pk * 25.0 / k as f64);
+    let p = ZeroInflatedPoisson.p(&30, &params).unwrap();
+    assert!((p - expected).abs() < 1e-12 * expected);
+
+    assert!(ZeroInflatedPoissonParams::new(1.5, 2.0).is_err());
+    assert!(ZeroInflatedPoissonParams::new(0.3, 0.0).is_err());
+  }
+}
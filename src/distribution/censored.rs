@@ -0,0 +1,164 @@
+use crate::DistributionError;
+use crate::{
+  CumulativeDistribution, DependentJoint, Distribution, IndependentJoint, RandomVariable,
+};
+use rand::prelude::RngCore;
+use std::{ops::BitAnd, ops::Mul};
+
+/// An observation that may be known only up to an interval.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CensoredObservation {
+  Exact(f64),
+  /// Only known to exceed the value.
+  Right(f64),
+  /// Only known to be below the value.
+  Left(f64),
+  /// Only known to lie between `lo` and `hi`.
+  Interval(f64, f64),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CensoredError {
+  #[error("Interval must satisfy 'lo' <= 'hi'")]
+  InvalidInterval,
+}
+
+/// # Censored
+/// Likelihood of possibly censored observations of `D`.
+#[derive(Clone, Debug)]
+pub struct Censored<D>
+where
+  D: CumulativeDistribution<T = f64>,
+{
+  distribution: D,
+}
+
+impl<D> Censored<D>
+where
+  D: CumulativeDistribution<T = f64>,
+{
+  pub fn new(distribution: D) -> Self {
+    Self { distribution }
+  }
+}
+
+impl<D> Distribution for Censored<D>
+where
+  D: CumulativeDistribution<T = f64>,
+{
+  type T = CensoredObservation;
+  type U = D::U;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    match *x {
+      CensoredObservation::Exact(x) => self.distribution.p(&x, theta),
+      CensoredObservation::Right(x) => Ok(1.0 - self.distribution.cdf(&x, theta)?),
+      CensoredObservation::Left(x) => self.distribution.cdf(&x, theta),
+      CensoredObservation::Interval(lo, hi) => {
+        if hi < lo {
+          return Err(DistributionError::InvalidParameters(
+            CensoredError::InvalidInterval.into(),
+          ));
+        }
+
+        Ok(self.distribution.cdf(&hi, theta)? - self.distribution.cdf(&lo, theta)?)
+      }
+    }
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    match x {
+      CensoredObservation::Exact(x) => self.distribution.ln_p(x, theta),
+      _ => Ok(self.p(x, theta)?.ln()),
+    }
+  }
+
+  /// Draws an uncensored observation.
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    Ok(CensoredObservation::Exact(
+      self.distribution.sample(theta, rng)?,
+    ))
+  }
+}
+
+impl<D, Rhs, TRhs> Mul<Rhs> for Censored<D>
+where
+  D: CumulativeDistribution<T = f64>,
+  Rhs: Distribution<T = TRhs, U = D::U>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, CensoredObservation, TRhs, D::U>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<D, Rhs, URhs> BitAnd<Rhs> for Censored<D>
+where
+  D: CumulativeDistribution<T = f64>,
+  Rhs: Distribution<T = D::U, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, CensoredObservation, D::U, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Censored, CensoredObservation, Distribution, Exp, ExpParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let lambda = 1.0;
+    let c = 1.0;
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    // survival times right-censored at the end of the study
+    let x = Exp
+      .sample_n(5000, &ExpParams::new(lambda).unwrap(), &mut rng)
+      .unwrap()
+      .into_iter()
+      .map(|xi| {
+        if xi < c {
+          CensoredObservation::Exact(xi)
+        } else {
+          CensoredObservation::Right(c)
+        }
+      })
+      .collect::<Vec<_>>();
+
+    let censored = Censored::new(Exp);
+    let ln_l = |lambda: f64, ignore: bool| {
+      let params = ExpParams::new(lambda).unwrap();
+      x.iter()
+        .map(|xi| match (xi, ignore) {
+          (CensoredObservation::Right(c), true) => Exp.ln_p(c, &params).unwrap(),
+          _ => censored.ln_p(xi, &params).unwrap(),
+        })
+        .sum::<f64>()
+    };
+    let fit = |ignore: bool| {
+      (1..300)
+        .map(|i| i as f64 * 0.01)
+        .max_by(|&a, &b| ln_l(a, ignore).partial_cmp(&ln_l(b, ignore)).unwrap())
+        .unwrap()
+    };
+
+    assert!((fit(false) - lambda).abs() < 0.05);
+    assert!(lambda + 0.2 < fit(true));
+
+    let params = ExpParams::new(lambda).unwrap();
+    let interval = censored
+      .p(&CensoredObservation::Interval(0.5, 1.0), &params)
+      .unwrap();
+    assert!((interval - ((-0.5f64).exp() - (-1.0f64).exp())).abs() < 1e-12);
+    assert!(censored
+      .p(&CensoredObservation::Interval(1.0, 0.5), &params)
+      .is_err());
+  }
+}
@@ -0,0 +1,9 @@
+use crate::{Distribution, DistributionError};
+
+/// # ConditionDifferentiableDistribution
+/// ![tex](https://latex.codecogs.com/svg.latex?\frac%7B\partial%20\ln%20p%28x%7C\mathbf{\theta}%29%7D%7B\partial%20\mathbf{\theta}%7D)
+///
+/// The layout of the returned vector is defined by each implementation.
+pub trait ConditionDifferentiableDistribution: Distribution {
+  fn ln_diff_condition(&self, x: &Self::T, theta: &Self::U) -> Result<Vec<f64>, DistributionError>;
+}
@@ -0,0 +1,223 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use rand::prelude::RngCore;
+use std::{
+  fmt::Debug,
+  ops::{BitAnd, Mul},
+};
+
+#[derive(Clone)]
+pub struct ConditionedDistribution<'a, D, T, U1, U2>
+where
+  D: Distribution<T = T, U = U1>,
+  T: RandomVariable,
+  U1: RandomVariable,
+  U2: RandomVariable,
+{
+  distribution: D,
+  condition: &'a (dyn Fn(&U2) -> Result<U1, DistributionError> + Send + Sync),
+}
+
+impl<'a, D, T, U1, U2> ConditionedDistribution<'a, D, T, U1, U2>
+where
+  D: Distribution<T = T, U = U1>,
+  T: RandomVariable,
+  U1: RandomVariable,
+  U2: RandomVariable,
+{
+  pub fn new(
+    distribution: D,
+    condition: &'a (dyn Fn(&U2) -> Result<U1, DistributionError> + Send + Sync),
+  ) -> Self {
+    Self {
+      distribution,
+      condition,
+    }
+  }
+}
+
+impl<'a, D, T, U1, U2> Debug for ConditionedDistribution<'a, D, T, U1, U2>
+where
+  D: Distribution<T = T, U = U1>,
+  T: RandomVariable,
+  U1: RandomVariable,
+  U2: RandomVariable,
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "ConditionedDistribution {{ distribution: {:#?} }}",
+      self.distribution
+    )
+  }
+}
+
+impl<'a, D, T, U1, U2> Distribution for ConditionedDistribution<'a, D, T, U1, U2>
+where
+  D: Distribution<T = T, U = U1>,
+  T: RandomVariable,
+  U1: RandomVariable,
+  U2: RandomVariable,
+{
+  type T = T;
+  type U = U2;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    self.distribution.p(x, &(self.condition)(theta)?)
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    self.distribution.ln_p(x, &(self.condition)(theta)?)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    self.distribution.sample(&(self.condition)(theta)?, rng)
+  }
+}
+
+pub trait ConditionableDistribution: Distribution + Sized {
+  fn condition<'a, U2>(
+    self,
+    condition: &'a (dyn Fn(&U2) -> Result<Self::U, DistributionError> + Send + Sync),
+  ) -> ConditionedDistribution<'a, Self, Self::T, Self::U, U2>
+  where
+    U2: RandomVariable;
+}
+
+impl<D, T, U1> ConditionableDistribution for D
+where
+  D: Distribution<T = T, U = U1>,
+  T: RandomVariable,
+  U1: RandomVariable,
+{
+  fn condition<'a, U2>(
+    self,
+    condition: &'a (dyn Fn(&U2) -> Result<Self::U, DistributionError> + Send + Sync),
+  ) -> ConditionedDistribution<'a, Self, Self::T, Self::U, U2>
+  where
+    U2: RandomVariable,
+  {
+    ConditionedDistribution::<Self, Self::T, Self::U, U2>::new(self, condition)
+  }
+}
+
+impl<'a, D, T, U1, U2, Rhs, TRhs> Mul<Rhs> for ConditionedDistribution<'a, D, T, U1, U2>
+where
+  D: Distribution<T = T, U = U1>,
+  T: RandomVariable,
+  U1: RandomVariable,
+  U2: RandomVariable,
+  Rhs: Distribution<T = TRhs, U = U2>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, T, TRhs, U2>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<'a, D, T, U1, U2, Rhs, URhs> BitAnd<Rhs> for ConditionedDistribution<'a, D, T, U1, U2>
+where
+  D: Distribution<T = T, U = U1>,
+  T: RandomVariable,
+  U1: RandomVariable,
+  U2: RandomVariable,
+  Rhs: Distribution<T = U2, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, T, U2, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+/// A distribution bound to its parameters, so that `p` and `sample` no longer take `theta`.
+#[derive(Clone, Debug)]
+pub struct Conditioned<D>
+where
+  D: Distribution,
+{
+  distribution: D,
+  theta: D::U,
+}
+
+impl<D> Conditioned<D>
+where
+  D: Distribution,
+{
+  pub fn new(distribution: D, theta: D::U) -> Self {
+    Self {
+      distribution,
+      theta,
+    }
+  }
+
+  pub fn distribution(&self) -> &D {
+    &self.distribution
+  }
+
+  pub fn theta(&self) -> &D::U {
+    &self.theta
+  }
+
+  pub fn p(&self, x: &D::T) -> Result<f64, DistributionError> {
+    self.distribution.p(x, &self.theta)
+  }
+
+  pub fn ln_p(&self, x: &D::T) -> Result<f64, DistributionError> {
+    self.distribution.ln_p(x, &self.theta)
+  }
+
+  pub fn sample(&self, rng: &mut dyn RngCore) -> Result<D::T, DistributionError> {
+    self.distribution.sample(&self.theta, rng)
+  }
+
+  pub fn eject(self) -> (D, D::U) {
+    (self.distribution, self.theta)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::distribution::Distribution;
+  use crate::*;
+  use rand::prelude::*;
+  #[test]
+  fn it_works() {
+    // x|mu ~ N(mu, 1), mu ~ N(0, 1)
+    let model = Normal.condition(&|mu: &f64| NormalParams::new(*mu, 1.0)) & Normal;
+    let params = NormalParams::new(0.0, 1.0).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let (x, mu) = model.sample(&params, &mut rng).unwrap();
+    let expected =
+      Normal.p(&x, &NormalParams::new(mu, 1.0).unwrap()).unwrap() * Normal.p(&mu, &params).unwrap();
+
+    assert!((model.p(&(x, mu), &params).unwrap() - expected).abs() < 1e-12);
+
+    // errors from the condition are propagated
+    let model = Normal.condition(&|sigma: &f64| NormalParams::new(0.0, *sigma));
+    assert!(model.p(&0.0, &-1.0).is_err());
+    assert!(model.sample(&-1.0, &mut rng).is_err());
+  }
+
+  #[test]
+  fn conditioned() {
+    let params = NormalParams::new(1.0, 2.0).unwrap();
+    let normal = Conditioned::new(Normal, params.clone());
+
+    assert_eq!(normal.p(&0.5).unwrap(), Normal.p(&0.5, &params).unwrap());
+    assert_eq!(
+      normal.ln_p(&0.5).unwrap(),
+      Normal.ln_p(&0.5, &params).unwrap()
+    );
+
+    let x = normal.sample(&mut StdRng::from_seed([1; 32])).unwrap();
+    let expected = Normal
+      .sample(&params, &mut StdRng::from_seed([1; 32]))
+      .unwrap();
+    assert_eq!(x, expected);
+  }
+}
@@ -1,6 +1,6 @@
 use crate::DistributionError;
 use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
-use rand::prelude::StdRng;
+use rand::prelude::RngCore;
 use std::{
   fmt::Debug,
   ops::{BitAnd, Mul},
@@ -69,7 +69,7 @@ where
     self.distribution.p(&(self.inv)(x)?, theta)
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let value = self.distribution.sample(theta, rng)?;
 
     Ok((self.map)(value)?)
@@ -0,0 +1,24 @@
+use crate::{Distribution, DistributionError};
+
+/// # CumulativeDistribution
+/// ![tex](https://latex.codecogs.com/svg.latex?F%28x%7C\mathbf{\theta}%29%3DP%28X\leq%20x%7C\mathbf{\theta}%29)
+pub trait CumulativeDistribution: Distribution {
+  fn cdf(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError>;
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{CumulativeDistribution, Exp, ExpParams, Normal, NormalParams};
+
+  #[test]
+  fn it_works() {
+    let exp = ExpParams::new(2.0).unwrap();
+    assert_eq!(Exp.cdf(&-1.0, &exp).unwrap(), 0.0);
+    assert!((Exp.cdf(&1.0, &exp).unwrap() - (1.0 - (-2.0f64).exp())).abs() < 1e-12);
+
+    let normal = NormalParams::new(1.0, 2.0).unwrap();
+    assert!((Normal.cdf(&1.0, &normal).unwrap() - 0.5).abs() < 1e-12);
+    // one σ above the mean
+    assert!((Normal.cdf(&3.0, &normal).unwrap() - 0.841344746).abs() < 1e-8);
+  }
+}
@@ -1,5 +1,5 @@
 use crate::DistributionError;
-use rand::prelude::StdRng;
+use rand::prelude::RngCore;
 
 use crate::{Distribution, IndependentJoint, RandomVariable};
 use std::{ops::BitAnd, ops::Mul};
@@ -47,7 +47,11 @@ where
     Ok(self.lhs.p(&x.0, &x.1)? * self.rhs.p(&x.1, theta)?)
   }
 
-  fn sample(&self, theta: &UR, rng: &mut StdRng) -> Result<(T, UL), DistributionError> {
+  fn ln_p(&self, x: &(T, UL), theta: &UR) -> Result<f64, DistributionError> {
+    Ok(self.lhs.ln_p(&x.0, &x.1)? + self.rhs.ln_p(&x.1, theta)?)
+  }
+
+  fn sample(&self, theta: &UR, rng: &mut dyn RngCore) -> Result<(T, UL), DistributionError> {
     let rhs = self.rhs.sample(theta, rng)?;
     Ok((self.lhs.sample(&rhs, rng)?, rhs))
   }
@@ -103,4 +107,35 @@ mod tests {
 
     println!("{:#?}", x);
   }
+
+  #[test]
+  fn p() {
+    // x|lambda ~ Exp(lambda), lambda ~ Gamma(2, 1)
+    let model = Exp.condition(&|lambda: &f64| ExpParams::new(*lambda)) & Gamma;
+    let params = GammaParams::new(2.0, 1.0).unwrap();
+    let x = (0.5, 3.0);
+
+    // 3 e^{-1.5} * 3 e^{-3}
+    let expected = 3.0 * (-1.5f64).exp() * 3.0 * (-3.0f64).exp();
+
+    assert!((model.p(&x, &params).unwrap() - expected).abs() < 1e-12);
+    assert!((model.ln_p(&x, &params).unwrap() - expected.ln()).abs() < 1e-12);
+  }
+
+  #[test]
+  fn sample() {
+    // x|mu ~ N(mu, 1), mu ~ N(1, 2), so that x ~ N(1, sqrt(5))
+    let model = Normal.condition(&|mu: &f64| NormalParams::new(*mu, 1.0)) & Normal;
+    let params = NormalParams::new(1.0, 2.0).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let n = 20000;
+    let x = model.sample_n(n, &params, &mut rng).unwrap();
+
+    let mean = x.iter().map(|xi| xi.0).sum::<f64>() / n as f64;
+    let var = x.iter().map(|xi| (xi.0 - mean).powi(2)).sum::<f64>() / n as f64;
+
+    assert!((mean - 1.0).abs() < 0.1);
+    assert!((var - 5.0).abs() < 0.3);
+  }
 }
@@ -0,0 +1,194 @@
+use crate::{check_probability, Distribution, DistributionError};
+
+/// # DiscreteDistribution
+/// ![tex](https://latex.codecogs.com/svg.latex?F%28x%7C\mathbf{\theta}%29%3D\sum_%7Bk\leq%20x%7Dp%28k%7C\mathbf{\theta}%29)
+pub trait DiscreteDistribution: Distribution<T = u64> {
+  /// Smallest value of the support.
+  fn min_value(&self, _theta: &Self::U) -> u64 {
+    0
+  }
+
+  /// Largest value of the support if it is bounded.
+  fn max_value(&self, _theta: &Self::U) -> Option<u64> {
+    None
+  }
+
+  fn cmf(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let min = self.min_value(theta);
+    let max = self.max_value(theta).map_or(*x, |max| max.min(*x));
+
+    if *x < min {
+      return Ok(0.0);
+    }
+
+    (min..=max).map(|k| self.p(&k, theta)).sum()
+  }
+
+  /// Smallest `k` with `cmf(k) >= u`.
+  /// For an unbounded support the search stops once the mass underflows to zero past its bulk.
+  fn inverse_cmf(&self, u: f64, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    check_probability(u)?;
+
+    let min = self.min_value(theta);
+    let max = self.max_value(theta);
+
+    let mut cumulative = 0.0;
+    let mut seen_mass = false;
+    let mut k = min;
+    loop {
+      let pk = self.p(&k, theta)?;
+      cumulative += pk;
+      seen_mass |= pk > 0.0;
+
+      if u <= cumulative || max == Some(k) || (seen_mass && pk == 0.0) {
+        return Ok(k);
+      }
+      k += 1;
+    }
+  }
+
+  /// Most probable value, the smallest one on ties.
+  /// The scan stops once the mass left unseen can no longer exceed the best one found.
+  fn mode(&self, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    let max = self.max_value(theta);
+
+    let mut mode = self.min_value(theta);
+    let mut best = self.p(&mode, theta)?;
+    let mut cumulative = best;
+    let mut seen_mass = best > 0.0;
+    let mut k = mode;
+    while max != Some(k) && best < 1.0 - cumulative {
+      k += 1;
+      let pk = self.p(&k, theta)?;
+      if seen_mass && pk == 0.0 {
+        break;
+      }
+      cumulative += pk;
+      seen_mass |= pk > 0.0;
+      if best < pk {
+        mode = k;
+        best = pk;
+      }
+    }
+
+    Ok(mode)
+  }
+
+  fn median(&self, theta: &Self::U) -> Result<Self::T, DistributionError> {
+    self.inverse_cmf(0.5, theta)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    Binominal, BinominalParams, DiscreteDistribution, Distribution, DistributionError, Geometric,
+    GeometricParams, Poisson, PoissonParams,
+  };
+  use rand::prelude::*;
+
+  fn check<D>(distribution: &D, theta: &D::U, until: u64)
+  where
+    D: DiscreteDistribution,
+  {
+    let mut previous = 0.0;
+    for k in 0..=until {
+      let cmf = distribution.cmf(&k, theta).unwrap();
+      assert!(previous <= cmf);
+      previous = cmf;
+
+      if cmf > 0.0 && cmf < 1.0 - 1e-12 {
+        assert_eq!(distribution.inverse_cmf(cmf, theta).unwrap(), k);
+      }
+    }
+    assert!((previous - 1.0).abs() < 1e-8);
+  }
+
+  #[test]
+  fn it_works() {
+    check(&Poisson, &PoissonParams::new(3.5).unwrap(), 40);
+    check(&Binominal, &BinominalParams::new(12, 0.3).unwrap(), 15);
+    check(&Geometric, &GeometricParams::new(0.4).unwrap(), 60);
+
+    // the recurrence keeps working where the factorial in `p` would overflow
+    let large = PoissonParams::new(50.0).unwrap();
+    assert!((Poisson.cmf(&200, &large).unwrap() - 1.0).abs() < 1e-10);
+    assert!(Poisson.inverse_cmf(1.5, &large).is_err());
+
+    // e^-λ underflows, but the masses around the mode do not
+    let huge = PoissonParams::new(1000.0).unwrap();
+    check(&Poisson, &huge, 1300);
+    assert!(0.5 < Poisson.cmf(&1000, &huge).unwrap());
+    assert_eq!(Poisson.inverse_cmf(0.5, &huge).unwrap(), 1000);
+  }
+
+  /// Falls back to the trait defaults through `p` and `cmf`.
+  #[derive(Clone, Debug)]
+  struct Scan<D>(D);
+
+  impl<D> Distribution for Scan<D>
+  where
+    D: DiscreteDistribution,
+  {
+    type T = u64;
+    type U = D::U;
+
+    fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+      self.0.p(x, theta)
+    }
+
+    fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+      self.0.sample(theta, rng)
+    }
+  }
+
+  impl<D> DiscreteDistribution for Scan<D>
+  where
+    D: DiscreteDistribution,
+  {
+    fn min_value(&self, theta: &Self::U) -> u64 {
+      self.0.min_value(theta)
+    }
+
+    fn max_value(&self, theta: &Self::U) -> Option<u64> {
+      self.0.max_value(theta)
+    }
+  }
+
+  #[test]
+  fn mode_and_median() {
+    let poisson = PoissonParams::new(3.5).unwrap();
+    assert_eq!(Poisson.mode(&poisson).unwrap(), 3);
+    assert_eq!(Scan(Poisson).mode(&poisson).unwrap(), 3);
+    assert_eq!(Poisson.median(&poisson).unwrap(), 3);
+
+    let binominal = BinominalParams::new(12, 0.3).unwrap();
+    assert_eq!(Binominal.mode(&binominal).unwrap(), 3);
+    assert_eq!(Scan(Binominal).mode(&binominal).unwrap(), 3);
+    assert_eq!(Binominal.median(&binominal).unwrap(), 4);
+    let certain = BinominalParams::new(5, 1.0).unwrap();
+    assert_eq!(Binominal.mode(&certain).unwrap(), 5);
+    assert_eq!(Scan(Binominal).mode(&certain).unwrap(), 5);
+    let impossible = BinominalParams::new(5, 0.0).unwrap();
+    assert_eq!(Binominal.mode(&impossible).unwrap(), 0);
+    assert_eq!(Scan(Binominal).mode(&impossible).unwrap(), 0);
+
+    // p(2) = p(3) for both, and ties resolve to the smaller value
+    let poisson = PoissonParams::new(3.0).unwrap();
+    assert_eq!(Poisson.mode(&poisson).unwrap(), 2);
+    assert_eq!(Scan(Poisson).mode(&poisson).unwrap(), 2);
+    let binominal = BinominalParams::new(5, 0.5).unwrap();
+    assert_eq!(Binominal.mode(&binominal).unwrap(), 2);
+    assert_eq!(Scan(Binominal).mode(&binominal).unwrap(), 2);
+    let poisson = PoissonParams::new(0.5).unwrap();
+    assert_eq!(Poisson.mode(&poisson).unwrap(), 0);
+    assert_eq!(Scan(Poisson).mode(&poisson).unwrap(), 0);
+
+    // the support starts at the first trial
+    let geometric = GeometricParams::new(0.4).unwrap();
+    assert_eq!(Geometric.mode(&geometric).unwrap(), 1);
+    assert_eq!(Scan(Geometric).mode(&geometric).unwrap(), 1);
+    // 1 - 0.6^2 = 0.64 is the first cumulative mass above one half
+    assert_eq!(Geometric.median(&geometric).unwrap(), 2);
+  }
+}
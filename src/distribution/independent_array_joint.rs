@@ -1,6 +1,6 @@
 use crate::DistributionError;
 use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
-use rand::prelude::StdRng;
+use rand::prelude::RngCore;
 use std::iter::Iterator;
 use std::{ops::BitAnd, ops::Mul};
 
@@ -32,7 +32,7 @@ where
       .product()
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     self
       .distributions
       .iter()
@@ -1,6 +1,6 @@
 use crate::DistributionError;
 use crate::{DependentJoint, Distribution, RandomVariable};
-use rand::prelude::StdRng;
+use rand::prelude::RngCore;
 use std::{ops::BitAnd, ops::Mul};
 
 /// # IndependentJoint
@@ -46,7 +46,11 @@ where
     Ok(self.lhs.p(&x.0, theta)? * self.rhs.p(&x.1, theta)?)
   }
 
-  fn sample(&self, theta: &U, rng: &mut StdRng) -> Result<(TL, TR), DistributionError> {
+  fn ln_p(&self, x: &(TL, TR), theta: &U) -> Result<f64, DistributionError> {
+    Ok(self.lhs.ln_p(&x.0, theta)? + self.rhs.ln_p(&x.1, theta)?)
+  }
+
+  fn sample(&self, theta: &U, rng: &mut dyn RngCore) -> Result<(TL, TR), DistributionError> {
     Ok((self.lhs.sample(theta, rng)?, self.rhs.sample(theta, rng)?))
   }
 }
@@ -102,4 +106,16 @@ mod tests {
 
     println!("{:#?}", x);
   }
+
+  #[test]
+  fn p() {
+    let model = Normal * Normal;
+    let params = NormalParams::new(1.0, 2.0).unwrap();
+    let x = (0.5, -1.5);
+
+    let expected = Normal.p(&x.0, &params).unwrap() * Normal.p(&x.1, &params).unwrap();
+
+    assert!((model.p(&x, &params).unwrap() - expected).abs() < 1e-12);
+    assert!((model.ln_p(&x, &params).unwrap() - expected.ln()).abs() < 1e-12);
+  }
 }
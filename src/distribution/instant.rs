@@ -1,19 +1,24 @@
 use crate::DistributionError;
 use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
-use rand::prelude::StdRng;
+use rand::prelude::RngCore;
 use std::{
   fmt::Debug,
   ops::{BitAnd, Mul},
 };
 
+type DensityFn<'a, T, U> = dyn Fn(&T, &U) -> Result<f64, DistributionError> + Send + Sync + 'a;
+
+/// A distribution defined on the spot by a density closure and a sampling closure.
+/// Errors returned by the closures are propagated unchanged.
 #[derive(Clone)]
 pub struct InstantDistribution<'a, T, U>
 where
   T: RandomVariable,
   U: RandomVariable,
 {
-  p: &'a (dyn Fn(&T, &U) -> Result<f64, DistributionError> + Send + Sync),
-  sample: &'a (dyn Fn(&U, &mut StdRng) -> Result<T, DistributionError> + Send + Sync),
+  p: &'a DensityFn<'a, T, U>,
+  ln_p: Option<&'a DensityFn<'a, T, U>>,
+  sample: &'a (dyn Fn(&U, &mut dyn RngCore) -> Result<T, DistributionError> + Send + Sync),
 }
 
 impl<'a, T, U> InstantDistribution<'a, T, U>
@@ -22,10 +27,21 @@ where
   U: RandomVariable,
 {
   pub fn new(
-    p: &'a (dyn Fn(&T, &U) -> Result<f64, DistributionError> + Send + Sync),
-    sample: &'a (dyn Fn(&U, &mut StdRng) -> Result<T, DistributionError> + Send + Sync),
+    p: &'a DensityFn<'a, T, U>,
+    sample: &'a (dyn Fn(&U, &mut dyn RngCore) -> Result<T, DistributionError> + Send + Sync),
   ) -> Self {
-    Self { p, sample }
+    Self {
+      p,
+      ln_p: None,
+      sample,
+    }
+  }
+
+  /// Evaluates `ln_p` directly instead of taking the log of `p`, which keeps tails from underflowing.
+  pub fn with_ln_p(mut self, ln_p: &'a DensityFn<'a, T, U>) -> Self {
+    self.ln_p = Some(ln_p);
+
+    self
   }
 }
 
@@ -51,7 +67,14 @@ where
     (self.p)(x, theta)
   }
 
-  fn sample(&self, theta: &U, rng: &mut StdRng) -> Result<T, DistributionError> {
+  fn ln_p(&self, x: &T, theta: &U) -> Result<f64, DistributionError> {
+    match self.ln_p {
+      Some(ln_p) => ln_p(x, theta),
+      None => Ok((self.p)(x, theta)?.ln()),
+    }
+  }
+
+  fn sample(&self, theta: &U, rng: &mut dyn RngCore) -> Result<T, DistributionError> {
     (self.sample)(theta, rng)
   }
 }
@@ -83,3 +106,52 @@ where
     DependentJoint::new(self, rhs)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::{Distribution, DistributionError, InstantDistribution, NormalError};
+  use rand::prelude::*;
+  use rand_distr::StandardNormal;
+  use std::f64::consts::PI;
+
+  #[test]
+  fn it_works() {
+    let check = |sigma: f64| {
+      if sigma <= 0.0 {
+        return Err(DistributionError::InvalidParameters(
+          NormalError::SigmaMustBePositive.into(),
+        ));
+      }
+      Ok(sigma)
+    };
+    let p = |x: &f64, theta: &(f64, f64)| {
+      let sigma = check(theta.1)?;
+      Ok((-(x - theta.0).powi(2) / (2.0 * sigma * sigma)).exp() / (2.0 * PI * sigma * sigma).sqrt())
+    };
+    let ln_p = |x: &f64, theta: &(f64, f64)| {
+      let sigma = check(theta.1)?;
+      Ok(-(x - theta.0).powi(2) / (2.0 * sigma * sigma) - 0.5 * (2.0 * PI * sigma * sigma).ln())
+    };
+    let sample = |theta: &(f64, f64), rng: &mut dyn RngCore| {
+      let sigma = check(theta.1)?;
+      Ok(theta.0 + sigma * rng.sample::<f64, _>(StandardNormal))
+    };
+    let distribution = InstantDistribution::new(&p, &sample);
+    let theta = (2.0, 0.5);
+
+    assert!((distribution.ln_p(&2.3, &theta).unwrap() - ln_p(&2.3, &theta).unwrap()).abs() < 1e-12);
+    // the log of `p` underflows far in the tail, the direct `ln_p` does not
+    assert_eq!(distribution.ln_p(&40.0, &theta).unwrap(), f64::NEG_INFINITY);
+    let distribution = distribution.with_ln_p(&ln_p);
+    assert!(distribution.ln_p(&40.0, &theta).unwrap().is_finite());
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let x = distribution.sample_n(10000, &theta, &mut rng).unwrap();
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    assert!((mean - 2.0).abs() < 0.02);
+
+    assert!(distribution.p(&0.0, &(0.0, -1.0)).is_err());
+    assert!(distribution.ln_p(&0.0, &(0.0, -1.0)).is_err());
+    assert!(distribution.sample(&(0.0, -1.0), &mut rng).is_err());
+  }
+}
@@ -0,0 +1,58 @@
+use crate::{Distribution, DistributionError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum MaximumLikelihoodError {
+  #[error("Samples are empty")]
+  Empty,
+}
+
+/// # MaximumLikelihood
+/// ![tex](https://latex.codecogs.com/svg.latex?\hat%7B\mathbf{\theta}%7D%3D\arg\max_%7B\mathbf{\theta}%7D\prod_ip%28x_i%7C\mathbf{\theta}%29)
+pub trait MaximumLikelihood: Distribution {
+  fn fit(samples: &[Self::T]) -> Result<Self::U, DistributionError>;
+}
+
+pub(crate) fn check_not_empty<T>(samples: &[T]) -> Result<(), DistributionError> {
+  if samples.is_empty() {
+    return Err(DistributionError::InvalidParameters(
+      MaximumLikelihoodError::Empty.into(),
+    ));
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    Distribution, Exp, ExpParams, MaximumLikelihood, Normal, NormalParams, Poisson, PoissonParams,
+  };
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let n = 20000;
+
+    let x = Normal
+      .sample_n(n, &NormalParams::new(1.5, 2.0).unwrap(), &mut rng)
+      .unwrap();
+    let normal = Normal::fit(&x).unwrap();
+    assert!((normal.mu() - 1.5).abs() < 0.05);
+    assert!((normal.sigma() - 2.0).abs() < 0.05);
+
+    let x = Exp
+      .sample_n(n, &ExpParams::new(3.0).unwrap(), &mut rng)
+      .unwrap();
+    assert!((Exp::fit(&x).unwrap().lambda() - 3.0).abs() < 0.1);
+
+    let x = Poisson
+      .sample_n(n, &PoissonParams::new(4.0).unwrap(), &mut rng)
+      .unwrap();
+    assert!((Poisson::fit(&x).unwrap().lambda() - 4.0).abs() < 0.05);
+
+    assert!(Normal::fit(&[]).is_err());
+    assert!(Exp::fit(&[]).is_err());
+    assert!(Poisson::fit(&[]).is_err());
+  }
+}
@@ -0,0 +1,62 @@
+use crate::{check_not_empty, Distribution, DistributionError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum MethodOfMomentsError {
+  #[error("Sample mean and variance are inconsistent with the distribution")]
+  InconsistentMoments,
+}
+
+/// # MethodOfMoments
+/// Solves the parameters from the sample mean and variance.
+pub trait MethodOfMoments: Distribution {
+  fn fit_moments(samples: &[Self::T]) -> Result<Self::U, DistributionError>;
+}
+
+/// Sample mean and (biased) sample variance.
+pub(crate) fn mean_var(samples: &[f64]) -> Result<(f64, f64), DistributionError> {
+  check_not_empty(samples)?;
+  let n = samples.len() as f64;
+
+  let mean = samples.iter().sum::<f64>() / n;
+  let var = samples.iter().map(|xi| (xi - mean).powi(2)).sum::<f64>() / n;
+
+  if var <= 0.0 {
+    return Err(DistributionError::InvalidParameters(
+      MethodOfMomentsError::InconsistentMoments.into(),
+    ));
+  }
+
+  Ok((mean, var))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Beta, BetaParams, Distribution, Gamma, GammaParams, MethodOfMoments};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let n = 20000;
+
+    let x = Beta
+      .sample_n(n, &BetaParams::new(2.0, 5.0).unwrap(), &mut rng)
+      .unwrap();
+    let beta = Beta::fit_moments(&x).unwrap();
+    assert!((beta.alpha() - 2.0).abs() < 0.1);
+    assert!((beta.beta() - 5.0).abs() < 0.25);
+
+    let x = Gamma
+      .sample_n(n, &GammaParams::new(3.0, 0.5).unwrap(), &mut rng)
+      .unwrap();
+    let gamma = Gamma::fit_moments(&x).unwrap();
+    assert!((gamma.shape() - 3.0).abs() < 0.15);
+    assert!((gamma.scale() - 0.5).abs() < 0.03);
+
+    // variance 1 is too large for any Beta with mean 0.5
+    assert!(Beta::fit_moments(&[-0.5, 1.5]).is_err());
+    assert!(Beta::fit_moments(&[0.5, 0.5]).is_err());
+    assert!(Gamma::fit_moments(&[-1.0, -2.0]).is_err());
+    assert!(Gamma::fit_moments(&[]).is_err());
+  }
+}
@@ -1,19 +1,46 @@
+pub mod censored;
+pub mod condition_differentiable;
+pub mod conditioned;
 pub mod converted;
+pub mod cumulative;
 pub mod dependent_joint;
+pub mod discrete_distribution;
 pub mod independent_array_joint;
 pub mod independent_joint;
 pub mod instant;
+pub mod maximum_likelihood;
+pub mod method_of_moments;
+pub mod model_comparison;
+pub mod quantile;
+pub(crate) mod special_functions;
+pub mod support;
+pub mod tempered;
+pub mod value_differentiable;
 pub mod variational_inference;
 
+pub use censored::*;
+pub use condition_differentiable::*;
+pub use conditioned::*;
 pub use converted::*;
+pub use cumulative::*;
 pub use dependent_joint::*;
+pub use discrete_distribution::*;
 pub use independent_array_joint::*;
 pub use independent_joint::*;
 pub use instant::*;
+pub use maximum_likelihood::*;
+pub use method_of_moments::*;
+pub use model_comparison::*;
 use opensrdk_kernel_method::KernelError;
 use opensrdk_linear_algebra::MatrixError;
+pub use quantile::*;
 use rand::prelude::*;
+use rayon::prelude::*;
+pub(crate) use special_functions::*;
 use std::{error::Error, fmt::Debug};
+pub use support::*;
+pub use tempered::*;
+pub use value_differentiable::*;
 pub use variational_inference::*;
 
 pub trait RandomVariable: Clone + Debug + PartialEq + Send + Sync {}
@@ -49,6 +76,11 @@ impl From<Box<dyn Error + Send + Sync>> for DistributionError {
   }
 }
 
+/// `StdRng` seeded from the operating system, for callers that do not need a reproducible stream.
+pub fn default_rng() -> StdRng {
+  StdRng::from_entropy()
+}
+
 /// # Distribution
 /// ![tex](https://latex.codecogs.com/svg.latex?p%28x%7C\mathbf{\theta}%29)
 pub trait Distribution: Clone + Debug + Send + Sync {
@@ -59,5 +91,101 @@ pub trait Distribution: Clone + Debug + Send + Sync {
   fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
     Ok(self.p(x, theta)?.ln())
   }
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError>;
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError>;
+
+  /// Draws `n` independent samples.
+  fn sample_n(
+    &self,
+    n: usize,
+    theta: &Self::U,
+    rng: &mut dyn RngCore,
+  ) -> Result<Vec<Self::T>, DistributionError> {
+    (0..n).map(|_| self.sample(theta, rng)).collect()
+  }
+
+  /// Draws `n` independent samples in parallel.
+  /// Each draw uses its own `StdRng` seeded from `rng`, so the result does not depend on thread scheduling.
+  fn par_sample_n(
+    &self,
+    n: usize,
+    theta: &Self::U,
+    rng: &mut dyn RngCore,
+  ) -> Result<Vec<Self::T>, DistributionError> {
+    let seeds = (0..n).map(|_| rng.gen()).collect::<Vec<_>>();
+
+    seeds
+      .into_par_iter()
+      .map(|seed| self.sample(theta, &mut StdRng::from_seed(seed)))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    BinominalError, BinominalParams, Distribution, DistributionError, GeometricError,
+    GeometricParams, Normal, NormalParams, PoissonError, PoissonParams,
+  };
+  use rand::prelude::*;
+
+  #[test]
+  fn invalid_parameters() {
+    fn source<T>(result: Result<T, DistributionError>) -> Box<dyn std::error::Error + Send + Sync> {
+      match result {
+        Err(DistributionError::InvalidParameters(e)) => e,
+        _ => panic!("expected InvalidParameters"),
+      }
+    }
+
+    assert!(matches!(
+      source(PoissonParams::new(-1.0)).downcast_ref(),
+      Some(PoissonError::LambdaMustBePositive)
+    ));
+    assert!(matches!(
+      source(GeometricParams::new(1.5)).downcast_ref(),
+      Some(GeometricError::PMustBeProbability)
+    ));
+    assert!(matches!(
+      source(BinominalParams::new(10, -0.1)).downcast_ref(),
+      Some(BinominalError::PMustBeProbability)
+    ));
+    assert!(matches!(
+      source(BinominalParams::from_failure_prob(10, 1.1)).downcast_ref(),
+      Some(BinominalError::QMustBeProbability)
+    ));
+  }
+
+  #[test]
+  fn sample_n() {
+    let params = NormalParams::new(0.0, 1.0).unwrap();
+
+    let x = Normal
+      .sample_n(10, &params, &mut StdRng::from_seed([1; 32]))
+      .unwrap();
+    let y = Normal
+      .sample_n(10, &params, &mut StdRng::from_seed([1; 32]))
+      .unwrap();
+
+    assert_eq!(x.len(), 10);
+    assert_eq!(x, y);
+  }
+
+  #[test]
+  fn par_sample_n() {
+    let params = NormalParams::new(0.0, 1.0).unwrap();
+
+    let x = Normal
+      .par_sample_n(1000, &params, &mut StdRng::from_seed([1; 32]))
+      .unwrap();
+    let y = Normal
+      .par_sample_n(1000, &params, &mut StdRng::from_seed([1; 32]))
+      .unwrap();
+    let z = Normal
+      .par_sample_n(1000, &params, &mut StdRng::from_seed([2; 32]))
+      .unwrap();
+
+    assert_eq!(x.len(), 1000);
+    assert_eq!(x, y);
+    assert_ne!(x, z);
+  }
 }
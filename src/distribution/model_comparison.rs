@@ -0,0 +1,61 @@
+/// Bayes factor ![tex](https://latex.codecogs.com/svg.latex?p%28\mathcal%7BD%7D%7CM_a%29/p%28\mathcal%7BD%7D%7CM_b%29) from log marginal likelihoods.
+pub fn bayes_factor(log_ml_a: f64, log_ml_b: f64) -> f64 {
+  (log_ml_a - log_ml_b).exp()
+}
+
+/// Ranks `(name, log marginal likelihood)` pairs and returns `(name, posterior model probability)` in descending order, assuming equal prior model probabilities.
+pub fn compare_models<'a>(models: &[(&'a str, f64)]) -> Vec<(&'a str, f64)> {
+  let max = models
+    .iter()
+    .map(|&(_, log_ml)| log_ml)
+    .fold(f64::NEG_INFINITY, f64::max);
+  // softmax shifted by the maximum to avoid overflow
+  let sum = models
+    .iter()
+    .map(|&(_, log_ml)| (log_ml - max).exp())
+    .sum::<f64>();
+
+  let mut ranked = models
+    .iter()
+    .map(|&(name, log_ml)| (name, (log_ml - max).exp() / sum))
+    .collect::<Vec<_>>();
+  ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+  ranked
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::nonparametric::{ExactGP, GaussianProcess, GaussianProcessParams};
+  use crate::{bayes_factor, compare_models, Distribution};
+  use opensrdk_kernel_method::RBF;
+
+  #[test]
+  fn it_works() {
+    let x = (0..20).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+
+    let log_ml = |theta: Vec<f64>| {
+      ExactGP::new(RBF)
+        .ln_p(&y, &GaussianProcessParams::new(x.clone(), theta))
+        .unwrap()
+    };
+    let smooth = log_ml(vec![1.0, 1.0]);
+    let rough = log_ml(vec![1.0, 0.05]);
+
+    let ranked = compare_models(&[("rough", rough), ("smooth", smooth)]);
+
+    assert_eq!(ranked[0].0, "smooth");
+    assert!((ranked.iter().map(|&(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!(1.0 < bayes_factor(smooth, rough));
+    assert!(
+      (ranked[0].1 / ranked[1].1 - bayes_factor(smooth, rough)).abs()
+        < 1e-6 * bayes_factor(smooth, rough)
+    );
+
+    // probabilities stay finite far beyond the range of exp
+    let ranked = compare_models(&[("a", -1e4), ("b", -1e4 + 2.0f64.ln())]);
+    assert_eq!(ranked[0].0, "b");
+    assert!((ranked[0].1 - 2.0 / 3.0).abs() < 1e-12);
+  }
+}
@@ -0,0 +1,45 @@
+use crate::{Distribution, DistributionError};
+
+/// # QuantileDistribution
+/// ![tex](https://latex.codecogs.com/svg.latex?F%5E%7B-1%7D%28p%7C\mathbf{\theta}%29)
+pub trait QuantileDistribution: Distribution {
+  fn quantile(&self, p: f64, theta: &Self::U) -> Result<Self::T, DistributionError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum QuantileError {
+  #[error("'p' must be probability.")]
+  PMustBeProbability,
+}
+
+pub(crate) fn check_probability(p: f64) -> Result<(), DistributionError> {
+  if !(0.0..=1.0).contains(&p) {
+    return Err(DistributionError::InvalidParameters(
+      QuantileError::PMustBeProbability.into(),
+    ));
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{CumulativeDistribution, Exp, ExpParams, Normal, NormalParams, QuantileDistribution};
+
+  #[test]
+  fn it_works() {
+    let exp = ExpParams::new(2.0).unwrap();
+    let normal = NormalParams::new(1.0, 2.0).unwrap();
+
+    for &p in [0.01, 0.3, 0.5, 0.9].iter() {
+      let x = Exp.quantile(p, &exp).unwrap();
+      assert!((Exp.cdf(&x, &exp).unwrap() - p).abs() < 1e-12);
+
+      let x = Normal.quantile(p, &normal).unwrap();
+      assert!((Normal.cdf(&x, &normal).unwrap() - p).abs() < 1e-10);
+    }
+
+    assert!(Normal.quantile(1.5, &normal).is_err());
+    assert!(Exp.quantile(-0.1, &exp).is_err());
+  }
+}
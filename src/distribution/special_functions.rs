@@ -0,0 +1,11 @@
+use special::Gamma;
+
+/// ![tex](https://latex.codecogs.com/svg.latex?%5Csigma%28x%29%3D1/%281+e%5E%7B-x%7D%29)
+pub(crate) fn sigmoid(x: f64) -> f64 {
+  1.0 / (1.0 + (-x).exp())
+}
+
+/// ![tex](https://latex.codecogs.com/svg.latex?%5Cln%5CGamma%28x%29) without its sign.
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+  Gamma::ln_gamma(x).0
+}
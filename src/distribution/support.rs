@@ -0,0 +1,38 @@
+use crate::Distribution;
+
+/// # Support
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7Bsupp%7D%28p%28\cdot%7C\mathbf{\theta}%29%29)
+pub trait Support: Distribution {
+  fn in_support(&self, x: &Self::T, theta: &Self::U) -> bool;
+  /// Lower and upper bounds of the support. `None` means unbounded.
+  fn support_bounds(&self, theta: &Self::U) -> (Option<f64>, Option<f64>);
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    Beta, BetaParams, Exp, ExpParams, Gamma, GammaParams, Poisson, PoissonParams, Support,
+  };
+
+  #[test]
+  fn it_works() {
+    let exp = ExpParams::new(1.0).unwrap();
+    assert_eq!(Exp.support_bounds(&exp), (Some(0.0), None));
+    assert!(Exp.in_support(&0.0, &exp));
+    assert!(!Exp.in_support(&-1.0, &exp));
+
+    let gamma = GammaParams::new(2.0, 1.0).unwrap();
+    assert_eq!(Gamma.support_bounds(&gamma), (Some(0.0), None));
+    assert!(Gamma.in_support(&3.0, &gamma));
+    assert!(!Gamma.in_support(&-0.5, &gamma));
+
+    let beta = BetaParams::new(2.0, 2.0).unwrap();
+    assert_eq!(Beta.support_bounds(&beta), (Some(0.0), Some(1.0)));
+    assert!(Beta.in_support(&0.5, &beta));
+    assert!(!Beta.in_support(&1.5, &beta));
+
+    let poisson = PoissonParams::new(1.0).unwrap();
+    assert_eq!(Poisson.support_bounds(&poisson), (Some(0.0), None));
+    assert!(Poisson.in_support(&3, &poisson));
+  }
+}
@@ -0,0 +1,140 @@
+use crate::DistributionError;
+use crate::{DependentJoint, Distribution, IndependentJoint, RandomVariable};
+use rand::prelude::RngCore;
+use std::ops::{BitAnd, Mul};
+
+/// # Tempered
+/// ![tex](https://latex.codecogs.com/svg.latex?p%28x%7C\mathbf{\theta}%29%5E%7B\beta%7D)
+///
+/// Unnormalized for `0 < β < 1`, which is enough for annealing and SMC where only density ratios matter.
+#[derive(Clone, Debug)]
+pub struct Tempered<D>
+where
+  D: Distribution,
+{
+  distribution: D,
+  beta: f64,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TemperedError {
+  #[error("'β' must be non-negative")]
+  BetaMustBeNonNegative,
+  #[error("Sampling is only supported for 'β' = 1")]
+  SampleRequiresUntempered,
+}
+
+impl<D> Tempered<D>
+where
+  D: Distribution,
+{
+  pub fn new(distribution: D, beta: f64) -> Result<Self, DistributionError> {
+    if beta < 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        TemperedError::BetaMustBeNonNegative.into(),
+      ));
+    }
+
+    Ok(Self { distribution, beta })
+  }
+
+  pub fn distribution(&self) -> &D {
+    &self.distribution
+  }
+
+  pub fn beta(&self) -> f64 {
+    self.beta
+  }
+}
+
+impl<D> Distribution for Tempered<D>
+where
+  D: Distribution,
+{
+  type T = D::T;
+  type U = D::U;
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.distribution.p(x, theta)?.powf(self.beta))
+  }
+
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    if self.beta == 0.0 {
+      // keeps the density flat even where the original one vanishes
+      return Ok(0.0);
+    }
+
+    Ok(self.beta * self.distribution.ln_p(x, theta)?)
+  }
+
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    if self.beta != 1.0 {
+      return Err(DistributionError::InvalidParameters(
+        TemperedError::SampleRequiresUntempered.into(),
+      ));
+    }
+
+    self.distribution.sample(theta, rng)
+  }
+}
+
+impl<D, Rhs, TRhs> Mul<Rhs> for Tempered<D>
+where
+  D: Distribution,
+  Rhs: Distribution<T = TRhs, U = D::U>,
+  TRhs: RandomVariable,
+{
+  type Output = IndependentJoint<Self, Rhs, D::T, TRhs, D::U>;
+
+  fn mul(self, rhs: Rhs) -> Self::Output {
+    IndependentJoint::new(self, rhs)
+  }
+}
+
+impl<D, Rhs, URhs> BitAnd<Rhs> for Tempered<D>
+where
+  D: Distribution,
+  Rhs: Distribution<T = D::U, U = URhs>,
+  URhs: RandomVariable,
+{
+  type Output = DependentJoint<Self, Rhs, D::T, D::U, URhs>;
+
+  fn bitand(self, rhs: Rhs) -> Self::Output {
+    DependentJoint::new(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Distribution, Normal, NormalParams, Tempered};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = NormalParams::new(1.0, 2.0).unwrap();
+
+    let flat = Tempered::new(Normal, 0.0).unwrap();
+    for &x in [-100.0, 0.0, 1.0, 3.0].iter() {
+      assert_eq!(flat.p(&x, &params).unwrap(), 1.0);
+      assert_eq!(flat.ln_p(&x, &params).unwrap(), 0.0);
+    }
+
+    let original = Tempered::new(Normal, 1.0).unwrap();
+    for &x in [-3.0, 0.0, 1.0, 3.0].iter() {
+      assert!((original.p(&x, &params).unwrap() - Normal.p(&x, &params).unwrap()).abs() < 1e-12);
+      assert!(
+        (original.ln_p(&x, &params).unwrap() - Normal.ln_p(&x, &params).unwrap()).abs() < 1e-12
+      );
+    }
+
+    let half = Tempered::new(Normal, 0.5).unwrap();
+    assert!(
+      (half.ln_p(&0.0, &params).unwrap() - 0.5 * Normal.ln_p(&0.0, &params).unwrap()).abs() < 1e-12
+    );
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    assert!(original.sample(&params, &mut rng).is_ok());
+    assert!(half.sample(&params, &mut rng).is_err());
+    assert!(Tempered::new(Normal, -1.0).is_err());
+  }
+}
@@ -0,0 +1,7 @@
+use crate::{Distribution, DistributionError};
+
+/// # ValueDifferentiableDistribution
+/// ![tex](https://latex.codecogs.com/svg.latex?\frac%7B\partial%20\ln%20p%28x%7C\mathbf{\theta}%29%7D%7B\partial%20x%7D)
+pub trait ValueDifferentiableDistribution: Distribution {
+  fn ln_diff_value(&self, x: &Self::T, theta: &Self::U) -> Result<Vec<f64>, DistributionError>;
+}
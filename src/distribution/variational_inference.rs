@@ -16,6 +16,7 @@ where
   /// - self: q(x|θ)
   /// - likelihood: p(y|x)
   /// - prior: p(x)
+  /// - rng: draws the samples of p(x), e.g. `default_rng()`
   /// - return: θ
   fn variational_inference<U, DL, DP>(
     &self,
@@ -27,6 +28,7 @@ where
     sample_batch: usize,
     sample_total: usize,
     max_iter: usize,
+    rng: &mut dyn RngCore,
   ) -> Result<Vec<f64>, Box<dyn Error>>
   where
     U: RandomVariable,
@@ -34,11 +36,7 @@ where
     DP: Distribution<T = T, U = ()>,
   {
     let mut params = vec![0.0; theta_len];
-    let mut rng = SeedableRng::from_rng(thread_rng())?;
-    let x = (0..sample_total)
-      .into_iter()
-      .map(|_| prior.sample(&(), &mut rng))
-      .collect::<Result<Vec<_>, _>>()?;
+    let x = prior.sample_n(sample_total, &(), rng)?;
 
     SgdAdam::default().with_max_iter(max_iter).minimize(
       &mut params,
@@ -75,3 +73,42 @@ where
   T: RandomVariable,
 {
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    default_rng, ConditionableDistribution, Normal, NormalParams, VariationalInferenceDistribution,
+  };
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    // q(x|θ) = N(θ_0, 1), y | x ~ N(x, 1), x ~ N(0, 1)
+    let q = Normal.condition(&|theta: &Vec<f64>| NormalParams::new(theta[0], 1.0));
+    let likelihood = Normal.condition(&|x: &f64| NormalParams::new(*x, 1.0));
+    let prior = Normal.condition(&|_: &()| NormalParams::new(0.0, 1.0));
+
+    let run = |rng: &mut dyn RngCore| {
+      q.variational_inference(
+        1,
+        |x, theta| vec![x - theta[0]],
+        &1.0,
+        likelihood.clone(),
+        prior.clone(),
+        5,
+        20,
+        10,
+        rng,
+      )
+      .unwrap()
+    };
+
+    let x = run(&mut StdRng::from_seed([1; 32]));
+    let y = run(&mut StdRng::from_seed([1; 32]));
+    let z = run(&mut StdRng::from_seed([2; 32]));
+
+    assert_eq!(x, y);
+    assert_ne!(x, z);
+    assert_eq!(run(&mut default_rng()).len(), 1);
+  }
+}
@@ -10,10 +10,12 @@ extern crate rayon;
 extern crate thiserror;
 
 pub mod continuous;
+pub mod copula;
 pub mod discrete;
 pub mod distribution;
 pub mod mcmc;
 pub mod nonparametric;
+pub mod point_process;
 
 pub use continuous::*;
 pub use discrete::*;
@@ -0,0 +1,52 @@
+use rand::prelude::*;
+use rayon::prelude::*;
+
+/// Runs `n_chains` chains in parallel and collects `n_samples` values from each.
+/// Each chain is built by `sampler_factory` from its own seed drawn from `rng`, so the result does not depend on thread scheduling.
+pub fn run_chains<S>(
+  sampler_factory: impl Fn(u64) -> S + Send + Sync,
+  n_chains: usize,
+  n_samples: usize,
+  rng: &mut dyn RngCore,
+) -> Vec<Vec<f64>>
+where
+  S: Iterator<Item = f64>,
+{
+  let seeds = (0..n_chains).map(|_| rng.gen()).collect::<Vec<u64>>();
+
+  seeds
+    .into_par_iter()
+    .map(|seed| sampler_factory(seed).take(n_samples).collect())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{mcmc::run_chains, mcmc::SliceSampler, Normal, NormalParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = NormalParams::new(0.0, 1.0).unwrap();
+    let factory = |seed: u64| {
+      let sampler = SliceSampler::new(&Normal, &params);
+      let mut rng = StdRng::seed_from_u64(seed);
+      let mut x = 0.0;
+
+      std::iter::from_fn(move || {
+        x = sampler.sample(x, &mut rng).unwrap();
+        Some(x)
+      })
+    };
+
+    let x = run_chains(factory, 4, 100, &mut StdRng::from_seed([1; 32]));
+    let y = run_chains(factory, 4, 100, &mut StdRng::from_seed([1; 32]));
+    let z = run_chains(factory, 4, 100, &mut StdRng::from_seed([2; 32]));
+
+    assert_eq!(x.len(), 4);
+    assert!(x.iter().all(|chain| chain.len() == 100));
+    assert_eq!(x, y);
+    assert_ne!(x, z);
+    assert_ne!(x[0], x[1]);
+  }
+}
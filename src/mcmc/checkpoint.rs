@@ -0,0 +1,90 @@
+use crate::DistributionError;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CheckpointError {
+  #[error("The chain has not been started")]
+  NotStarted,
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+}
+
+/// Everything needed to continue a chain exactly where it stopped.
+/// The random stream is kept as the ChaCha seed and the position in it, which is how `StdRng` generates.
+/// With the `serde` feature it can be serialized, e.g. to write it to a file.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SamplerState {
+  position: Vec<f64>,
+  seed: [u8; 32],
+  word_pos: u128,
+  step: usize,
+  accepted: usize,
+}
+
+impl SamplerState {
+  pub fn new(
+    position: Vec<f64>,
+    seed: [u8; 32],
+    word_pos: u128,
+    step: usize,
+    accepted: usize,
+  ) -> Self {
+    Self {
+      position,
+      seed,
+      word_pos,
+      step,
+      accepted,
+    }
+  }
+
+  pub fn position(&self) -> &[f64] {
+    &self.position
+  }
+
+  pub fn seed(&self) -> [u8; 32] {
+    self.seed
+  }
+
+  pub fn word_pos(&self) -> u128 {
+    self.word_pos
+  }
+
+  /// Number of transitions made so far.
+  pub fn step(&self) -> usize {
+    self.step
+  }
+
+  /// Number of accepted proposals so far.
+  pub fn accepted(&self) -> usize {
+    self.accepted
+  }
+
+  pub fn acceptance_rate(&self) -> f64 {
+    if self.step == 0 {
+      return 0.0;
+    }
+
+    self.accepted as f64 / self.step as f64
+  }
+}
+
+/// A sampler owning its chain, whose state can be saved and loaded to resume a long run.
+pub trait CheckpointableSampler {
+  fn save_state(&self) -> Result<SamplerState, DistributionError>;
+  fn load_state(&mut self, state: SamplerState) -> Result<(), DistributionError>;
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+  use crate::mcmc::SamplerState;
+
+  #[test]
+  fn json() {
+    // word positions past u64 must survive the round trip
+    let state = SamplerState::new(vec![0.5, -1.5], [7; 32], u128::from(u64::MAX) + 3, 200, 57);
+
+    let json = serde_json::to_string(&state).unwrap();
+    assert_eq!(serde_json::from_str::<SamplerState>(&json).unwrap(), state);
+  }
+}
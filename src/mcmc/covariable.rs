@@ -0,0 +1,81 @@
+use crate::DistributionError;
+use opensrdk_linear_algebra::*;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CovariableError {
+  #[error("At least 2 samples are required")]
+  TooFewSamples,
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+}
+
+pub trait Covariable {
+  /// Unbiased sample covariance of the samples, which must all have the same length.
+  fn covariance(self) -> Result<Matrix, DistributionError>;
+}
+
+impl<I> Covariable for I
+where
+  I: IntoIterator<Item = Vec<f64>>,
+{
+  fn covariance(self) -> Result<Matrix, DistributionError> {
+    let samples = self.into_iter().collect::<Vec<_>>();
+    let n = samples.len();
+
+    if n < 2 {
+      return Err(DistributionError::InvalidParameters(
+        CovariableError::TooFewSamples.into(),
+      ));
+    }
+    let dim = samples[0].len();
+    if samples.iter().any(|x| x.len() != dim) {
+      return Err(DistributionError::InvalidParameters(
+        CovariableError::DimensionMismatch.into(),
+      ));
+    }
+
+    let mean = samples.iter().fold(vec![0.0; dim], |mut mean, x| {
+      mean.iter_mut().zip(x.iter()).for_each(|(mi, xi)| *mi += xi);
+      mean
+    });
+    let mean = mean.into_iter().map(|mi| mi / n as f64).collect::<Vec<_>>();
+
+    // dim x n centered samples
+    let centered = samples
+      .into_iter()
+      .flat_map(|x| x.into_iter().zip(mean.iter()).map(|(xi, mi)| xi - mi))
+      .collect::<Vec<_>>();
+    let centered = Matrix::from(dim, centered);
+
+    Ok((1.0 / (n - 1) as f64) * (&centered * centered.t()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{mcmc::Covariable, Distribution, MultivariateNormal, MultivariateNormalParams};
+  use opensrdk_linear_algebra::*;
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let lsigma = Matrix::from(3, vec![1.0, 0.5, -0.3, 0.0, 0.8, 0.2, 0.0, 0.0, 0.6]);
+    let sigma = &lsigma * lsigma.t();
+    let params = MultivariateNormalParams::new(vec![1.0, 2.0, 3.0], lsigma).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let x = MultivariateNormal
+      .sample_n(20000, &params, &mut rng)
+      .unwrap();
+    let cov = x.covariance().unwrap();
+
+    for i in 0..3 {
+      for j in 0..3 {
+        assert!((cov[(i, j)] - sigma[(i, j)]).abs() < 0.05);
+      }
+    }
+
+    assert!(vec![vec![1.0, 2.0]].covariance().is_err());
+    assert!(vec![vec![1.0, 2.0], vec![3.0]].covariance().is_err());
+  }
+}
@@ -0,0 +1,243 @@
+use crate::DistributionError;
+
+#[derive(thiserror::Error, Debug)]
+pub enum DiagnosticsError {
+  #[error("Chains must have at least 4 draws each")]
+  TooFewDraws,
+  #[error("Chains must have the same length and dimension")]
+  ShapeMismatch,
+}
+
+/// Effective sample size of each dimension over multiple chains `chains[chain][draw][dimension]`.
+///
+/// Combines within- and between-chain autocorrelations and truncates them by Geyer's initial monotone sequence, as Stan does.
+pub fn ess_multivariate(chains: &[Vec<Vec<f64>>]) -> Result<Vec<f64>, DistributionError> {
+  let (_, _, dim) = check_chains(chains)?;
+
+  Ok(
+    (0..dim)
+      .map(|d| {
+        let chains = chains
+          .iter()
+          .map(|chain| chain.iter().map(|xt| xt[d]).collect::<Vec<_>>())
+          .collect::<Vec<_>>();
+
+        ess(&chains)
+      })
+      .collect(),
+  )
+}
+
+/// Split ![tex](https://latex.codecogs.com/svg.latex?\hat%7BR%7D) of each dimension over multiple chains `chains[chain][draw][dimension]`.
+///
+/// Each chain is split in half so that a drift within a chain also shows up, and values close to 1 indicate the chains have mixed.
+pub fn rhat_multivariate(chains: &[Vec<Vec<f64>>]) -> Result<Vec<f64>, DistributionError> {
+  let (_, n, dim) = check_chains(chains)?;
+
+  Ok(
+    (0..dim)
+      .map(|d| {
+        let halves = chains
+          .iter()
+          .flat_map(|chain| {
+            let x = chain.iter().map(|xt| xt[d]).collect::<Vec<_>>();
+            vec![x[..n / 2].to_vec(), x[n - n / 2..].to_vec()]
+          })
+          .collect::<Vec<_>>();
+
+        rhat(&halves)
+      })
+      .collect(),
+  )
+}
+
+/// `(chains, draws, dimensions)` of `chains[chain][draw][dimension]`, which must all have the same number of draws of the same dimension.
+fn check_chains(chains: &[Vec<Vec<f64>>]) -> Result<(usize, usize, usize), DistributionError> {
+  let n = chains.first().map_or(0, |chain| chain.len());
+  if chains.is_empty() || n < 4 {
+    return Err(DistributionError::InvalidParameters(
+      DiagnosticsError::TooFewDraws.into(),
+    ));
+  }
+  let dim = chains[0][0].len();
+  if chains
+    .iter()
+    .any(|chain| chain.len() != n || chain.iter().any(|xt| xt.len() != dim))
+  {
+    return Err(DistributionError::InvalidParameters(
+      DiagnosticsError::ShapeMismatch.into(),
+    ));
+  }
+
+  Ok((chains.len(), n, dim))
+}
+
+fn rhat(chains: &[Vec<f64>]) -> f64 {
+  let m = chains.len() as f64;
+  let n = chains[0].len() as f64;
+
+  let means = chains
+    .iter()
+    .map(|chain| chain.iter().sum::<f64>() / n)
+    .collect::<Vec<_>>();
+  let mean = means.iter().sum::<f64>() / m;
+  let between = means.iter().map(|mi| (mi - mean).powi(2)).sum::<f64>() / (m - 1.0);
+  let within = chains
+    .iter()
+    .zip(means.iter())
+    .map(|(chain, mi)| chain.iter().map(|x| (x - mi).powi(2)).sum::<f64>() / (n - 1.0))
+    .sum::<f64>()
+    / m;
+
+  ((n - 1.0) / n + between / within).sqrt()
+}
+
+/// Biased autocovariance at every lag.
+fn autocovariance(x: &[f64]) -> Vec<f64> {
+  let n = x.len();
+  let mean = x.iter().sum::<f64>() / n as f64;
+
+  (0..n)
+    .map(|t| {
+      (0..n - t)
+        .map(|i| (x[i] - mean) * (x[i + t] - mean))
+        .sum::<f64>()
+        / n as f64
+    })
+    .collect()
+}
+
+fn ess(chains: &[Vec<f64>]) -> f64 {
+  let m = chains.len();
+  let n = chains[0].len();
+
+  let acov = chains
+    .iter()
+    .map(|chain| autocovariance(chain))
+    .collect::<Vec<_>>();
+  let acov_mean = |t: usize| acov.iter().map(|a| a[t]).sum::<f64>() / m as f64;
+
+  let chain_means = chains
+    .iter()
+    .map(|chain| chain.iter().sum::<f64>() / n as f64)
+    .collect::<Vec<_>>();
+  let mean_var = acov_mean(0) * n as f64 / (n - 1) as f64;
+  let mut var_plus = mean_var * (n - 1) as f64 / n as f64;
+  if 1 < m {
+    let mean = chain_means.iter().sum::<f64>() / m as f64;
+    var_plus += chain_means
+      .iter()
+      .map(|mi| (mi - mean).powi(2))
+      .sum::<f64>()
+      / (m - 1) as f64;
+  }
+
+  let rho = |t: usize| 1.0 - (mean_var - acov_mean(t)) / var_plus;
+
+  let mut rho_hat = vec![0.0; n];
+  rho_hat[0] = 1.0;
+  let mut rho_even = 1.0;
+  let mut rho_odd = rho(1);
+  rho_hat[1] = rho_odd;
+
+  // Geyer's initial positive sequence
+  let mut t = 1;
+  while t + 5 < n && 0.0 < rho_even + rho_odd {
+    rho_even = rho(t + 1);
+    rho_odd = rho(t + 2);
+    if 0.0 <= rho_even + rho_odd {
+      rho_hat[t + 1] = rho_even;
+      rho_hat[t + 2] = rho_odd;
+    }
+    t += 2;
+  }
+  let max_t = t;
+  if 0.0 < rho_even {
+    rho_hat[max_t + 1] = rho_even;
+  }
+
+  // initial monotone sequence
+  let mut t = 1;
+  while t + 4 <= max_t {
+    if rho_hat[t - 1] + rho_hat[t] < rho_hat[t + 1] + rho_hat[t + 2] {
+      let pair = (rho_hat[t - 1] + rho_hat[t]) / 2.0;
+      rho_hat[t + 1] = pair;
+      rho_hat[t + 2] = pair;
+    }
+    t += 2;
+  }
+
+  let draws = (m * n) as f64;
+  let tau = -1.0 + 2.0 * rho_hat[..max_t].iter().sum::<f64>() + rho_hat[max_t + 1];
+
+  draws / tau.max(1.0 / draws.log10())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::mcmc::{ess_multivariate, rhat_multivariate};
+  use rand::prelude::*;
+  use rand_distr::StandardNormal;
+
+  #[test]
+  fn it_works() {
+    // by hand: both chains have mean 4.5, so var+ = 21/4 and the within-chain variance is 6.
+    // ρ1 = 27/56 and ρ2 = 11/84, while ρ2 + ρ3 < 0 ends the sequence, so τ = 1 + 2 ρ1 + ρ2 = 176/84
+    let chains = vec![
+      (1..=8).map(|i| vec![i as f64]).collect::<Vec<_>>(),
+      (1..=8).rev().map(|i| vec![i as f64]).collect::<Vec<_>>(),
+    ];
+    let ess = ess_multivariate(&chains).unwrap();
+    assert!((ess[0] - 16.0 * 84.0 / 176.0).abs() < 1e-12);
+
+    // AR(1) chains with φ = 0.9 have τ = (1 + φ) / (1 - φ) = 19 in the first dimension,
+    // and the second dimension is independent noise
+    let mut rng = StdRng::from_seed([1; 32]);
+    let (m, n) = (4, 5000);
+    let chains = (0..m)
+      .map(|_| {
+        let mut x = 0.0;
+        (0..n)
+          .map(|_| {
+            x = 0.9 * x + (1.0f64 - 0.81).sqrt() * rng.sample::<f64, _>(StandardNormal);
+            vec![x, rng.sample(StandardNormal)]
+          })
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+    let ess = ess_multivariate(&chains).unwrap();
+
+    let draws = (m * n) as f64;
+    assert!((ess[0] / (draws / 19.0) - 1.0).abs() < 0.25);
+    assert!((ess[1] / draws - 1.0).abs() < 0.1);
+
+    assert!(ess_multivariate(&[]).is_err());
+    assert!(ess_multivariate(&[vec![vec![0.0]; 10], vec![vec![0.0]; 9]]).is_err());
+  }
+
+  #[test]
+  fn rhat() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let mut chain = |offset: f64| {
+      (0..1000)
+        .map(|_| vec![offset + rng.sample::<f64, _>(StandardNormal)])
+        .collect::<Vec<_>>()
+    };
+
+    let mixed = vec![chain(0.0), chain(0.0), chain(0.0), chain(0.0)];
+    let rhat = rhat_multivariate(&mixed).unwrap();
+    assert!((rhat[0] - 1.0).abs() < 0.01);
+
+    // one chain stuck in another mode
+    let stuck = vec![chain(0.0), chain(0.0), chain(0.0), chain(3.0)];
+    assert!(rhat_multivariate(&stuck).unwrap()[0] > 1.3);
+
+    // a trend within a single chain is caught by the split
+    let trend = vec![(0..1000)
+      .map(|t| vec![t as f64 / 100.0])
+      .collect::<Vec<_>>()];
+    assert!(rhat_multivariate(&trend).unwrap()[0] > 1.5);
+
+    assert!(rhat_multivariate(&[vec![vec![0.0]; 3]]).is_err());
+  }
+}
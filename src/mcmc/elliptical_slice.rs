@@ -54,32 +54,72 @@ where
     }
   }
 
-  pub fn sample(&self, rng: &mut StdRng) -> Result<B, Box<dyn Error>> {
+  /// One transition from the current state `b`.
+  pub fn sample(&self, b: B, rng: &mut dyn RngCore) -> Result<B, Box<dyn Error>> {
     let nu = self.prior.sample(&(), rng)?;
 
-    let mut b = self.prior.sample(&(), rng)?;
-
-    let rho = self.likelihood.p(self.value, &b)? * rng.gen_range(0.0..1.0);
+    // in log space so that likelihoods underflowing to 0 still move the slice
+    let ln_rho = self.likelihood.ln_p(self.value, &b)? + rng.gen_range(0.0f64..1.0).ln();
     let mut theta = rng.gen_range(0.0..2.0 * PI);
 
     let mut start = theta - 2.0 * PI;
     let mut end = theta;
 
     loop {
-      b = b.ellipse(theta, &nu);
+      // every proposal lies on the ellipse through the current state
+      let proposal = b.clone().ellipse(theta, &nu);
 
-      if rho < self.likelihood.p(self.value, &b)? {
-        break;
+      if ln_rho < self.likelihood.ln_p(self.value, &proposal)? {
+        return Ok(proposal);
       }
 
-      if 0.0 < theta {
-        end = 0.0;
+      if theta < 0.0 {
+        start = theta;
       } else {
-        start = 0.0;
+        end = theta;
       }
       theta = rng.gen_range(start..end);
     }
+  }
+
+  /// `n` successive states of the chain started at `initial`, excluding `initial` itself.
+  pub fn sample_chain(
+    &self,
+    initial: B,
+    n: usize,
+    rng: &mut dyn RngCore,
+  ) -> Result<Vec<B>, Box<dyn Error>> {
+    let mut b = initial;
+
+    (0..n)
+      .map(|_| {
+        b = self.sample(b.clone(), rng)?;
+        Ok(b.clone())
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{mcmc::EllipticalSliceSampler, ConditionableDistribution, Normal, NormalParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    // a | b ~ N(b, 0.5^2), b ~ N(0, 1), so b | a ~ N(4a / 5, 1 / 5)
+    let likelihood = Normal.condition(&|b: &f64| NormalParams::new(*b, 0.5));
+    let prior = Normal.condition(&|_: &()| NormalParams::new(0.0, 1.0));
+    let a = 1.0;
+    let sampler = EllipticalSliceSampler::new(&a, &likelihood, &prior);
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let chain = sampler.sample_chain(0.0, 20000, &mut rng).unwrap();
+    let n = chain.len() as f64;
+    let mean = chain.iter().sum::<f64>() / n;
+    let var = chain.iter().map(|b| (b - mean).powi(2)).sum::<f64>() / n;
 
-    Ok(b)
+    assert!((mean - 0.8).abs() < 0.02);
+    assert!((var - 0.2).abs() < 0.02);
   }
 }
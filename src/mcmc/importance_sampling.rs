@@ -0,0 +1,188 @@
+use crate::{mcmc::ln_sum_exp, Distribution, DistributionError, RandomVariable};
+use rand::prelude::*;
+
+/// Weight draws from the proposal q(x) against an unnormalized target f(x|theta) with w = f(x|theta) / q(x).
+pub struct ImportanceSampling<'a, D, Q, T, U, UQ>
+where
+  D: Distribution<T = T, U = U>,
+  Q: Distribution<T = T, U = UQ>,
+  T: RandomVariable,
+  U: RandomVariable,
+  UQ: RandomVariable,
+{
+  distribution: &'a D,
+  theta: &'a U,
+  proposal: &'a Q,
+  proposal_theta: &'a UQ,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ImportanceSamplingError {
+  #[error("Number of samples must be positive")]
+  NoSamples,
+}
+
+impl<'a, D, Q, T, U, UQ> ImportanceSampling<'a, D, Q, T, U, UQ>
+where
+  D: Distribution<T = T, U = U>,
+  Q: Distribution<T = T, U = UQ>,
+  T: RandomVariable,
+  U: RandomVariable,
+  UQ: RandomVariable,
+{
+  pub fn new(distribution: &'a D, theta: &'a U, proposal: &'a Q, proposal_theta: &'a UQ) -> Self {
+    Self {
+      distribution,
+      theta,
+      proposal,
+      proposal_theta,
+    }
+  }
+
+  pub fn sample(
+    &self,
+    n: usize,
+    rng: &mut dyn RngCore,
+  ) -> Result<ImportanceSamples<T>, DistributionError> {
+    if n == 0 {
+      return Err(DistributionError::InvalidParameters(
+        ImportanceSamplingError::NoSamples.into(),
+      ));
+    }
+
+    let x = self.proposal.sample_n(n, self.proposal_theta, rng)?;
+    let ln_w = x
+      .iter()
+      .map(|xi| {
+        Ok(self.distribution.ln_p(xi, self.theta)? - self.proposal.ln_p(xi, self.proposal_theta)?)
+      })
+      .collect::<Result<Vec<_>, DistributionError>>()?;
+
+    Ok(ImportanceSamples { x, ln_w })
+  }
+}
+
+/// Draws of the proposal with their unnormalized log weights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportanceSamples<T>
+where
+  T: RandomVariable,
+{
+  x: Vec<T>,
+  ln_w: Vec<f64>,
+}
+
+impl<T> ImportanceSamples<T>
+where
+  T: RandomVariable,
+{
+  pub fn x(&self) -> &[T] {
+    &self.x
+  }
+
+  pub fn ln_weights(&self) -> &[f64] {
+    &self.ln_w
+  }
+
+  /// Weights normalized to sum to 1, in the order of `x`.
+  pub fn importance_weights(&self) -> Vec<f64> {
+    let ln_sum = ln_sum_exp(&self.ln_w);
+
+    self.ln_w.iter().map(|w| (w - ln_sum).exp()).collect()
+  }
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?1%2F\sum_i%20w_i%5E2) of the normalized weights, between 1 and N.
+  pub fn effective_sample_size(&self) -> f64 {
+    1.0 / self.importance_weights().iter().map(|w| w * w).sum::<f64>()
+  }
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?\ln%20\hat%7BZ%7D%3D\ln\sum_i%20w_i-\ln%20N), the estimate of ln ∫ f(x|theta) dx.
+  pub fn log_evidence(&self) -> f64 {
+    ln_sum_exp(&self.ln_w) - (self.ln_w.len() as f64).ln()
+  }
+
+  /// Self-normalized estimate of the expectation of `f` under the normalized target.
+  pub fn expectation<F>(&self, f: F) -> f64
+  where
+    F: Fn(&T) -> f64,
+  {
+    let ln_sum = ln_sum_exp(&self.ln_w);
+
+    self
+      .x
+      .iter()
+      .zip(self.ln_w.iter())
+      .map(|(xi, w)| (w - ln_sum).exp() * f(xi))
+      .sum()
+  }
+
+  pub fn eject(self) -> (Vec<T>, Vec<f64>) {
+    (self.x, self.ln_w)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{mcmc::ImportanceSampling, Distribution, DistributionError, Normal, NormalParams};
+  use rand::prelude::*;
+
+  /// 3 N(x; 1, 0.5^2), whose normalizing constant is 3
+  #[derive(Clone, Debug)]
+  struct Scaled;
+
+  impl Distribution for Scaled {
+    type T = f64;
+    type U = ();
+
+    fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+      Ok(self.ln_p(x, theta)?.exp())
+    }
+
+    fn ln_p(&self, x: &Self::T, _: &Self::U) -> Result<f64, DistributionError> {
+      Ok(3f64.ln() + Normal.ln_p(x, &NormalParams::new(1.0, 0.5)?)?)
+    }
+
+    fn sample(&self, _: &Self::U, _: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+      Ok(1.0)
+    }
+  }
+
+  #[test]
+  fn it_works() {
+    let proposal_theta = NormalParams::new(0.0, 2.0).unwrap();
+    let sampler = ImportanceSampling::new(&Scaled, &(), &Normal, &proposal_theta);
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let coarse = sampler.sample(100, &mut rng).unwrap();
+    let fine = sampler.sample(20000, &mut rng).unwrap();
+
+    let expected = 3f64.ln();
+    assert!((fine.log_evidence() - expected).abs() < 0.02);
+    assert!((fine.log_evidence() - expected).abs() < (coarse.log_evidence() - expected).abs());
+    assert!((fine.expectation(|x| *x) - 1.0).abs() < 0.02);
+
+    assert!(sampler.sample(0, &mut rng).is_err());
+  }
+
+  #[test]
+  fn effective_sample_size() {
+    let n = 1000;
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let matched_theta = NormalParams::new(1.0, 0.5).unwrap();
+    let matched = ImportanceSampling::new(&Scaled, &(), &Normal, &matched_theta)
+      .sample(n, &mut rng)
+      .unwrap();
+    let w = matched.importance_weights();
+    assert_eq!(w.len(), n);
+    assert!((w.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!((matched.effective_sample_size() - n as f64).abs() < 1e-6);
+
+    // the proposal barely covers the target
+    let mismatched_theta = NormalParams::new(-3.0, 0.5).unwrap();
+    let mismatched = ImportanceSampling::new(&Scaled, &(), &Normal, &mismatched_theta)
+      .sample(n, &mut rng)
+      .unwrap();
+    assert!(mismatched.effective_sample_size() < 5.0);
+  }
+}
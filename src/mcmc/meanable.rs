@@ -0,0 +1,80 @@
+use super::OnlineMoments;
+use crate::DistributionError;
+
+pub trait Meanable {
+  /// Elementwise mean of the samples, empty for no samples.
+  fn mean(self) -> Result<Vec<f64>, DistributionError>;
+}
+
+impl<I> Meanable for I
+where
+  I: IntoIterator<Item = Vec<f64>>,
+{
+  fn mean(self) -> Result<Vec<f64>, DistributionError> {
+    self.into_iter().map(Ok).try_mean()
+  }
+}
+
+pub trait TryMeanable {
+  /// Elementwise mean of samples that may have failed to be drawn, stopping at the first error.
+  fn try_mean(self) -> Result<Vec<f64>, DistributionError>;
+}
+
+impl<I> TryMeanable for I
+where
+  I: IntoIterator<Item = Result<Vec<f64>, DistributionError>>,
+{
+  fn try_mean(self) -> Result<Vec<f64>, DistributionError> {
+    let mut moments = OnlineMoments::new();
+    for x in self {
+      moments.push(&x?)?;
+    }
+
+    Ok(moments.mean().to_vec())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::mcmc::{Meanable, TryMeanable};
+  use crate::{Distribution, DistributionError, MultivariateNormal, MultivariateNormalParams};
+  use opensrdk_linear_algebra::*;
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let params = MultivariateNormalParams::new(vec![1.0, -2.0], Matrix::identity(2)).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let x = MultivariateNormal
+      .sample_n(10000, &params, &mut rng)
+      .unwrap();
+    let mean = x.clone().mean().unwrap();
+    assert!((mean[0] - 1.0).abs() < 0.05);
+    assert!((mean[1] + 2.0).abs() < 0.05);
+
+    let draws = (0..10000).map(|_| MultivariateNormal.sample(&params, &mut rng));
+    let try_mean = draws.try_mean().unwrap();
+    assert!((try_mean[0] - 1.0).abs() < 0.05);
+
+    assert_eq!(Vec::<Vec<f64>>::new().mean().unwrap(), Vec::<f64>::new());
+    assert!(vec![vec![1.0], vec![1.0, 2.0]].mean().is_err());
+  }
+
+  #[test]
+  fn try_mean_error() {
+    let mut drawn = 0;
+    let draws = (0..10).map(|i| {
+      drawn += 1;
+      if i == 3 {
+        return Err(DistributionError::Others("draw failed".into()));
+      }
+
+      Ok(vec![i as f64])
+    });
+
+    assert!(draws.try_mean().is_err());
+    // nothing is drawn after the error
+    assert_eq!(drawn, 4);
+  }
+}
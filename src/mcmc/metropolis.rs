@@ -0,0 +1,534 @@
+use super::{rhat_multivariate, CheckpointError, CheckpointableSampler, SamplerState};
+use crate::{
+  Distribution, DistributionError, MultivariateNormal, MultivariateNormalParams, Normal,
+  NormalParams, RandomVariable,
+};
+use opensrdk_linear_algebra::*;
+use rand::prelude::*;
+use rand_chacha::ChaCha12Rng;
+use std::error::Error;
+
+/// Draws per chain between two R-hat checks in `MetropolisHastings::run_until`.
+pub const RHAT_INTERVAL: usize = 50;
+
+#[derive(thiserror::Error, Debug)]
+pub enum MetropolisWithinGibbsError {
+  #[error("Block index out of range")]
+  IndexOutOfRange,
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+}
+
+type ConditionalSampler<'b> =
+  dyn Fn(&[f64], &mut dyn RngCore) -> Result<Vec<f64>, Box<dyn Error>> + 'b;
+
+/// A block of coordinates updated in one step of `MetropolisHastings::sample_within_gibbs`.
+pub enum GibbsBlock<'b> {
+  /// Drawn directly from its full conditional, given the whole current value.
+  Direct {
+    indices: Vec<usize>,
+    sample: &'b ConditionalSampler<'b>,
+  },
+  /// Updated by one random-walk Metropolis step with the proposal `x'_i = x_i + scale z_i`.
+  Metropolis { indices: Vec<usize>, scale: f64 },
+}
+
+/// Sample from p(x|theta) by random-walk Metropolis-Hastings with a Gaussian proposal
+pub struct MetropolisHastings<'a, D, U>
+where
+  D: Distribution<T = Vec<f64>, U = U>,
+  U: RandomVariable,
+{
+  distribution: &'a D,
+  theta: &'a U,
+  proposal_lcov: Option<Matrix>,
+  chain: Option<Chain>,
+}
+
+/// The chain advanced by `MetropolisHastings::step`, with the generator `StdRng` wraps so that its position can be saved.
+#[derive(Clone, Debug)]
+struct Chain {
+  x: Vec<f64>,
+  rng: ChaCha12Rng,
+  step: usize,
+  accepted: usize,
+}
+
+impl<'a, D, U> MetropolisHastings<'a, D, U>
+where
+  D: Distribution<T = Vec<f64>, U = U>,
+  U: RandomVariable,
+{
+  /// Proposes with the identity covariance unless `with_proposal_cov` is given.
+  pub fn new(distribution: &'a D, theta: &'a U) -> Self {
+    Self {
+      distribution,
+      theta,
+      proposal_lcov: None,
+      chain: None,
+    }
+  }
+
+  /// `L` of the proposal covariance `Sigma = L * L^T`, so that `x' = x + L z`.
+  pub fn with_proposal_cov(mut self, proposal_lcov: Matrix) -> Self {
+    self.proposal_lcov = Some(proposal_lcov);
+
+    self
+  }
+
+  pub fn proposal_lcov(&self) -> Option<&Matrix> {
+    self.proposal_lcov.as_ref()
+  }
+
+  /// One transition from the current value `x`.
+  /// The acceptance ratio is formed from `ln_p`, so it stays defined where `p` underflows to 0 for both states.
+  pub fn sample(&self, x: Vec<f64>, rng: &mut dyn RngCore) -> Result<Vec<f64>, Box<dyn Error>> {
+    Ok(self.transition(x, rng)?.0)
+  }
+
+  /// The next state and whether the proposal was accepted.
+  pub(crate) fn transition(
+    &self,
+    x: Vec<f64>,
+    rng: &mut dyn RngCore,
+  ) -> Result<(Vec<f64>, bool), DistributionError> {
+    let lcov = match &self.proposal_lcov {
+      Some(lcov) => lcov.clone(),
+      None => Matrix::identity(x.len()),
+    };
+
+    let ln_px = self.distribution.ln_p(&x, self.theta)?;
+    let proposal =
+      MultivariateNormal.sample(&MultivariateNormalParams::new(x.clone(), lcov)?, rng)?;
+    let ln_p_proposal = self.distribution.ln_p(&proposal, self.theta)?;
+
+    // the random-walk proposal is symmetric
+    if rng.gen_range(0.0f64..1.0).ln() < ln_p_proposal - ln_px {
+      return Ok((proposal, true));
+    }
+
+    Ok((x, false))
+  }
+
+  /// One sweep over `blocks`, in order, from the current value `x`.
+  /// A `Metropolis` block targets its full conditional through the joint `ln_p`, as the other coordinates stay fixed during its step.
+  pub fn sample_within_gibbs(
+    &self,
+    mut x: Vec<f64>,
+    blocks: &[GibbsBlock],
+    rng: &mut dyn RngCore,
+  ) -> Result<Vec<f64>, Box<dyn Error>> {
+    for block in blocks {
+      let indices = match block {
+        GibbsBlock::Direct { indices, .. } | GibbsBlock::Metropolis { indices, .. } => indices,
+      };
+      if indices.iter().any(|&i| x.len() <= i) {
+        return Err(MetropolisWithinGibbsError::IndexOutOfRange.into());
+      }
+
+      match block {
+        GibbsBlock::Direct { sample, .. } => {
+          let values = sample(&x, rng)?;
+          if values.len() != indices.len() {
+            return Err(MetropolisWithinGibbsError::DimensionMismatch.into());
+          }
+          for (&i, value) in indices.iter().zip(values) {
+            x[i] = value;
+          }
+        }
+        GibbsBlock::Metropolis { scale, .. } => {
+          let ln_px = self.distribution.ln_p(&x, self.theta)?;
+          let mut proposal = x.clone();
+          for &i in indices {
+            proposal[i] = Normal.sample(&NormalParams::new(x[i], *scale)?, rng)?;
+          }
+          let ln_p_proposal = self.distribution.ln_p(&proposal, self.theta)?;
+
+          if rng.gen_range(0.0f64..1.0).ln() < ln_p_proposal - ln_px {
+            x = proposal;
+          }
+        }
+      }
+    }
+
+    Ok(x)
+  }
+
+  /// Starts the chain advanced by `step` from `initial`, with its own generator seeded by `seed`.
+  pub fn start(&mut self, initial: Vec<f64>, seed: [u8; 32]) {
+    self.chain = Some(Chain {
+      x: initial,
+      rng: ChaCha12Rng::from_seed(seed),
+      step: 0,
+      accepted: 0,
+    });
+  }
+
+  /// One transition of the chain begun by `start` or `load_state`.
+  pub fn step(&mut self) -> Result<Vec<f64>, Box<dyn Error>> {
+    let mut chain = self.chain.take().ok_or(CheckpointError::NotStarted)?;
+    let result = self.transition(chain.x.clone(), &mut chain.rng);
+    if let Ok((x, accepted)) = &result {
+      chain.x = x.clone();
+      chain.step += 1;
+      chain.accepted += *accepted as usize;
+    }
+    self.chain = Some(chain);
+
+    Ok(result?.0)
+  }
+
+  /// Yields one state per transition, starting from `initial`.
+  /// The first error from `sample` is yielded as the last item.
+  pub fn iter<'b>(
+    &'b self,
+    rng: &'b mut dyn RngCore,
+    initial: Vec<f64>,
+  ) -> impl Iterator<Item = Result<Vec<f64>, Box<dyn Error>>> + 'b {
+    let mut x = Some(initial);
+
+    std::iter::from_fn(move || {
+      let next = self.sample(x.take()?, rng);
+      if let Ok(next) = &next {
+        x = Some(next.clone());
+      }
+
+      Some(next)
+    })
+  }
+
+  /// Runs one chain from each of `initials` until the split R-hat of every dimension drops below `rhat_threshold`, or until `max_iter` draws per chain.
+  /// R-hat is checked every `RHAT_INTERVAL` draws, and the chains are returned as `chains[chain][draw][dimension]`.
+  pub fn run_until(
+    &self,
+    rng: &mut dyn RngCore,
+    initials: Vec<Vec<f64>>,
+    max_iter: usize,
+    rhat_threshold: f64,
+  ) -> Result<Vec<Vec<Vec<f64>>>, Box<dyn Error>> {
+    let mut x = initials;
+    let mut chains = vec![vec![]; x.len()];
+
+    for t in 1..=max_iter {
+      for (xi, chain) in x.iter_mut().zip(chains.iter_mut()) {
+        *xi = self.sample(xi.clone(), rng)?;
+        chain.push(xi.clone());
+      }
+
+      if t % RHAT_INTERVAL == 0
+        && rhat_multivariate(&chains)?
+          .iter()
+          .all(|&rhat| rhat < rhat_threshold)
+      {
+        break;
+      }
+    }
+
+    Ok(chains)
+  }
+}
+
+impl<'a, D, U> CheckpointableSampler for MetropolisHastings<'a, D, U>
+where
+  D: Distribution<T = Vec<f64>, U = U>,
+  U: RandomVariable,
+{
+  fn save_state(&self) -> Result<SamplerState, DistributionError> {
+    let chain = self
+      .chain
+      .as_ref()
+      .ok_or_else(|| DistributionError::InvalidParameters(CheckpointError::NotStarted.into()))?;
+
+    Ok(SamplerState::new(
+      chain.x.clone(),
+      chain.rng.get_seed(),
+      chain.rng.get_word_pos(),
+      chain.step,
+      chain.accepted,
+    ))
+  }
+
+  fn load_state(&mut self, state: SamplerState) -> Result<(), DistributionError> {
+    if let Some(lcov) = &self.proposal_lcov {
+      if lcov.rows() != state.position().len() {
+        return Err(DistributionError::InvalidParameters(
+          CheckpointError::DimensionMismatch.into(),
+        ));
+      }
+    }
+
+    let mut rng = ChaCha12Rng::from_seed(state.seed());
+    rng.set_word_pos(state.word_pos());
+    self.chain = Some(Chain {
+      x: state.position().to_vec(),
+      rng,
+      step: state.step(),
+      accepted: state.accepted(),
+    });
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    mcmc::{
+      ess_multivariate, rhat_multivariate, CheckpointableSampler, GibbsBlock, MetropolisHastings,
+    },
+    Distribution, DistributionError, InstantDistribution, MultivariateNormal,
+    MultivariateNormalParams, Normal, NormalParams,
+  };
+  use opensrdk_linear_algebra::*;
+  use rand::{prelude::*, rngs::SmallRng};
+  use std::error::Error;
+
+  #[test]
+  fn it_works() {
+    let lsigma = Matrix::from(2, vec![1.0, 0.95, 0.0, (1.0f64 - 0.95 * 0.95).sqrt()]);
+    let params = MultivariateNormalParams::new(vec![0.0, 0.0], lsigma.clone()).unwrap();
+
+    let scale = 2.38 / 2f64.sqrt();
+    let run = |sampler: MetropolisHastings<_, _>| {
+      let mut rng = StdRng::from_seed([1; 32]);
+      let mut x = vec![0.0, 0.0];
+
+      (0..10000)
+        .map(|_| {
+          x = sampler.sample(x.clone(), &mut rng).unwrap();
+          x.clone()
+        })
+        .collect::<Vec<_>>()
+    };
+
+    let isotropic = run(
+      MetropolisHastings::new(&MultivariateNormal, &params)
+        .with_proposal_cov(scale * Matrix::identity(2)),
+    );
+    let matched =
+      run(MetropolisHastings::new(&MultivariateNormal, &params).with_proposal_cov(scale * lsigma));
+
+    let mean = matched.iter().map(|xi| xi[0]).sum::<f64>() / matched.len() as f64;
+    assert!(mean.abs() < 0.1);
+
+    let isotropic = ess_multivariate(&[isotropic]).unwrap();
+    let matched = ess_multivariate(&[matched]).unwrap();
+    assert!(2.0 * isotropic[0] < matched[0]);
+  }
+
+  #[test]
+  fn concentrated() {
+    let params = MultivariateNormalParams::new(vec![0.0], 0.01 * Matrix::identity(1)).unwrap();
+    let sampler = MetropolisHastings::new(&MultivariateNormal, &params)
+      .with_proposal_cov(0.05 * Matrix::identity(1));
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    // the density ratio would be 0 / 0 along the way from the start
+    let initial = vec![1.0];
+    assert_eq!(MultivariateNormal.p(&initial, &params).unwrap(), 0.0);
+
+    let x = sampler
+      .iter(&mut rng, initial)
+      .take(2000)
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    assert_eq!(x.len(), 2000);
+    assert!(x.iter().all(|xi| xi[0].is_finite()));
+
+    let tail = &x[1000..];
+    let mean = tail.iter().map(|xi| xi[0]).sum::<f64>() / tail.len() as f64;
+    assert!(mean.abs() < 0.01);
+  }
+
+  #[test]
+  fn iter() {
+    let params = MultivariateNormalParams::new(vec![1.0, -1.0], Matrix::identity(2)).unwrap();
+    let sampler = MetropolisHastings::new(&MultivariateNormal, &params);
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let x = sampler
+      .iter(&mut rng, vec![0.0, 0.0])
+      .take(5000)
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    assert_eq!(x.len(), 5000);
+
+    let mean = x.iter().map(|xi| xi[0]).sum::<f64>() / x.len() as f64;
+    assert!((mean - 1.0).abs() < 0.15);
+
+    // yields the error and stops when the target rejects the state
+    let mut rng = StdRng::from_seed([1; 32]);
+    let mut rejected = sampler.iter(&mut rng, vec![0.0]);
+    assert!(rejected.next().unwrap().is_err());
+    assert!(rejected.next().is_none());
+  }
+
+  #[test]
+  fn other_rng() {
+    let params = MultivariateNormalParams::new(vec![1.0, -1.0], Matrix::identity(2)).unwrap();
+    let sampler = MetropolisHastings::new(&MultivariateNormal, &params);
+    // a generator other than `StdRng`
+    let mut rng = SmallRng::seed_from_u64(1);
+
+    let x = sampler
+      .iter(&mut rng, vec![0.0, 0.0])
+      .take(5000)
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    let mean = x.iter().map(|xi| xi[1]).sum::<f64>() / x.len() as f64;
+    assert!((mean + 1.0).abs() < 0.15);
+
+    let direct = MultivariateNormal
+      .sample_n(2000, &params, &mut rng)
+      .unwrap();
+    let mean = direct.iter().map(|xi| xi[0]).sum::<f64>() / direct.len() as f64;
+    assert!((mean - 1.0).abs() < 0.1);
+  }
+
+  #[test]
+  fn run_until() {
+    let params = MultivariateNormalParams::new(vec![1.0, -1.0], Matrix::identity(2)).unwrap();
+    let sampler = MetropolisHastings::new(&MultivariateNormal, &params);
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    // overdispersed starting points
+    let initials = vec![
+      vec![10.0, 10.0],
+      vec![-10.0, 10.0],
+      vec![10.0, -10.0],
+      vec![-10.0, -10.0],
+    ];
+    let max_iter = 20000;
+    let chains = sampler
+      .run_until(&mut rng, initials, max_iter, 1.05)
+      .unwrap();
+
+    let n = chains[0].len();
+    assert_eq!(chains.len(), 4);
+    assert!(chains.iter().all(|chain| chain.len() == n));
+    assert!(n < max_iter / 10);
+    assert!(rhat_multivariate(&chains)
+      .unwrap()
+      .iter()
+      .all(|&rhat| rhat < 1.05));
+
+    // a threshold below 1 is never met
+    let chains = sampler
+      .run_until(&mut rng, vec![vec![0.0, 0.0]; 2], 200, 0.5)
+      .unwrap();
+    assert_eq!(chains[0].len(), 200);
+  }
+
+  #[test]
+  fn within_gibbs() {
+    // mu ~ N(0, 1), beta | mu ~ N(mu, 1), y_i ~ Bernoulli(sigmoid(beta t_i))
+    let t = [-1.5, -1.0, -0.5, 0.0, 0.5, 1.0, 1.5, 2.0];
+    let y = [false, false, true, false, true, true, false, true];
+    let ln_likelihood = |beta: f64| {
+      t.iter()
+        .zip(y.iter())
+        .map(|(&ti, &yi)| {
+          let z = if yi { beta * ti } else { -beta * ti };
+          -(1.0 + (-z).exp()).ln()
+        })
+        .sum::<f64>()
+    };
+    let ln_p = |x: &Vec<f64>, _: &()| -> Result<f64, DistributionError> {
+      Ok(
+        Normal.ln_p(&x[0], &NormalParams::new(0.0, 1.0)?)?
+          + Normal.ln_p(&x[1], &NormalParams::new(x[0], 1.0)?)?
+          + ln_likelihood(x[1]),
+      )
+    };
+    let p =
+      |x: &Vec<f64>, theta: &()| -> Result<f64, DistributionError> { Ok(ln_p(x, theta)?.exp()) };
+    let sample = |_: &(), _: &mut dyn RngCore| -> Result<Vec<f64>, DistributionError> {
+      Err(DistributionError::Others(
+        "no direct draw from the joint".into(),
+      ))
+    };
+    let distribution = InstantDistribution::new(&p, &sample).with_ln_p(&ln_p);
+
+    // mu | beta ~ N(beta / 2, 1 / 2) is conjugate, but beta | mu is not
+    let sample_mu = |x: &[f64], rng: &mut dyn RngCore| -> Result<Vec<f64>, Box<dyn Error>> {
+      Ok(vec![Normal.sample(
+        &NormalParams::new(x[1] / 2.0, 0.5f64.sqrt())?,
+        rng,
+      )?])
+    };
+    let blocks = [
+      GibbsBlock::Direct {
+        indices: vec![0],
+        sample: &sample_mu,
+      },
+      GibbsBlock::Metropolis {
+        indices: vec![1],
+        scale: 1.5,
+      },
+    ];
+
+    let sampler = MetropolisHastings::new(&distribution, &());
+    let mut rng = StdRng::from_seed([1; 32]);
+    let mut x = vec![0.0, 0.0];
+    let samples = (0..20000)
+      .map(|_| {
+        x = sampler
+          .sample_within_gibbs(x.clone(), &blocks, &mut rng)
+          .unwrap();
+        x.clone()
+      })
+      .collect::<Vec<_>>();
+
+    // marginally beta ~ N(0, 2) a priori, so E[beta | y] by quadrature
+    let grid = (0..4001).map(|i| -10.0 + i as f64 * 0.005);
+    let (num, den) = grid.fold((0.0, 0.0), |(num, den), beta| {
+      let w = (-beta * beta / 4.0 + ln_likelihood(beta)).exp();
+      (num + beta * w, den + w)
+    });
+    let expected = num / den;
+
+    let mu = samples.iter().map(|xi| xi[0]).sum::<f64>() / samples.len() as f64;
+    let beta = samples.iter().map(|xi| xi[1]).sum::<f64>() / samples.len() as f64;
+    assert!((beta - expected).abs() < 0.1);
+    assert!((mu - expected / 2.0).abs() < 0.1);
+
+    let out_of_range = [GibbsBlock::Metropolis {
+      indices: vec![2],
+      scale: 1.0,
+    }];
+    assert!(sampler
+      .sample_within_gibbs(vec![0.0, 0.0], &out_of_range, &mut rng)
+      .is_err());
+  }
+
+  #[test]
+  fn checkpoint() {
+    let params = MultivariateNormalParams::new(vec![1.0, -1.0], Matrix::identity(2)).unwrap();
+
+    let mut sampler = MetropolisHastings::new(&MultivariateNormal, &params);
+    assert!(sampler.save_state().is_err());
+    assert!(sampler.step().is_err());
+
+    sampler.start(vec![0.0, 0.0], [1; 32]);
+    for _ in 0..100 {
+      sampler.step().unwrap();
+    }
+    let saved = sampler.save_state().unwrap();
+    let continued = (0..100)
+      .map(|_| sampler.step().unwrap())
+      .collect::<Vec<_>>();
+    let state = sampler.save_state().unwrap();
+    drop(sampler);
+
+    let mut resumed = MetropolisHastings::new(&MultivariateNormal, &params);
+    assert_eq!(saved.step(), 100);
+    resumed.load_state(saved).unwrap();
+    let replayed = (0..100)
+      .map(|_| resumed.step().unwrap())
+      .collect::<Vec<_>>();
+
+    assert_eq!(replayed, continued);
+    assert_eq!(resumed.save_state().unwrap(), state);
+    assert!(0.0 < state.acceptance_rate() && state.acceptance_rate() < 1.0);
+  }
+}
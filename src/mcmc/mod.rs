@@ -1,3 +1,27 @@
+pub mod chains;
+pub mod checkpoint;
+pub mod covariable;
+pub mod diagnostics;
 pub mod elliptical_slice;
+pub mod importance_sampling;
+pub mod meanable;
+pub mod metropolis;
+pub mod online_moments;
+pub mod quantile;
+pub mod simplex;
+pub mod slice_sampling;
+pub mod smc;
 
+pub use chains::*;
+pub use checkpoint::*;
+pub use covariable::*;
+pub use diagnostics::*;
 pub use elliptical_slice::*;
+pub use importance_sampling::*;
+pub use meanable::*;
+pub use metropolis::*;
+pub use online_moments::*;
+pub use quantile::*;
+pub use simplex::*;
+pub use slice_sampling::*;
+pub use smc::*;
@@ -0,0 +1,94 @@
+use crate::DistributionError;
+
+#[derive(thiserror::Error, Debug)]
+pub enum OnlineMomentsError {
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+}
+
+/// Elementwise mean and variance of a stream of samples by Welford's algorithm, so that a long chain need not be kept in memory.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OnlineMoments {
+  n: usize,
+  mean: Vec<f64>,
+  m2: Vec<f64>,
+}
+
+impl OnlineMoments {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The first sample fixes the dimension.
+  pub fn push(&mut self, x: &[f64]) -> Result<(), DistributionError> {
+    if self.n == 0 {
+      self.mean = vec![0.0; x.len()];
+      self.m2 = vec![0.0; x.len()];
+    } else if x.len() != self.mean.len() {
+      return Err(DistributionError::InvalidParameters(
+        OnlineMomentsError::DimensionMismatch.into(),
+      ));
+    }
+    self.n += 1;
+
+    for ((xi, mi), m2i) in x.iter().zip(self.mean.iter_mut()).zip(self.m2.iter_mut()) {
+      let delta = xi - *mi;
+      *mi += delta / self.n as f64;
+      *m2i += delta * (xi - *mi);
+    }
+
+    Ok(())
+  }
+
+  pub fn n(&self) -> usize {
+    self.n
+  }
+
+  pub fn mean(&self) -> &[f64] {
+    &self.mean
+  }
+
+  /// Unbiased sample variance, zero until two samples have been pushed.
+  pub fn variance(&self) -> Vec<f64> {
+    if self.n < 2 {
+      return vec![0.0; self.m2.len()];
+    }
+
+    self
+      .m2
+      .iter()
+      .map(|m2i| m2i / (self.n - 1) as f64)
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::mcmc::{Covariable, OnlineMoments};
+
+  #[test]
+  fn it_works() {
+    let x = (0..50)
+      .map(|i| {
+        let t = i as f64;
+        vec![1e6 + (0.7 * t).sin(), t * t / 10.0]
+      })
+      .collect::<Vec<_>>();
+
+    let mut moments = OnlineMoments::new();
+    for xi in x.iter() {
+      moments.push(xi).unwrap();
+    }
+    assert_eq!(moments.n(), 50);
+
+    let cov = x.clone().covariance().unwrap();
+    for d in 0..2 {
+      let mean = x.iter().map(|xi| xi[d]).sum::<f64>() / 50.0;
+      assert!((moments.mean()[d] - mean).abs() < 1e-9 * mean.abs().max(1.0));
+      assert!((moments.variance()[d] - cov[(d, d)]).abs() < 1e-8 * cov[(d, d)]);
+    }
+
+    assert!(moments.push(&[0.0]).is_err());
+    assert_eq!(OnlineMoments::new().variance(), Vec::<f64>::new());
+  }
+}
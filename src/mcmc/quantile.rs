@@ -0,0 +1,198 @@
+use crate::{check_probability, DistributionError};
+use special::Gamma;
+
+#[derive(thiserror::Error, Debug)]
+pub enum SampleQuantileError {
+  #[error("Samples are empty")]
+  Empty,
+  #[error("Dimension mismatch")]
+  DimensionMismatch,
+  #[error("Weights must be non-negative with a positive sum")]
+  InvalidWeights,
+  #[error("At least two distinct samples are needed")]
+  TooFewDistinct,
+}
+
+/// `p`-quantile of equally weighted samples, the same as `weighted_quantile` with unit weights.
+pub fn quantile(samples: &[f64], p: f64) -> Result<f64, DistributionError> {
+  weighted_quantile(samples, &vec![1.0; samples.len()], p)
+}
+
+/// `p`-quantile of weighted samples.
+/// The sorted `i`-th sample sits at the midpoint of its cumulative weight ![tex](https://latex.codecogs.com/svg.latex?%28S_i-w_i/2%29/S), and `p` is linearly interpolated between these points.
+pub fn weighted_quantile(
+  samples: &[f64],
+  weights: &[f64],
+  p: f64,
+) -> Result<f64, DistributionError> {
+  check_probability(p)?;
+  if samples.is_empty() {
+    return Err(DistributionError::InvalidParameters(
+      SampleQuantileError::Empty.into(),
+    ));
+  }
+  if samples.len() != weights.len() {
+    return Err(DistributionError::InvalidParameters(
+      SampleQuantileError::DimensionMismatch.into(),
+    ));
+  }
+  let total = weights.iter().sum::<f64>();
+  if weights.iter().any(|&w| w < 0.0) || total <= 0.0 {
+    return Err(DistributionError::InvalidParameters(
+      SampleQuantileError::InvalidWeights.into(),
+    ));
+  }
+
+  let mut sorted = samples
+    .iter()
+    .copied()
+    .zip(weights.iter().copied())
+    .filter(|&(_, w)| w > 0.0)
+    .collect::<Vec<_>>();
+  sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+  let mut cumulative = 0.0;
+  let points = sorted
+    .iter()
+    .map(|&(x, w)| {
+      cumulative += w;
+      ((cumulative - w / 2.0) / total, x)
+    })
+    .collect::<Vec<_>>();
+
+  let (first, last) = (points[0], points[points.len() - 1]);
+  if p <= first.0 {
+    return Ok(first.1);
+  }
+  if last.0 <= p {
+    return Ok(last.1);
+  }
+
+  let i = points
+    .iter()
+    .position(|&(c, _)| p < c)
+    .unwrap_or(points.len() - 1);
+  let (c0, x0) = points[i - 1];
+  let (c1, x1) = points[i];
+
+  Ok(x0 + (p - c0) / (c1 - c0) * (x1 - x0))
+}
+
+/// Empirical two-sided tail mass of `x`, twice the smaller fraction of samples at or beyond `x` on either side, capped at 1.
+pub fn tail_probability(samples: &[f64], x: f64) -> Result<f64, DistributionError> {
+  if samples.is_empty() {
+    return Err(DistributionError::InvalidParameters(
+      SampleQuantileError::Empty.into(),
+    ));
+  }
+
+  let n = samples.len() as f64;
+  let lower = samples.iter().filter(|&&s| s <= x).count() as f64 / n;
+  let upper = samples.iter().filter(|&&s| x <= s).count() as f64 / n;
+
+  Ok((2.0 * lower.min(upper)).min(1.0))
+}
+
+/// Whether `x` falls in the tails of the samples at level `alpha`.
+pub fn is_outlier(samples: &[f64], x: f64, alpha: f64) -> Result<bool, DistributionError> {
+  check_probability(alpha)?;
+
+  Ok(tail_probability(samples, x)? < alpha)
+}
+
+/// Kozachenko-Leonenko estimate of the differential entropy of the samples from the distance ![tex](https://latex.codecogs.com/svg.latex?%5Cvarepsilon_i) of each sample to its nearest neighbour,
+/// ![tex](https://latex.codecogs.com/svg.latex?%5Chat%7BH%7D%3D%5Cpsi%28n%29-%5Cpsi%281%29+%5Cln2+%5Cfrac%7B1%7D%7Bn%7D%5Csum_i%5Cln%5Cvarepsilon_i).
+pub fn entropy(samples: &[f64]) -> Result<f64, DistributionError> {
+  let n = samples.len();
+  if n < 2 {
+    return Err(DistributionError::InvalidParameters(
+      SampleQuantileError::TooFewDistinct.into(),
+    ));
+  }
+
+  let mut sorted = samples.to_vec();
+  sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+  // in one dimension the nearest neighbour is adjacent in the sorted order
+  let ln_epsilon = (0..n)
+    .map(|i| {
+      let left = if i == 0 {
+        f64::INFINITY
+      } else {
+        sorted[i] - sorted[i - 1]
+      };
+      let right = if i == n - 1 {
+        f64::INFINITY
+      } else {
+        sorted[i + 1] - sorted[i]
+      };
+      left.min(right).ln()
+    })
+    .sum::<f64>()
+    / n as f64;
+  if !ln_epsilon.is_finite() {
+    return Err(DistributionError::InvalidParameters(
+      SampleQuantileError::TooFewDistinct.into(),
+    ));
+  }
+
+  Ok((n as f64).digamma() - 1f64.digamma() + 2f64.ln() + ln_epsilon)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::mcmc::{entropy, is_outlier, quantile, tail_probability, weighted_quantile};
+  use crate::{Distribution, Normal, NormalParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    // cumulative midpoints are 1/16, 3/16, 5/16 and 11/16, so the median lies halfway between 3 and 10
+    let samples = vec![10.0, 1.0, 3.0, 2.0];
+    let weights = vec![5.0, 1.0, 1.0, 1.0];
+    assert!((weighted_quantile(&samples, &weights, 0.5).unwrap() - 6.5).abs() < 1e-12);
+    assert_eq!(weighted_quantile(&samples, &weights, 0.0).unwrap(), 1.0);
+    assert_eq!(weighted_quantile(&samples, &weights, 1.0).unwrap(), 10.0);
+
+    assert!((quantile(&samples, 0.5).unwrap() - 2.5).abs() < 1e-12);
+
+    assert!(quantile(&samples, 1.5).is_err());
+    assert!(quantile(&[], 0.5).is_err());
+    assert!(weighted_quantile(&samples, &[1.0], 0.5).is_err());
+    assert!(weighted_quantile(&samples, &[1.0, -1.0, 1.0, 1.0], 0.5).is_err());
+  }
+
+  #[test]
+  fn outlier() {
+    let params = NormalParams::new(1.0, 2.0).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+    let samples = Normal.sample_n(10000, &params, &mut rng).unwrap();
+
+    assert!(is_outlier(&samples, 1.0 + 4.0 * 2.0, 0.01).unwrap());
+    assert!(is_outlier(&samples, 1.0 - 4.0 * 2.0, 0.01).unwrap());
+    assert!(!is_outlier(&samples, 1.5, 0.01).unwrap());
+
+    // about 4.6% of the mass lies beyond 2 sigma
+    let tail = tail_probability(&samples, 1.0 + 2.0 * 2.0).unwrap();
+    assert!((tail - 0.0455).abs() < 0.01);
+    assert!((tail_probability(&samples, 1.0).unwrap() - 1.0).abs() < 0.02);
+
+    assert!(tail_probability(&[], 0.0).is_err());
+    assert!(is_outlier(&samples, 0.0, 1.5).is_err());
+  }
+
+  #[test]
+  fn nearest_neighbour_entropy() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    for &sigma in [0.5, 2.0].iter() {
+      let params = NormalParams::new(1.0, sigma).unwrap();
+      let samples = Normal.sample_n(10000, &params, &mut rng).unwrap();
+
+      let expected = 0.5 * (2.0 * std::f64::consts::PI * std::f64::consts::E * sigma.powi(2)).ln();
+      assert!((entropy(&samples).unwrap() - expected).abs() < 0.05);
+    }
+
+    assert!(entropy(&[1.0]).is_err());
+    assert!(entropy(&[1.0, 1.0, 1.0]).is_err());
+  }
+}
@@ -0,0 +1,125 @@
+use crate::{sigmoid, DistributionError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum SimplexError {
+  #[error("Not a point of the simplex")]
+  NotSimplex,
+}
+
+/// ![tex](https://latex.codecogs.com/svg.latex?\mathrm%7Bsoftmax%7D%28\mathbf%7Bz%7D%29_i%3De%5E%7Bz_i%7D/\sum_je%5E%7Bz_j%7D), shifted by the largest logit so that it does not overflow.
+pub fn softmax(logits: &[f64]) -> Vec<f64> {
+  let max = logits.iter().fold(f64::NEG_INFINITY, |m, &z| m.max(z));
+  let exp = logits.iter().map(|z| (z - max).exp()).collect::<Vec<_>>();
+  let sum = exp.iter().sum::<f64>();
+
+  exp.into_iter().map(|e| e / sum).collect()
+}
+
+/// Bijection between ![tex](https://latex.codecogs.com/svg.latex?\mathbb%7BR%7D%5E%7BK-1%7D) and the interior of the `K`-simplex, so that samplers can target e.g. `Dirichlet` weights on unconstrained reals.
+///
+/// The `k`-th stick takes the fraction ![tex](https://latex.codecogs.com/svg.latex?z_k%3D\sigma%28y_k-\ln%28K-k%29%29) of what is left, so `y = 0` maps to the uniform weights.
+#[derive(Clone, Debug)]
+pub struct StickBreakingTransform;
+
+impl StickBreakingTransform {
+  /// `K` weights from `K - 1` reals.
+  pub fn forward(&self, y: &[f64]) -> Vec<f64> {
+    let k = y.len() + 1;
+    let mut x = Vec::with_capacity(k);
+    let mut rest = 1.0;
+    for (i, yi) in y.iter().enumerate() {
+      let z = sigmoid(yi - ((k - 1 - i) as f64).ln());
+      x.push(rest * z);
+      rest -= rest * z;
+    }
+    x.push(rest);
+
+    x
+  }
+
+  /// `K - 1` reals from `K` positive weights summing to 1.
+  pub fn inverse(&self, x: &[f64]) -> Result<Vec<f64>, DistributionError> {
+    if x.is_empty() || x.iter().any(|&xi| xi <= 0.0) || (x.iter().sum::<f64>() - 1.0).abs() > 1e-8 {
+      return Err(DistributionError::InvalidParameters(
+        SimplexError::NotSimplex.into(),
+      ));
+    }
+
+    let k = x.len();
+    let mut rest = 1.0;
+
+    Ok(
+      x[..k - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, xi)| {
+          let z = xi / rest;
+          rest -= xi;
+          (z / (1.0 - z)).ln() + ((k - 1 - i) as f64).ln()
+        })
+        .collect(),
+    )
+  }
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?\ln%7C\partial%20x_%7B1:K-1%7D/\partial%20y%7C%3D\sum_k\ln%20z_k%2B\ln%281-z_k%29%2B\ln%281-\sum_%7Bj%3Ck%7Dx_j%29), to be added to the log density of a target on the simplex.
+  pub fn ln_jacobian(&self, y: &[f64]) -> f64 {
+    let k = y.len() + 1;
+    let mut rest = 1.0f64;
+    let mut ln_jacobian = 0.0;
+    for (i, yi) in y.iter().enumerate() {
+      let z = sigmoid(yi - ((k - 1 - i) as f64).ln());
+      ln_jacobian += z.ln() + (1.0 - z).ln() + rest.ln();
+      rest -= rest * z;
+    }
+
+    ln_jacobian
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::mcmc::{softmax, StickBreakingTransform};
+
+  #[test]
+  fn it_works() {
+    let p = softmax(&[1.0, 2.0, 3.0]);
+    assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!(p[0] < p[1] && p[1] < p[2]);
+    // stays finite for logits whose exponential overflows
+    let p = softmax(&[1000.0, 1000.0]);
+    assert_eq!(p, vec![0.5, 0.5]);
+  }
+
+  #[test]
+  fn stick_breaking() {
+    let transform = StickBreakingTransform;
+
+    assert_eq!(transform.forward(&[0.0, 0.0, 0.0]), vec![0.25; 4]);
+
+    let y = vec![0.7, -1.2, 0.3];
+    let x = transform.forward(&y);
+    assert!((x.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    let round_trip = transform.inverse(&x).unwrap();
+    assert!(y
+      .iter()
+      .zip(round_trip.iter())
+      .all(|(a, b)| (a - b).abs() < 1e-10));
+
+    // against the finite-difference determinant of y -> (x_1, x_2) for K = 3
+    let y = vec![0.4, -0.8];
+    let h = 1e-6;
+    let d = |j: usize| {
+      let (mut plus, mut minus) = (y.clone(), y.clone());
+      plus[j] += h;
+      minus[j] -= h;
+      let (xp, xm) = (transform.forward(&plus), transform.forward(&minus));
+      ((xp[0] - xm[0]) / (2.0 * h), (xp[1] - xm[1]) / (2.0 * h))
+    };
+    let ((a, c), (b, e)) = (d(0), d(1));
+    let numerical = (a * e - b * c).abs().ln();
+    assert!((transform.ln_jacobian(&y) - numerical).abs() < 1e-6);
+
+    assert!(transform.inverse(&[0.5, 0.6]).is_err());
+    assert!(transform.inverse(&[1.0, 0.0]).is_err());
+  }
+}
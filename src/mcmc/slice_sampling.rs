@@ -0,0 +1,271 @@
+use crate::{Distribution, DistributionError, RandomVariable};
+use rand::prelude::*;
+use std::error::Error;
+
+#[derive(thiserror::Error, Debug)]
+pub enum SliceSamplingError {
+  #[error("Width must be positive")]
+  WidthMustBePositive,
+  #[error("Current value must have positive density")]
+  OutOfSlice,
+}
+
+/// Sample from p(x|theta) by Neal's stepping-out and shrinkage procedure
+pub struct SliceSampler<'a, D, U>
+where
+  D: Distribution<T = f64, U = U>,
+  U: RandomVariable,
+{
+  distribution: &'a D,
+  theta: &'a U,
+  width: f64,
+  max_stepouts: usize,
+}
+
+impl<'a, D, U> SliceSampler<'a, D, U>
+where
+  D: Distribution<T = f64, U = U>,
+  U: RandomVariable,
+{
+  pub fn new(distribution: &'a D, theta: &'a U) -> Self {
+    Self {
+      distribution,
+      theta,
+      width: 1.0,
+      max_stepouts: usize::MAX,
+    }
+  }
+
+  /// Initial width `w` of the bracket around the current value.
+  pub fn with_width(mut self, width: f64) -> Self {
+    self.width = width;
+
+    self
+  }
+
+  /// Maximum number `m` of width steps taken while expanding the bracket.
+  pub fn with_max_stepouts(mut self, max_stepouts: usize) -> Self {
+    self.max_stepouts = max_stepouts;
+
+    self
+  }
+
+  pub fn width(&self) -> f64 {
+    self.width
+  }
+
+  pub fn max_stepouts(&self) -> usize {
+    self.max_stepouts
+  }
+
+  /// One transition from the current value `x`.
+  pub fn sample(&self, x: f64, rng: &mut dyn RngCore) -> Result<f64, Box<dyn Error>> {
+    let ln_p = |x: f64| self.distribution.ln_p(&x, self.theta);
+
+    Ok(slice_step(&ln_p, x, self.width, self.max_stepouts, rng)?)
+  }
+}
+
+/// Sample from p(x|theta) for `Vec<f64>` values by coordinate-wise slice sampling
+pub struct MultivariateSliceSampler<'a, D, U>
+where
+  D: Distribution<T = Vec<f64>, U = U>,
+  U: RandomVariable,
+{
+  distribution: &'a D,
+  theta: &'a U,
+  width: f64,
+  max_stepouts: usize,
+}
+
+impl<'a, D, U> MultivariateSliceSampler<'a, D, U>
+where
+  D: Distribution<T = Vec<f64>, U = U>,
+  U: RandomVariable,
+{
+  pub fn new(distribution: &'a D, theta: &'a U) -> Self {
+    Self {
+      distribution,
+      theta,
+      width: 1.0,
+      max_stepouts: usize::MAX,
+    }
+  }
+
+  /// Initial width `w` of the bracket, shared by every coordinate.
+  pub fn with_width(mut self, width: f64) -> Self {
+    self.width = width;
+
+    self
+  }
+
+  /// Maximum number `m` of width steps taken while expanding each bracket.
+  pub fn with_max_stepouts(mut self, max_stepouts: usize) -> Self {
+    self.max_stepouts = max_stepouts;
+
+    self
+  }
+
+  pub fn width(&self) -> f64 {
+    self.width
+  }
+
+  pub fn max_stepouts(&self) -> usize {
+    self.max_stepouts
+  }
+
+  /// One sweep over all coordinates of the current value `x`, in order.
+  pub fn sample(&self, mut x: Vec<f64>, rng: &mut dyn RngCore) -> Result<Vec<f64>, Box<dyn Error>> {
+    for i in 0..x.len() {
+      let ln_p = |xi: f64| {
+        let mut x = x.clone();
+        x[i] = xi;
+        self.distribution.ln_p(&x, self.theta)
+      };
+
+      x[i] = slice_step(&ln_p, x[i], self.width, self.max_stepouts, rng)?;
+    }
+
+    Ok(x)
+  }
+}
+
+pub(crate) fn slice_step(
+  ln_p: &dyn Fn(f64) -> Result<f64, DistributionError>,
+  x: f64,
+  width: f64,
+  max_stepouts: usize,
+  rng: &mut dyn RngCore,
+) -> Result<f64, DistributionError> {
+  if width.is_nan() || width <= 0.0 {
+    return Err(DistributionError::InvalidParameters(
+      SliceSamplingError::WidthMustBePositive.into(),
+    ));
+  }
+
+  let ln_px = ln_p(x)?;
+  if ln_px == f64::NEG_INFINITY {
+    return Err(DistributionError::InvalidParameters(
+      SliceSamplingError::OutOfSlice.into(),
+    ));
+  }
+  let ln_y = ln_px + rng.gen_range(0.0f64..1.0).ln();
+
+  // stepping out, with the step budget split at random between both ends
+  let mut left = x - width * rng.gen_range(0.0..1.0);
+  let mut right = left + width;
+  let mut j = (max_stepouts as f64 * rng.gen_range(0.0..1.0)).floor() as usize;
+  let mut k = max_stepouts.saturating_sub(1).saturating_sub(j);
+
+  while j > 0 && ln_y < ln_p(left)? {
+    left -= width;
+    j -= 1;
+  }
+  while k > 0 && ln_y < ln_p(right)? {
+    right += width;
+    k -= 1;
+  }
+
+  // shrinkage towards x until the proposal lies in the slice
+  loop {
+    let x_new = rng.gen_range(left..right);
+
+    if ln_y < ln_p(x_new)? {
+      return Ok(x_new);
+    }
+
+    if x_new < x {
+      left = x_new;
+    } else {
+      right = x_new;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    mcmc::{MultivariateSliceSampler, SliceSampler},
+    Distribution, DistributionError, InstantDistribution, MultivariateNormal,
+    MultivariateNormalParams, Normal, NormalParams,
+  };
+  use opensrdk_linear_algebra::*;
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    // equal mixture of N(-2, 1) and N(2, 1)
+    let p = |x: &f64, _: &()| -> Result<f64, DistributionError> {
+      Ok(
+        0.5 * Normal.p(x, &NormalParams::new(-2.0, 1.0)?)?
+          + 0.5 * Normal.p(x, &NormalParams::new(2.0, 1.0)?)?,
+      )
+    };
+    let sample = |_: &(), _: &mut dyn RngCore| -> Result<f64, DistributionError> {
+      Err(DistributionError::Others(
+        "no direct draw from the mixture".into(),
+      ))
+    };
+    let distribution = InstantDistribution::new(&p, &sample);
+
+    // a width much smaller than the gap between the modes
+    let sampler = SliceSampler::new(&distribution, &())
+      .with_width(0.1)
+      .with_max_stepouts(1000);
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let n = 20000;
+    let mut x = -2.0;
+    let mut samples = vec![];
+    for _ in 0..n {
+      x = sampler.sample(x, &mut rng).unwrap();
+      samples.push(x);
+    }
+
+    let right = samples.iter().filter(|&&xi| 0.0 < xi).count() as f64 / n as f64;
+    let mean = samples.iter().sum::<f64>() / n as f64;
+    let var = samples.iter().map(|xi| (xi - mean).powi(2)).sum::<f64>() / n as f64;
+
+    assert!((right - 0.5).abs() < 0.1);
+    assert!(mean.abs() < 0.3);
+    assert!((var - 5.0).abs() < 0.5);
+
+    assert!(SliceSampler::new(&distribution, &())
+      .with_width(0.0)
+      .sample(0.0, &mut rng)
+      .is_err());
+  }
+
+  #[test]
+  fn multivariate() {
+    let mu = vec![1.0, -1.0];
+    let lsigma = Matrix::from(2, vec![1.0, 0.8, 0.0, 0.6]);
+    let params = MultivariateNormalParams::new(mu.clone(), lsigma.clone()).unwrap();
+    let sigma = &lsigma * lsigma.t();
+
+    let sampler = MultivariateSliceSampler::new(&MultivariateNormal, &params).with_width(0.5);
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let n = 20000;
+    let mut x = vec![0.0, 0.0];
+    let mut samples = vec![];
+    for _ in 0..n {
+      x = sampler.sample(x, &mut rng).unwrap();
+      samples.push(x.clone());
+    }
+
+    for i in 0..2 {
+      let mean_i = samples.iter().map(|xk| xk[i]).sum::<f64>() / n as f64;
+      assert!((mean_i - mu[i]).abs() < 0.1);
+
+      for j in 0..2 {
+        let cov = samples
+          .iter()
+          .map(|xk| (xk[i] - mu[i]) * (xk[j] - mu[j]))
+          .sum::<f64>()
+          / n as f64;
+        assert!((cov - sigma[(i, j)]).abs() < 0.1);
+      }
+    }
+  }
+}
@@ -0,0 +1,483 @@
+use crate::{
+  mcmc::{Covariable, MetropolisHastings},
+  Distribution, DistributionError, RandomVariable, Tempered,
+};
+use opensrdk_linear_algebra::*;
+use rand::prelude::*;
+use rayon::prelude::*;
+
+/// Sample from p(x|y) ∝ p(y|x) p(x) by sequential Monte Carlo over the tempered targets p(y|x)^β p(x), moving β from 0 to 1.
+/// Particles are reweighted at each step, resampled when the effective sample size drops below half, and rejuvenated by Metropolis-Hastings moves.
+/// Reweighting and moves run in parallel over the particles, each moved with its own seed drawn from `rng`, so the result does not depend on thread scheduling.
+pub struct SmcSampler<'a, L, P, Y, UP>
+where
+  L: Distribution<T = Y, U = Vec<f64>>,
+  P: Distribution<T = Vec<f64>, U = UP>,
+  Y: RandomVariable,
+  UP: RandomVariable,
+{
+  likelihood: &'a L,
+  y: &'a Y,
+  prior: &'a P,
+  prior_theta: &'a UP,
+  schedule: Vec<f64>,
+  n_moves: usize,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SmcSamplerError {
+  #[error("Schedule must be increasing within (0, 1] and end at 1")]
+  InvalidSchedule,
+  #[error("Number of particles must be positive")]
+  NoParticles,
+}
+
+impl<'a, L, P, Y, UP> SmcSampler<'a, L, P, Y, UP>
+where
+  L: Distribution<T = Y, U = Vec<f64>>,
+  P: Distribution<T = Vec<f64>, U = UP>,
+  Y: RandomVariable,
+  UP: RandomVariable,
+{
+  /// Uses 20 temperatures `β_t = (t / 20)^2` and 5 moves per step unless overridden.
+  pub fn new(likelihood: &'a L, y: &'a Y, prior: &'a P, prior_theta: &'a UP) -> Self {
+    Self {
+      likelihood,
+      y,
+      prior,
+      prior_theta,
+      schedule: (1..=20).map(|t| (t as f64 / 20.0).powi(2)).collect(),
+      n_moves: 5,
+    }
+  }
+
+  pub fn with_schedule(mut self, schedule: Vec<f64>) -> Result<Self, DistributionError> {
+    let increasing = schedule.windows(2).all(|w| w[0] < w[1]);
+    if schedule.is_empty()
+      || !increasing
+      || schedule[0] <= 0.0
+      || schedule[schedule.len() - 1] != 1.0
+    {
+      return Err(DistributionError::InvalidParameters(
+        SmcSamplerError::InvalidSchedule.into(),
+      ));
+    }
+    self.schedule = schedule;
+
+    Ok(self)
+  }
+
+  pub fn with_moves(mut self, n_moves: usize) -> Self {
+    self.n_moves = n_moves;
+
+    self
+  }
+
+  pub fn schedule(&self) -> &[f64] {
+    &self.schedule
+  }
+
+  pub fn n_moves(&self) -> usize {
+    self.n_moves
+  }
+
+  pub fn sample(
+    &self,
+    n_particles: usize,
+    rng: &mut dyn RngCore,
+  ) -> Result<SmcSamples, DistributionError> {
+    if n_particles == 0 {
+      return Err(DistributionError::InvalidParameters(
+        SmcSamplerError::NoParticles.into(),
+      ));
+    }
+
+    let mut x = self.prior.sample_n(n_particles, self.prior_theta, rng)?;
+    let mut ln_w = vec![-(n_particles as f64).ln(); n_particles];
+    let mut ln_evidence = 0.0;
+    let mut beta_prev = 0.0;
+    let mut steps = Vec::with_capacity(self.schedule.len());
+
+    for &beta in self.schedule.iter() {
+      let increments = x
+        .par_iter()
+        .map(|xi| Ok((beta - beta_prev) * self.likelihood.ln_p(self.y, xi)?))
+        .collect::<Result<Vec<_>, DistributionError>>()?;
+
+      ln_w
+        .iter_mut()
+        .zip(increments.iter())
+        .for_each(|(w, inc)| *w += inc);
+      // the weights entering this step are normalized, so their new sum estimates Z_t / Z_{t-1}
+      let ln_sum = ln_sum_exp(&ln_w);
+      ln_evidence += ln_sum;
+      ln_w.iter_mut().for_each(|w| *w -= ln_sum);
+
+      let ess = 1.0 / ln_w.iter().map(|w| (2.0 * w).exp()).sum::<f64>();
+      let resampled = ess < n_particles as f64 / 2.0;
+      if resampled {
+        x = resample(&x, &ln_w, rng);
+        ln_w = vec![-(n_particles as f64).ln(); n_particles];
+      }
+      steps.push(SmcStep {
+        beta,
+        ess,
+        resampled,
+      });
+
+      let target = TemperedPosterior {
+        likelihood: Tempered::new(self.likelihood.clone(), beta)?,
+        y: self.y,
+        prior: self.prior,
+        prior_theta: self.prior_theta,
+      };
+      let sampler = MetropolisHastings::new(&target, &()).with_proposal_cov(proposal_lcov(&x));
+      // one seed per particle keeps the moves independent of thread scheduling
+      let seeds = (0..n_particles).map(|_| rng.gen()).collect::<Vec<u64>>();
+      x = x
+        .into_par_iter()
+        .zip(seeds.into_par_iter())
+        .map(|(mut xi, seed)| {
+          let mut rng = StdRng::seed_from_u64(seed);
+          for _ in 0..self.n_moves {
+            xi = sampler.transition(xi, &mut rng)?.0;
+          }
+          Ok(xi)
+        })
+        .collect::<Result<Vec<_>, DistributionError>>()?;
+
+      beta_prev = beta;
+    }
+
+    Ok(SmcSamples {
+      x,
+      ln_w,
+      ln_evidence,
+      steps,
+    })
+  }
+}
+
+/// Weighted particles approximating p(x|y) together with an estimate of ln p(y).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmcSamples {
+  x: Vec<Vec<f64>>,
+  ln_w: Vec<f64>,
+  ln_evidence: f64,
+  steps: Vec<SmcStep>,
+}
+
+/// What happened to the particles at one temperature of the schedule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmcStep {
+  beta: f64,
+  ess: f64,
+  resampled: bool,
+}
+
+impl SmcStep {
+  pub fn beta(&self) -> f64 {
+    self.beta
+  }
+
+  /// Effective sample size of the reweighted particles, before resampling.
+  pub fn ess(&self) -> f64 {
+    self.ess
+  }
+
+  /// Whether `ess` dropped below half of the particles, so that they were resampled.
+  pub fn resampled(&self) -> bool {
+    self.resampled
+  }
+}
+
+impl SmcSamples {
+  pub fn x(&self) -> &[Vec<f64>] {
+    &self.x
+  }
+
+  /// Normalized log weights of `x`.
+  pub fn ln_weights(&self) -> &[f64] {
+    &self.ln_w
+  }
+
+  pub fn ln_evidence(&self) -> f64 {
+    self.ln_evidence
+  }
+
+  /// One entry per temperature of the schedule.
+  pub fn steps(&self) -> &[SmcStep] {
+    &self.steps
+  }
+
+  pub fn eject(self) -> (Vec<Vec<f64>>, Vec<f64>, f64) {
+    (self.x, self.ln_w, self.ln_evidence)
+  }
+}
+
+#[derive(Clone, Debug)]
+struct TemperedPosterior<'a, L, P, Y, UP>
+where
+  L: Distribution<T = Y, U = Vec<f64>>,
+  P: Distribution<T = Vec<f64>, U = UP>,
+  Y: RandomVariable,
+  UP: RandomVariable,
+{
+  likelihood: Tempered<L>,
+  y: &'a Y,
+  prior: &'a P,
+  prior_theta: &'a UP,
+}
+
+impl<'a, L, P, Y, UP> Distribution for TemperedPosterior<'a, L, P, Y, UP>
+where
+  L: Distribution<T = Y, U = Vec<f64>>,
+  P: Distribution<T = Vec<f64>, U = UP>,
+  Y: RandomVariable,
+  UP: RandomVariable,
+{
+  type T = Vec<f64>;
+  type U = ();
+
+  fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.ln_p(x, theta)?.exp())
+  }
+
+  fn ln_p(&self, x: &Self::T, _: &Self::U) -> Result<f64, DistributionError> {
+    Ok(self.likelihood.ln_p(self.y, x)? + self.prior.ln_p(x, self.prior_theta)?)
+  }
+
+  fn sample(&self, _: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+    self.prior.sample(self.prior_theta, rng)
+  }
+}
+
+pub(crate) fn ln_sum_exp(ln_x: &[f64]) -> f64 {
+  let max = ln_x.iter().fold(f64::NEG_INFINITY, |m, &v| m.max(v));
+  if !max.is_finite() {
+    return max;
+  }
+
+  max + ln_x.iter().map(|v| (v - max).exp()).sum::<f64>().ln()
+}
+
+/// Systematic resampling.
+fn resample(x: &[Vec<f64>], ln_w: &[f64], rng: &mut dyn RngCore) -> Vec<Vec<f64>> {
+  let n = x.len();
+  let u0 = rng.gen_range(0.0..1.0);
+
+  let mut resampled = Vec::with_capacity(n);
+  let mut i = 0;
+  let mut w_sum = ln_w[0].exp();
+  for j in 0..n {
+    let u = (j as f64 + u0) / n as f64;
+    while w_sum < u && i < n - 1 {
+      i += 1;
+      w_sum += ln_w[i].exp();
+    }
+    resampled.push(x[i].clone());
+  }
+
+  resampled
+}
+
+/// Cholesky factor of the scaled particle covariance, falling back to the identity when it is degenerate.
+fn proposal_lcov(x: &[Vec<f64>]) -> Matrix {
+  let dim = x[0].len();
+  let scale = 2.38f64.powi(2) / dim as f64;
+  let cov = match x.iter().cloned().covariance() {
+    Ok(cov) => scale * cov,
+    Err(_) => return Matrix::identity(dim),
+  };
+
+  cov.potrf().unwrap_or_else(|_| Matrix::identity(dim))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    mcmc::SmcSampler, Distribution, DistributionError, MultivariateNormal,
+    MultivariateNormalParams, Normal, NormalParams,
+  };
+  use rand::prelude::*;
+  use std::collections::HashSet;
+  use std::sync::{Arc, Mutex};
+
+  /// p(y|x) = (N(x; -3, 0.5^2) + N(x; 3, 0.5^2)) / 2 as a function of `x`
+  #[derive(Clone, Debug)]
+  struct Bimodal;
+
+  impl Distribution for Bimodal {
+    type T = ();
+    type U = Vec<f64>;
+
+    fn p(&self, _: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+      Ok(
+        0.5 * Normal.p(&theta[0], &NormalParams::new(-3.0, 0.5)?)?
+          + 0.5 * Normal.p(&theta[0], &NormalParams::new(3.0, 0.5)?)?,
+      )
+    }
+
+    fn sample(&self, _: &Self::U, _: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn it_works() {
+    let prior_theta = MultivariateNormalParams::isotropic(vec![0.0], 5.0).unwrap();
+    let sampler = SmcSampler::new(&Bimodal, &(), &MultivariateNormal, &prior_theta);
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    let samples = sampler.sample(1000, &mut rng).unwrap();
+
+    // Z = ∫ N(x; 0, 5^2) p(y|x) dx = N(3; 0, 5^2 + 0.5^2)
+    let expected = Normal
+      .ln_p(&3.0, &NormalParams::new(0.0, 25.25f64.sqrt()).unwrap())
+      .unwrap();
+    assert!((samples.ln_evidence() - expected).abs() < 0.1);
+
+    let positive = samples
+      .x()
+      .iter()
+      .zip(samples.ln_weights().iter())
+      .filter(|(xi, _)| xi[0] > 0.0)
+      .map(|(_, w)| w.exp())
+      .sum::<f64>();
+    assert!(0.3 < positive && positive < 0.7);
+  }
+
+  #[test]
+  fn seeds() {
+    let prior_theta = MultivariateNormalParams::isotropic(vec![0.0], 5.0).unwrap();
+    let sampler = SmcSampler::new(&Bimodal, &(), &MultivariateNormal, &prior_theta);
+    let run = |seed: u8| {
+      sampler
+        .sample(100, &mut StdRng::from_seed([seed; 32]))
+        .unwrap()
+    };
+
+    assert_eq!(run(1), run(1));
+    assert_ne!(run(1).x(), run(2).x());
+  }
+
+  /// Π_i N(y_i; x, 0.01^2) as a function of `x`
+  #[derive(Clone, Debug)]
+  struct Informative;
+
+  impl Distribution for Informative {
+    type T = Vec<f64>;
+    type U = Vec<f64>;
+
+    fn p(&self, y: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+      Ok(self.ln_p(y, theta)?.exp())
+    }
+
+    fn ln_p(&self, y: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+      let params = NormalParams::new(theta[0], 0.01)?;
+      y.iter().map(|yi| Normal.ln_p(yi, &params)).sum()
+    }
+
+    fn sample(&self, theta: &Self::U, _: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+      Ok(theta.clone())
+    }
+  }
+
+  #[test]
+  fn informative() {
+    let y = (0..200)
+      .map(|i| 1.0 + 0.01 * (i as f64 * 0.7).sin())
+      .collect::<Vec<_>>();
+    let prior_theta = MultivariateNormalParams::isotropic(vec![0.0], 2.0).unwrap();
+    let sampler = SmcSampler::new(&Informative, &y, &MultivariateNormal, &prior_theta);
+
+    // β_1 ln p(y|x) is in the thousands below zero for most particles, where p(y|x)^β_1 underflows to 0
+    let samples = sampler
+      .sample(500, &mut StdRng::from_seed([1; 32]))
+      .unwrap();
+    let w = samples
+      .ln_weights()
+      .iter()
+      .map(|w| w.exp())
+      .collect::<Vec<_>>();
+    assert!(w.iter().all(|w| w.is_finite()));
+    assert!((w.iter().sum::<f64>() - 1.0).abs() < 1e-10);
+    assert!(samples.ln_evidence().is_finite());
+
+    let mean = samples
+      .x()
+      .iter()
+      .zip(w.iter())
+      .map(|(xi, w)| xi[0] * w)
+      .sum::<f64>();
+    let y_mean = y.iter().sum::<f64>() / y.len() as f64;
+    assert!((mean - y_mean).abs() < 1e-3);
+
+    // the likelihood is sharp, so the first temperatures leave few effective particles
+    let steps = samples.steps();
+    assert_eq!(steps.len(), sampler.schedule().len());
+    assert!(steps[0].resampled());
+    for (step, &beta) in steps.iter().zip(sampler.schedule().iter()) {
+      assert_eq!(step.beta(), beta);
+      assert!(0.0 < step.ess() && step.ess() <= 500.0 + 1e-9);
+      assert_eq!(step.resampled(), step.ess() < 250.0);
+    }
+  }
+
+  /// N(y; x, 1) as a function of `x`, recording the worker threads that evaluate it
+  #[derive(Clone, Debug)]
+  struct Recording {
+    threads: Arc<Mutex<HashSet<usize>>>,
+  }
+
+  impl Distribution for Recording {
+    type T = f64;
+    type U = Vec<f64>;
+
+    fn p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+      if let Some(i) = rayon::current_thread_index() {
+        self.threads.lock().unwrap().insert(i);
+      }
+      Normal.p(x, &NormalParams::new(theta[0], 1.0)?)
+    }
+
+    fn sample(&self, _: &Self::U, _: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
+      Ok(0.0)
+    }
+  }
+
+  #[test]
+  fn parallel() {
+    let prior_theta = MultivariateNormalParams::isotropic(vec![0.0], 2.0).unwrap();
+    let run = |n_threads: usize| {
+      let likelihood = Recording {
+        threads: Arc::new(Mutex::new(HashSet::new())),
+      };
+      let sampler = SmcSampler::new(&likelihood, &1.0, &MultivariateNormal, &prior_theta);
+      let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(n_threads)
+        .build()
+        .unwrap();
+      let samples = pool
+        .install(|| sampler.sample(200, &mut StdRng::from_seed([1; 32])))
+        .unwrap();
+      let n_used = likelihood.threads.lock().unwrap().len();
+
+      (samples, n_used)
+    };
+
+    let (serial, _) = run(1);
+    let (parallel, n_used) = run(4);
+    assert_eq!(serial, parallel);
+    assert!(1 < n_used);
+
+    let mean = serial
+      .x()
+      .iter()
+      .zip(serial.ln_weights().iter())
+      .map(|(xi, w)| xi[0] * w.exp())
+      .sum::<f64>();
+    // the posterior of x given y = 1 is N(0.8, 0.8)
+    assert!((mean - 0.8).abs() < 0.1);
+  }
+}
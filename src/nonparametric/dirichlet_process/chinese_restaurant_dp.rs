@@ -39,7 +39,7 @@ impl Distribution for ChineseRestaurantDP {
     }
   }
 
-  fn sample(&self, theta: &Self::U, rng: &mut StdRng) -> Result<Self::T, DistributionError> {
+  fn sample(&self, theta: &Self::U, rng: &mut dyn RngCore) -> Result<Self::T, DistributionError> {
     let i = theta.i();
     let alpha = theta.alpha();
     let z = theta.z();
@@ -0,0 +1,384 @@
+use crate::{Distribution, DistributionError, RandomVariable};
+use rand::prelude::*;
+
+/// # DirichletProcessMixture
+/// ![tex](https://latex.codecogs.com/svg.latex?x_i%20\sim%20p%28x_i%7C\phi_%7Bz_i%7D%29%2C%20\phi_k%20\sim%20G_0%2C%20z%20\sim%20\mathrm%7BCRP%7D%28\alpha%29)
+///
+/// Fitted by Gibbs sampling with auxiliary components (Neal's algorithm 8), so the baseline `B` does not have to be conjugate to the likelihood `D`.
+/// Component parameters are refreshed by independence Metropolis-Hastings steps proposing from the baseline.
+#[derive(Clone, Debug)]
+pub struct DirichletProcessMixture<D, B, T, U, UB>
+where
+  D: Distribution<T = T, U = U>,
+  B: Distribution<T = U, U = UB>,
+  T: RandomVariable,
+  U: RandomVariable,
+  UB: RandomVariable,
+{
+  likelihood: D,
+  baseline: B,
+  baseline_theta: UB,
+  alpha: f64,
+  n_auxiliary: usize,
+  n_param_steps: usize,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DirichletProcessMixtureError {
+  #[error("'α' must be positive")]
+  AlphaMustBePositive,
+  #[error("Number of auxiliary components must be positive")]
+  AuxiliaryMustBePositive,
+  #[error("Observations must not be empty")]
+  Empty,
+  #[error("Observation index out of range")]
+  IndexOutOfRange,
+}
+
+impl<D, B, T, U, UB> DirichletProcessMixture<D, B, T, U, UB>
+where
+  D: Distribution<T = T, U = U>,
+  B: Distribution<T = U, U = UB>,
+  T: RandomVariable,
+  U: RandomVariable,
+  UB: RandomVariable,
+{
+  pub fn new(
+    likelihood: D,
+    baseline: B,
+    baseline_theta: UB,
+    alpha: f64,
+  ) -> Result<Self, DistributionError> {
+    if alpha <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        DirichletProcessMixtureError::AlphaMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self {
+      likelihood,
+      baseline,
+      baseline_theta,
+      alpha,
+      n_auxiliary: 3,
+      n_param_steps: 10,
+    })
+  }
+
+  pub fn with_auxiliary(mut self, n_auxiliary: usize) -> Result<Self, DistributionError> {
+    if n_auxiliary == 0 {
+      return Err(DistributionError::InvalidParameters(
+        DirichletProcessMixtureError::AuxiliaryMustBePositive.into(),
+      ));
+    }
+    self.n_auxiliary = n_auxiliary;
+
+    Ok(self)
+  }
+
+  pub fn with_param_steps(mut self, n_param_steps: usize) -> Self {
+    self.n_param_steps = n_param_steps;
+
+    self
+  }
+
+  pub fn alpha(&self) -> f64 {
+    self.alpha
+  }
+
+  pub fn n_auxiliary(&self) -> usize {
+    self.n_auxiliary
+  }
+
+  pub fn n_param_steps(&self) -> usize {
+    self.n_param_steps
+  }
+
+  /// Parameter of a new cluster, drawn from the baseline ![tex](https://latex.codecogs.com/svg.latex?G_0).
+  pub fn sample_baseline(&self, rng: &mut dyn RngCore) -> Result<U, DistributionError> {
+    self.baseline.sample(&self.baseline_theta, rng)
+  }
+
+  /// Moves observation `i` of `state` into a new cluster whose parameter is drawn from the baseline, and returns the index of that cluster.
+  pub fn open_cluster(
+    &self,
+    state: &mut DirichletProcessMixtureResult<U>,
+    i: usize,
+    rng: &mut dyn RngCore,
+  ) -> Result<usize, DistributionError> {
+    let phi = self.sample_baseline(rng)?;
+
+    state.set_z_with_param(i, phi)
+  }
+
+  /// Runs `n_iter` sweeps starting from a single cluster and returns the last state.
+  pub fn fit(
+    &self,
+    x: &[T],
+    n_iter: usize,
+    rng: &mut dyn RngCore,
+  ) -> Result<DirichletProcessMixtureResult<U>, DistributionError> {
+    if x.is_empty() {
+      return Err(DistributionError::InvalidParameters(
+        DirichletProcessMixtureError::Empty.into(),
+      ));
+    }
+
+    let mut z = vec![0usize; x.len()];
+    let mut counts = vec![x.len()];
+    let mut params = vec![self.sample_baseline(rng)?];
+
+    for _ in 0..n_iter {
+      for i in 0..x.len() {
+        let k = z[i];
+        counts[k] -= 1;
+
+        let mut auxiliary = Vec::with_capacity(self.n_auxiliary);
+        if counts[k] == 0 {
+          // a singleton keeps its parameter as one of the auxiliary components
+          auxiliary.push(params.swap_remove(k));
+          counts.swap_remove(k);
+          let moved = counts.len();
+          z.iter_mut()
+            .filter(|zj| **zj == moved)
+            .for_each(|zj| *zj = k);
+        }
+        while auxiliary.len() < self.n_auxiliary {
+          auxiliary.push(self.sample_baseline(rng)?);
+        }
+
+        let ln_alpha_m = (self.alpha / self.n_auxiliary as f64).ln();
+        let ln_prior = counts
+          .iter()
+          .map(|&n| (n as f64).ln())
+          .chain(auxiliary.iter().map(|_| ln_alpha_m))
+          .collect::<Vec<_>>();
+        let ln_likelihood = params
+          .iter()
+          .chain(auxiliary.iter())
+          .map(|phi| self.likelihood.ln_p(&x[i], phi))
+          .collect::<Result<Vec<_>, _>>()?;
+
+        let chosen = sample_ln_weights(&ln_prior, &ln_likelihood, rng);
+        if chosen < params.len() {
+          z[i] = chosen;
+          counts[chosen] += 1;
+        } else {
+          z[i] = params.len();
+          counts.push(1);
+          params.push(auxiliary.swap_remove(chosen - params.len()));
+        }
+      }
+
+      for (k, phi) in params.iter_mut().enumerate() {
+        let members = x
+          .iter()
+          .zip(z.iter())
+          .filter(|&(_, &zi)| zi == k)
+          .map(|(xi, _)| xi)
+          .collect::<Vec<_>>();
+        let ln_l = |phi: &U| -> Result<f64, DistributionError> {
+          members.iter().map(|xi| self.likelihood.ln_p(xi, phi)).sum()
+        };
+
+        let mut ln_l_current = ln_l(phi)?;
+        for _ in 0..self.n_param_steps {
+          let proposal = self.sample_baseline(rng)?;
+          let ln_l_proposal = ln_l(&proposal)?;
+
+          if rng.gen_range(0.0f64..1.0).ln() < ln_l_proposal - ln_l_current {
+            *phi = proposal;
+            ln_l_current = ln_l_proposal;
+          }
+        }
+      }
+    }
+
+    Ok(DirichletProcessMixtureResult { z, params })
+  }
+}
+
+fn sample_ln_weights(ln_prior: &[f64], ln_likelihood: &[f64], rng: &mut dyn RngCore) -> usize {
+  let ln_w = ln_prior
+    .iter()
+    .zip(ln_likelihood.iter())
+    .map(|(a, b)| a + b)
+    .collect::<Vec<_>>();
+  let max = ln_w.iter().fold(f64::NEG_INFINITY, |m, &w| m.max(w));
+
+  // when every likelihood underflows the prior weights alone decide
+  let w = if max.is_finite() {
+    ln_w.iter().map(|w| (w - max).exp()).collect::<Vec<_>>()
+  } else {
+    ln_prior.iter().map(|w| w.exp()).collect::<Vec<_>>()
+  };
+
+  let u = rng.gen_range(0.0..w.iter().sum::<f64>());
+  let mut w_sum = 0.0;
+  for (k, wk) in w.iter().enumerate() {
+    w_sum += wk;
+    if u < w_sum {
+      return k;
+    }
+  }
+
+  w.len() - 1
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirichletProcessMixtureResult<U>
+where
+  U: RandomVariable,
+{
+  z: Vec<usize>,
+  params: Vec<U>,
+}
+
+impl<U> DirichletProcessMixtureResult<U>
+where
+  U: RandomVariable,
+{
+  /// Cluster index of each observation.
+  pub fn z(&self) -> &[usize] {
+    &self.z
+  }
+
+  /// Parameters of each cluster, indexed by the values in `z`.
+  pub fn params(&self) -> &[U] {
+    &self.params
+  }
+
+  pub fn n_clusters(&self) -> usize {
+    self.params.len()
+  }
+
+  /// Moves observation `i` into a new cluster with the parameter `phi` and returns its index.
+  /// A cluster left empty is removed, and the last cluster takes its index.
+  pub fn set_z_with_param(&mut self, i: usize, phi: U) -> Result<usize, DistributionError> {
+    if self.z.len() <= i {
+      return Err(DistributionError::InvalidParameters(
+        DirichletProcessMixtureError::IndexOutOfRange.into(),
+      ));
+    }
+
+    let old = self.z[i];
+    self.params.push(phi);
+    self.z[i] = self.params.len() - 1;
+
+    if !self.z.contains(&old) {
+      self.params.swap_remove(old);
+      let moved = self.params.len();
+      self
+        .z
+        .iter_mut()
+        .filter(|zj| **zj == moved)
+        .for_each(|zj| *zj = old);
+    }
+
+    Ok(self.z[i])
+  }
+
+  pub fn eject(self) -> (Vec<usize>, Vec<U>) {
+    (self.z, self.params)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::DirichletProcessMixtureResult;
+  use crate::nonparametric::DirichletProcessMixture;
+  use crate::{ConvertableDistribution, Distribution, Normal, NormalParams};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let centers = [-6.0, 0.0, 6.0];
+    let x = centers
+      .iter()
+      .flat_map(|&mu| {
+        Normal
+          .sample_n(30, &NormalParams::new(mu, 0.5).unwrap(), &mut rng)
+          .unwrap()
+      })
+      .collect::<Vec<_>>();
+
+    let map = |mu: f64| NormalParams::new(mu, 0.5);
+    let inv = |params: &NormalParams| Ok(params.mu());
+    let baseline = Normal.convert(&map, &inv);
+
+    let dpm =
+      DirichletProcessMixture::new(Normal, baseline, NormalParams::new(0.0, 5.0).unwrap(), 1.0)
+        .unwrap();
+    let result = dpm.fit(&x, 50, &mut rng).unwrap();
+
+    let sizes = (0..result.n_clusters())
+      .map(|k| result.z().iter().filter(|&&zi| zi == k).count())
+      .collect::<Vec<_>>();
+    assert_eq!(sizes.iter().filter(|&&n| n >= 10).count(), 3);
+
+    for blob in result.z().chunks(30) {
+      let k = blob[0];
+      assert!(blob.iter().filter(|&&zi| zi == k).count() >= 27);
+      assert!(
+        (result.params()[k].mu() - centers[0]).abs() < 0.5
+          || (result.params()[k].mu() - centers[1]).abs() < 0.5
+          || (result.params()[k].mu() - centers[2]).abs() < 0.5
+      );
+    }
+  }
+
+  #[test]
+  fn open_cluster() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let map = |mu: f64| NormalParams::new(mu, 0.5);
+    let inv = |params: &NormalParams| Ok(params.mu());
+    let baseline = Normal.convert(&map, &inv);
+    let dpm =
+      DirichletProcessMixture::new(Normal, baseline, NormalParams::new(2.0, 3.0).unwrap(), 1.0)
+        .unwrap();
+
+    let x = vec![0.0, 0.1, 5.0];
+    let fitted = dpm.fit(&x, 1, &mut rng).unwrap();
+
+    let mut mu = vec![];
+    for _ in 0..5000 {
+      let mut state = fitted.clone();
+      let k = dpm.open_cluster(&mut state, 1, &mut rng).unwrap();
+
+      assert_eq!(state.z()[1], k);
+      assert_eq!(state.z().iter().filter(|&&zi| zi == k).count(), 1);
+      // empty clusters are dropped, so every index stays in use
+      assert!((0..state.n_clusters()).all(|c| state.z().contains(&c)));
+      mu.push(state.params()[k].mu());
+    }
+
+    let n = mu.len() as f64;
+    let mean = mu.iter().sum::<f64>() / n;
+    let sd = (mu.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / n).sqrt();
+    assert!((mean - 2.0).abs() < 0.15);
+    assert!((sd - 3.0).abs() < 0.15);
+  }
+
+  #[test]
+  fn set_z_with_param() {
+    let mut state = DirichletProcessMixtureResult {
+      z: vec![0, 1, 1, 2],
+      params: vec![10.0, 20.0, 30.0],
+    };
+
+    // the singleton 0 empties, so the new cluster moves into its index
+    assert_eq!(state.set_z_with_param(0, 40.0).unwrap(), 0);
+    assert_eq!(state.z(), &[0, 1, 1, 2]);
+    assert_eq!(state.params(), &[40.0, 20.0, 30.0]);
+
+    assert_eq!(state.set_z_with_param(1, 50.0).unwrap(), 3);
+    assert_eq!(state.z(), &[0, 3, 1, 2]);
+    assert_eq!(state.n_clusters(), 4);
+
+    // a bad index leaves the state as it was
+    assert!(state.set_z_with_param(4, 60.0).is_err());
+    assert_eq!(state.z(), &[0, 3, 1, 2]);
+    assert_eq!(state.n_clusters(), 4);
+  }
+}
@@ -1,3 +1,5 @@
 pub mod chinese_restaurant_dp;
+pub mod dirichlet_process_mixture;
 
 pub use chinese_restaurant_dp::*;
+pub use dirichlet_process_mixture::*;
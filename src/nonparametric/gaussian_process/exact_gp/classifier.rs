@@ -0,0 +1,218 @@
+use super::ExactGP;
+use crate::DistributionError;
+use crate::{
+  nonparametric::{cross_kernel_matrix, GaussianProcessParams, GaussianProcessRegressorError},
+  sigmoid, NormalParams, RandomVariable,
+};
+use opensrdk_kernel_method::Kernel;
+use opensrdk_linear_algebra::*;
+use std::f64::consts::PI;
+
+/// Binary GP classification with the logistic link ![tex](https://latex.codecogs.com/svg.latex?p%28y%3D1%7Cf%29%3D\sigma%28f%29), under the Laplace approximation of the latent posterior.
+///
+/// The mode ![tex](https://latex.codecogs.com/svg.latex?\hat{\mathbf{f}}) is found by Newton iterations, and ![tex](https://latex.codecogs.com/svg.latex?p%28\mathbf{f}%7C\mathbf{y}%29\approx\mathcal{N}%28\hat{\mathbf{f}}%2C%28K_{XX}%5E%7B-1%7D%2BW%29%5E%7B-1%7D%29) with ![tex](https://latex.codecogs.com/svg.latex?W%3D\mathrm{diag}%28\pi%281-\pi%29%29).
+#[derive(Clone, Debug)]
+pub struct LaplaceGPClassifier<K, T>
+where
+  K: Kernel<T>,
+  T: RandomVariable,
+{
+  gp: ExactGP<K, T>,
+  x: Vec<T>,
+  theta: Vec<f64>,
+  f: Vec<f64>,
+  y_pi: Vec<f64>,
+  sqrt_w: Vec<f64>,
+  lb: Matrix,
+}
+
+impl<K, T> LaplaceGPClassifier<K, T>
+where
+  K: Kernel<T>,
+  T: RandomVariable,
+{
+  const MAX_ITER: usize = 100;
+  const TOLERANCE: f64 = 1e-8;
+
+  pub fn new(
+    gp: ExactGP<K, T>,
+    y: &[bool],
+    params: GaussianProcessParams<T>,
+  ) -> Result<Self, DistributionError> {
+    let (x, theta) = params.eject();
+
+    let n = y.len();
+    if n == 0 {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::Empty.into(),
+      ));
+    }
+    if n != x.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::DimensionMismatch.into(),
+      ));
+    }
+
+    let t = y
+      .iter()
+      .map(|&yi| if yi { 1.0 } else { 0.0 })
+      .collect::<Vec<_>>();
+    let kxx = gp.kxx(&theta, &x)?;
+
+    let mut f = vec![0.0; n];
+    for _ in 0..Self::MAX_ITER {
+      let (y_pi, sqrt_w) = gradient_and_sqrt_w(&t, &f);
+      let lb = b(&kxx, &sqrt_w).potrf()?;
+
+      // a = b - W^½ B^{-1} W^½ K b with b = W f + ∇ ln p(y|f), and then f = K a
+      let b = (0..n)
+        .map(|i| sqrt_w[i].powi(2) * f[i] + y_pi[i])
+        .collect::<Vec<_>>();
+      let kb = (&kxx * b.clone().col_mat()).vec();
+      let w_kb = (0..n).map(|i| sqrt_w[i] * kb[i]).collect::<Vec<_>>();
+      let b_inv_w_kb = lb.potrs(w_kb.col_mat())?.vec();
+      let a = (0..n)
+        .map(|i| b[i] - sqrt_w[i] * b_inv_w_kb[i])
+        .collect::<Vec<_>>();
+      let f_new = (&kxx * a.col_mat()).vec();
+
+      let delta = f_new
+        .iter()
+        .zip(f.iter())
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f64::max);
+      f = f_new;
+
+      if delta < Self::TOLERANCE {
+        break;
+      }
+    }
+
+    let (y_pi, sqrt_w) = gradient_and_sqrt_w(&t, &f);
+    let lb = b(&kxx, &sqrt_w).potrf()?;
+
+    Ok(Self {
+      gp,
+      x,
+      theta,
+      f,
+      y_pi,
+      sqrt_w,
+      lb,
+    })
+  }
+
+  pub fn n(&self) -> usize {
+    self.x.len()
+  }
+
+  /// Posterior mode of the latent function values at the training inputs.
+  pub fn mode(&self) -> &[f64] {
+    &self.f
+  }
+
+  /// Approximate posterior of the latent value ![tex](https://latex.codecogs.com/svg.latex?f_*).
+  pub fn predict_f(&self, xs: &T) -> Result<NormalParams, DistributionError> {
+    let xs = vec![xs.clone()];
+    let kxxs = cross_kernel_matrix(&self.gp.kernel, &self.theta, &self.x, &xs)?.vec();
+    let kxsxs = self.gp.kxx(&self.theta, &xs)?[(0, 0)];
+
+    let mean = kxxs
+      .iter()
+      .zip(self.y_pi.iter())
+      .map(|(k, g)| k * g)
+      .sum::<f64>();
+    let w_kxxs = kxxs
+      .iter()
+      .zip(self.sqrt_w.iter())
+      .map(|(k, w)| k * w)
+      .collect::<Vec<_>>();
+    let b_inv_w_kxxs = self.lb.potrs(w_kxxs.clone().col_mat())?.vec();
+    let variance = kxsxs
+      - w_kxxs
+        .iter()
+        .zip(b_inv_w_kxxs.iter())
+        .map(|(a, b)| a * b)
+        .sum::<f64>();
+
+    NormalParams::new(mean, variance.max(0.0).sqrt())
+  }
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?p%28y_*%3D1%7C\mathbf{y}%29\approx\sigma%28\mu_*/\sqrt{1%2B\pi\sigma_*%5E2/8}%29), the probit approximation of the averaged logistic.
+  pub fn predict(&self, xs: &T) -> Result<f64, DistributionError> {
+    let f = self.predict_f(xs)?;
+
+    Ok(sigmoid(
+      f.mu() / (1.0 + PI * f.sigma().powi(2) / 8.0).sqrt(),
+    ))
+  }
+}
+
+/// `(t - π, W^½)` at the latent values `f`.
+fn gradient_and_sqrt_w(t: &[f64], f: &[f64]) -> (Vec<f64>, Vec<f64>) {
+  let pi = f.iter().map(|fi| sigmoid(*fi)).collect::<Vec<_>>();
+
+  (
+    t.iter().zip(pi.iter()).map(|(ti, pi)| ti - pi).collect(),
+    pi.iter().map(|pi| (pi * (1.0 - pi)).sqrt()).collect(),
+  )
+}
+
+/// ![tex](https://latex.codecogs.com/svg.latex?B%3DI%2BW%5E%7B1/2%7DKW%5E%7B1/2%7D), which stays well conditioned even when `K` is singular.
+fn b(kxx: &Matrix, sqrt_w: &[f64]) -> Matrix {
+  let n = sqrt_w.len();
+  let mut b = Matrix::identity(n);
+  for c in 0..n {
+    for r in 0..n {
+      b[(r, c)] += sqrt_w[r] * kxx[(r, c)] * sqrt_w[c];
+    }
+  }
+
+  b
+}
+
+#[cfg(test)]
+mod tests {
+  use super::LaplaceGPClassifier;
+  use crate::nonparametric::{ExactGP, GaussianProcess, GaussianProcessParams};
+  use opensrdk_kernel_method::RBF;
+
+  #[test]
+  fn it_works() {
+    let x = (0..25)
+      .map(|i| vec![-3.0 + i as f64 * 0.25])
+      .collect::<Vec<_>>();
+    // positive right of the origin, with one label flipped on each side near the boundary
+    let y = x
+      .iter()
+      .map(|xi| (xi[0] > 0.0) != (xi[0] == -0.5 || xi[0] == 0.75))
+      .collect::<Vec<_>>();
+    let classifier = LaplaceGPClassifier::new(
+      ExactGP::new(RBF),
+      &y,
+      GaussianProcessParams::new(x, vec![10.0, 2.0]),
+    )
+    .unwrap();
+
+    let p = (-20..=20)
+      .map(|i| classifier.predict(&vec![i as f64 * 0.1]).unwrap())
+      .collect::<Vec<_>>();
+    assert!(p[0] < 0.2 && 0.8 < p[40]);
+    assert!(p.iter().all(|&pi| 0.0 < pi && pi < 1.0));
+
+    let crossing = (0..40).find(|&i| p[i] < 0.5 && 0.5 <= p[i + 1]).unwrap();
+    assert!((crossing as f64 * 0.1 - 2.0).abs() < 0.25);
+
+    // far from the data the prediction reverts to the prior
+    let far = classifier.predict_f(&vec![20.0]).unwrap();
+    assert!(far.mu().abs() < 1e-6);
+    assert!((classifier.predict(&vec![20.0]).unwrap() - 0.5).abs() < 1e-6);
+
+    assert!(LaplaceGPClassifier::new(
+      ExactGP::new(RBF),
+      &y[..3],
+      GaussianProcessParams::new(vec![vec![0.0]], vec![1.0, 1.0]),
+    )
+    .is_err());
+  }
+}
@@ -21,10 +21,16 @@ where
     return Ok(normal.p(x, &params)?);
   }
 
+  fn ln_p(&self, x: &Self::T, theta: &Self::U) -> Result<f64, DistributionError> {
+    let params = self.handle_temporal_params(theta)?;
+
+    MultivariateNormal.ln_p(x, &params)
+  }
+
   fn sample(
     &self,
     theta: &Self::U,
-    rng: &mut rand::prelude::StdRng,
+    rng: &mut dyn rand::RngCore,
   ) -> Result<Self::T, DistributionError> {
     let normal = MultivariateNormal;
     let params = self.handle_temporal_params(theta)?;
@@ -32,3 +38,35 @@ where
     return Ok(normal.sample(&params, rng)?);
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::nonparametric::{ExactGP, GaussianProcess, GaussianProcessParams};
+  use crate::Distribution;
+  use opensrdk_kernel_method::RBF;
+  use rand::prelude::*;
+  use rand_distr::StandardNormal;
+
+  #[test]
+  fn unwhiten() {
+    let gp = ExactGP::new(RBF).with_sigma(0.1);
+    let x = (0..5).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x, vec![1.0, 1.0]);
+
+    let expected = gp.sample(&params, &mut StdRng::from_seed([1; 32])).unwrap();
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let eta = (0..5)
+      .map(|_| rng.sample(StandardNormal))
+      .collect::<Vec<f64>>();
+    let mvn = gp.handle_temporal_params(&params).unwrap();
+    let f = mvn.unwhiten(&eta).unwrap();
+
+    for (a, b) in expected.iter().zip(f.iter()) {
+      assert!((a - b).abs() < 1e-12);
+    }
+    for (a, b) in eta.iter().zip(mvn.whiten(&f).unwrap().iter()) {
+      assert!((a - b).abs() < 1e-10);
+    }
+  }
+}
@@ -1,8 +1,15 @@
-use super::{super::kernel_matrix::kernel_matrix, ExactGP, GaussianProcessParams};
+use super::{
+  super::{
+    kernel_matrix::{kernel_matrix, packed_kernel_matrix},
+    GaussianProcessError, PackedCholesky,
+  },
+  ExactGP, GaussianProcessParams,
+};
 use crate::DistributionError;
 use crate::MultivariateNormalParams;
 use crate::RandomVariable;
 use opensrdk_kernel_method::*;
+use opensrdk_linear_algebra::*;
 pub use rayon::prelude::*;
 
 impl<K, T> ExactGP<K, T>
@@ -10,15 +17,119 @@ where
   K: Kernel<T>,
   T: RandomVariable,
 {
+  /// ![tex](https://latex.codecogs.com/svg.latex?K_%7BXX%7D%2B\sigma^2I)
+  pub(crate) fn kxx(&self, theta: &[f64], x: &[T]) -> Result<Matrix, DistributionError> {
+    let kxx = kernel_matrix(&self.kernel, theta, x, x)?;
+
+    if self.sigma == 0.0 {
+      return Ok(kxx);
+    }
+
+    Ok(kxx + self.sigma.powi(2) * Matrix::identity(x.len()))
+  }
+
+  /// Lower Cholesky factor of `kxx`, by `potrf_jittered`.
+  pub(crate) fn lkxx(&self, theta: &[f64], x: &[T]) -> Result<Matrix, DistributionError> {
+    let kxx = self.kxx(theta, x)?;
+    let scale = kxx.tr() / x.len() as f64;
+
+    potrf_jittered(kxx, scale)
+  }
+
+  /// `lkxx` in packed storage, factorized in place.
+  /// The kernel matrix is rebuilt for each jitter retry instead of being kept as a copy.
+  pub(crate) fn lkxx_packed(
+    &self,
+    theta: &[f64],
+    x: &[T],
+  ) -> Result<PackedCholesky, DistributionError> {
+    const RETRIES: usize = 5;
+
+    let kxx = |jitter: f64| {
+      let mut kxx = packed_kernel_matrix(&self.kernel, theta, x)?;
+      kxx.add_diag(self.sigma.powi(2) + jitter);
+      Ok::<_, DistributionError>(kxx)
+    };
+
+    let kxx0 = kxx(0.0)?;
+    let n = kxx0.n();
+    let tr = (0..n).map(|i| kxx0[(i, i)]).sum::<f64>();
+    if let Ok(lkxx) = kxx0.pptrf() {
+      return Ok(lkxx);
+    }
+
+    let mut epsilon = 1e-9 * tr / n as f64;
+    for _ in 0..RETRIES {
+      if let Ok(lkxx) = kxx(epsilon)?.pptrf() {
+        return Ok(lkxx);
+      }
+      epsilon *= 10.0;
+    }
+
+    Err(DistributionError::InvalidParameters(
+      GaussianProcessError::NotPositiveDefinite.into(),
+    ))
+  }
+
   pub(crate) fn handle_temporal_params(
     &self,
     params: &GaussianProcessParams<T>,
   ) -> Result<MultivariateNormalParams, DistributionError> {
-    let kxx = kernel_matrix(&self.kernel, &params.theta, &params.x, &params.x)?;
-    let lkxx = kxx.potrf()?;
+    let lkxx = self.lkxx(&params.theta, &params.x)?;
+
+    let mu = match &self.mean_fn {
+      Some(mean_fn) => params.x.iter().map(|xi| mean_fn.value(xi)).collect(),
+      None => vec![0.0; params.x.len()],
+    };
 
-    let params = MultivariateNormalParams::new(vec![0.0; params.x.len()], lkxx)?;
+    let params = MultivariateNormalParams::new(mu, lkxx)?;
 
     return Ok(params);
   }
 }
+
+/// `potrf` of a kernel matrix `k`.
+/// If the factorization fails, an increasing jitter starting at `1e-9 * scale` is added to the diagonal.
+/// `scale` is the typical variance of the entries, e.g. `tr(K) / n`; a Schur complement needs the scale of the kernel it came from.
+pub(crate) fn potrf_jittered(k: Matrix, scale: f64) -> Result<Matrix, DistributionError> {
+  const RETRIES: usize = 5;
+
+  let n = k.rows();
+  if let Ok(l) = k.clone().potrf() {
+    return Ok(l);
+  }
+
+  let mut epsilon = 1e-9 * scale;
+  for _ in 0..RETRIES {
+    if let Ok(l) = (k.clone() + epsilon * Matrix::identity(n)).potrf() {
+      return Ok(l);
+    }
+    epsilon *= 10.0;
+  }
+
+  Err(DistributionError::InvalidParameters(
+    GaussianProcessError::NotPositiveDefinite.into(),
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::nonparametric::{kernel_matrix, ExactGP, GaussianProcess};
+  use opensrdk_kernel_method::RBF;
+
+  #[test]
+  fn lkxx() {
+    let theta = vec![1.0, 1.0];
+    let x = vec![vec![0.0], vec![0.5], vec![0.5], vec![1.0], vec![1.0]];
+
+    // duplicated inputs make the kernel matrix singular
+    let kxx = kernel_matrix(&RBF, &theta, &x, &x).unwrap();
+    assert!(kxx.potrf().is_err());
+
+    let lkxx = ExactGP::new(RBF).lkxx(&theta, &x).unwrap();
+    assert!((0..x.len()).all(|i| lkxx[(i, i)] > 0.0));
+
+    let packed = ExactGP::new(RBF).lkxx_packed(&theta, &x).unwrap();
+    assert!((0..x.len()).all(|i| packed.get(i, i) > 0.0));
+  }
+}
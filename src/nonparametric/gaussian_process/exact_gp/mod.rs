@@ -1,3 +1,4 @@
+pub mod classifier;
 pub mod distribution;
 pub mod internal;
 pub mod regressor;
@@ -7,7 +8,7 @@ use crate::DistributionError;
 use crate::{opensrdk_linear_algebra::*, RandomVariable};
 use opensrdk_kernel_method::*;
 pub use rayon::prelude::*;
-use std::{fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
 /// Gaussian Process without approximation for scalability.
 ///
@@ -28,9 +29,68 @@ where
   T: RandomVariable,
 {
   kernel: K,
+  mean_fn: Option<MeanFn<T>>,
+  sigma: f64,
+  packed: bool,
   phantom: PhantomData<T>,
 }
 
+/// Mean function ![tex](https://latex.codecogs.com/svg.latex?m%28\mathbf{x}%29) of a GP.
+#[derive(Clone)]
+pub struct MeanFn<T>(Arc<dyn Fn(&T) -> f64 + Send + Sync>);
+
+impl<T> MeanFn<T> {
+  pub fn value(&self, x: &T) -> f64 {
+    (self.0)(x)
+  }
+}
+
+impl<T> Debug for MeanFn<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("MeanFn")
+  }
+}
+
+impl<K, T> ExactGP<K, T>
+where
+  K: Kernel<T>,
+  T: RandomVariable,
+{
+  /// Uses ![tex](https://latex.codecogs.com/svg.latex?\mathcal{GP}%28m%2CK_{XX}%29) instead of the constant empirical mean.
+  pub fn with_mean_fn(mut self, mean_fn: Box<dyn Fn(&T) -> f64 + Send + Sync>) -> Self {
+    self.mean_fn = Some(MeanFn(Arc::from(mean_fn)));
+
+    self
+  }
+
+  pub fn mean_fn(&self) -> Option<&MeanFn<T>> {
+    self.mean_fn.as_ref()
+  }
+
+  /// Adds the observation noise ![tex](https://latex.codecogs.com/svg.latex?\sigma^2I) to the kernel matrix.
+  pub fn with_sigma(mut self, sigma: f64) -> Self {
+    self.sigma = sigma;
+
+    self
+  }
+
+  pub fn sigma(&self) -> f64 {
+    self.sigma
+  }
+
+  /// Makes `ExactGPRegressor` build and factorize the kernel matrix in `SymmetricPackedMatrix` storage, which needs about half the memory of the dense path.
+  /// `posterior_after` extends the factor in the same storage.
+  pub fn with_packed_storage(mut self) -> Self {
+    self.packed = true;
+
+    self
+  }
+
+  pub fn packed_storage(&self) -> bool {
+    self.packed
+  }
+}
+
 impl<K, T> GaussianProcess<K, T> for ExactGP<K, T>
 where
   K: Kernel<T>,
@@ -39,6 +99,9 @@ where
   fn new(kernel: K) -> Self {
     Self {
       kernel,
+      mean_fn: None,
+      sigma: 0.0,
+      packed: false,
       phantom: PhantomData,
     }
   }
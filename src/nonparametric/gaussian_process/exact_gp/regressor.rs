@@ -1,16 +1,19 @@
 use super::super::{ey::ey, ey::y_ey};
+use super::internal::potrf_jittered;
 use super::ExactGP;
 use crate::DistributionError;
 use crate::{
-  nonparametric::{kernel_matrix, regressor::GaussianProcessRegressor},
+  nonparametric::{cross_kernel_matrix, kernel_matrix, regressor::GaussianProcessRegressor},
   RandomVariable,
 };
 use crate::{
-  nonparametric::{GaussianProcessParams, GaussianProcessRegressorError},
-  MultivariateNormalParams,
+  nonparametric::{GaussianProcessParams, GaussianProcessRegressorError, PackedCholesky},
+  Distribution, MultivariateNormal, MultivariateNormalParams, NormalParams,
 };
 use opensrdk_kernel_method::Kernel;
 use opensrdk_linear_algebra::*;
+use rand::prelude::*;
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub struct ExactGPRegressor<K, T>
@@ -20,12 +23,37 @@ where
 {
   gp: ExactGP<K, T>,
   ey: f64,
-  x: Vec<T>,
+  x: Arc<Vec<T>>,
+  y_ey: Vec<f64>,
   theta: Vec<f64>,
-  lkxx: Matrix,
+  lkxx: KernelFactor,
   kxx_inv_y: Matrix,
 }
 
+/// Cholesky factor of the kernel matrix in the storage chosen by `ExactGP::with_packed_storage`.
+#[derive(Clone, Debug)]
+enum KernelFactor {
+  Dense(Matrix),
+  Packed(PackedCholesky),
+}
+
+impl KernelFactor {
+  fn potrs(&self, b: Matrix) -> Result<Matrix, DistributionError> {
+    match self {
+      KernelFactor::Dense(l) => Ok(l.potrs(b)?),
+      KernelFactor::Packed(l) => l.pptrs(b),
+    }
+  }
+
+  /// `L[(r, c)]` for `c <= r`.
+  fn get(&self, r: usize, c: usize) -> f64 {
+    match self {
+      KernelFactor::Dense(l) => l[(r, c)],
+      KernelFactor::Packed(l) => l.get(r, c),
+    }
+  }
+}
+
 impl<K, T> GaussianProcessRegressor<ExactGP<K, T>, K, T> for ExactGPRegressor<K, T>
 where
   K: Kernel<T>,
@@ -36,7 +64,8 @@ where
     y: &[f64],
     params: GaussianProcessParams<T>,
   ) -> Result<Self, DistributionError> {
-    let (x, theta) = params.eject();
+    // hyperparameter searches fit many regressors on the same inputs through `GaussianProcessParams::with_theta`
+    let (x, theta) = params.eject_shared();
 
     let n = y.len();
     if n == 0 {
@@ -51,17 +80,20 @@ where
       ));
     }
 
-    let ey = ey(y);
-    let y_ey = &y_ey(y, ey);
+    let (ey, y_ey) = residuals(&gp, &x, y);
 
-    let kxx = kernel_matrix(&gp.kernel, &theta, &x, &x)?;
-    let lkxx = kxx.potrf()?;
+    let lkxx = if gp.packed_storage() {
+      KernelFactor::Packed(gp.lkxx_packed(&theta, &x)?)
+    } else {
+      KernelFactor::Dense(gp.lkxx(&theta, &x)?)
+    };
     let kxx_inv_y = lkxx.potrs(y_ey.to_vec().col_mat())?.vec().col_mat();
 
     Ok(Self {
       gp,
       ey,
       x,
+      y_ey,
       theta,
       lkxx,
       kxx_inv_y,
@@ -77,13 +109,617 @@ where
   }
 
   fn predict_multivariate(&self, xs: &[T]) -> Result<MultivariateNormalParams, DistributionError> {
-    let kxxs = kernel_matrix(&self.gp.kernel, &self.theta, &self.x, xs)?;
+    let kxxs = cross_kernel_matrix(&self.gp.kernel, &self.theta, &self.x, xs)?;
     let kxx_inv_kxxs_t = self.lkxx.potrs(kxxs.clone())?;
     let kxsxs = kernel_matrix(&self.gp.kernel, &self.theta, xs, xs)?;
 
-    let mean = self.ey + (&self.kxx_inv_y.t() * &kxxs).t();
-    let covariance = kxsxs - (kxx_inv_kxxs_t * &kxxs).t();
+    let mut mean = self.ey + (&self.kxx_inv_y.t() * &kxxs).t();
+    if let Some(mean_fn) = self.gp.mean_fn() {
+      for (i, xsi) in xs.iter().enumerate() {
+        mean[(i, 0)] += mean_fn.value(xsi);
+      }
+    }
+    // the posterior covariance is singular at the training inputs and on dense inputs
+    let scale = kxsxs.tr() / xs.len() as f64;
+    let covariance = kxsxs - kxxs.t() * kxx_inv_kxxs_t;
+
+    MultivariateNormalParams::new(mean.vec(), potrf_jittered(covariance, scale)?)
+  }
+}
+
+impl<K, T> ExactGPRegressor<K, T>
+where
+  K: Kernel<T>,
+  T: RandomVariable,
+{
+  /// Predictive distribution of the latent function value ![tex](https://latex.codecogs.com/svg.latex?f_*).
+  pub fn predict_f(&self, xs: &T) -> Result<NormalParams, DistributionError> {
+    self.predict(xs)
+  }
+
+  /// Predictive distribution of a noisy observation ![tex](https://latex.codecogs.com/svg.latex?y_*%3Df_*%2B\varepsilon), which adds the noise variance ![tex](https://latex.codecogs.com/svg.latex?\sigma^2).
+  pub fn predict_y(&self, xs: &T) -> Result<NormalParams, DistributionError> {
+    let f = self.predict(xs)?;
+
+    NormalParams::new(f.mu(), (f.sigma().powi(2) + self.gp.sigma.powi(2)).sqrt())
+  }
+
+  /// The regressor refitted with the extra observations `y_new` at `x_new`, without refactorizing the kernel matrix.
+  /// The Cholesky factor is extended blockwise, which costs ![tex](https://latex.codecogs.com/svg.latex?O%28N%5E2M%2BM%5E3%29) for `M` new points instead of ![tex](https://latex.codecogs.com/svg.latex?O%28%28N%2BM%29%5E3%29).
+  pub fn posterior_after(&self, x_new: &[T], y_new: &[f64]) -> Result<Self, DistributionError> {
+    let n = self.x.len();
+    let m = x_new.len();
+    if m == 0 {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::Empty.into(),
+      ));
+    }
+    if m != y_new.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::DimensionMismatch.into(),
+      ));
+    }
+
+    // L = [L11, 0; L21, L22] with L21^T = L11^{-1} K12 and L22 L22^T = K22 - L21 L21^T
+    let l11 = &self.lkxx;
+    let k12 = cross_kernel_matrix(&self.gp.kernel, &self.theta, &self.x, x_new)?;
+    let mut l21t = Matrix::new(n, m);
+    for c in 0..m {
+      for r in 0..n {
+        let l_x = (0..r).map(|i| l11.get(r, i) * l21t[(i, c)]).sum::<f64>();
+        l21t[(r, c)] = (k12[(r, c)] - l_x) / l11.get(r, r);
+      }
+    }
+    let k22 = self.gp.kxx(&self.theta, x_new)?;
+    let scale = k22.tr() / m as f64;
+    let l22 = potrf_jittered(k22 - l21t.t() * &l21t, scale)?;
+
+    let l = |r: usize, c: usize| match (r < n, c < n) {
+      (true, _) => l11.get(r, c),
+      (false, true) => l21t[(c, r - n)],
+      (false, false) => l22[(r - n, c - n)],
+    };
+    let l = &l;
+    // the extended factor keeps the storage of the fit
+    let lkxx = match l11 {
+      KernelFactor::Dense(_) => {
+        let mut lkxx = Matrix::new(n + m, n + m);
+        for c in 0..n + m {
+          for r in c..n + m {
+            lkxx[(r, c)] = l(r, c);
+          }
+        }
+        KernelFactor::Dense(lkxx)
+      }
+      KernelFactor::Packed(_) => KernelFactor::Packed(PackedCholesky::from(
+        n + m,
+        (0..n + m)
+          .flat_map(|c| (c..n + m).map(move |r| l(r, c)))
+          .collect(),
+      )?),
+    };
+
+    let y = self
+      .y_ey
+      .iter()
+      .zip(self.x.iter())
+      .map(|(yi, xi)| match self.gp.mean_fn() {
+        Some(mean_fn) => yi + mean_fn.value(xi),
+        None => yi + self.ey,
+      })
+      .chain(y_new.iter().copied())
+      .collect::<Vec<_>>();
+    let x = self
+      .x
+      .iter()
+      .chain(x_new.iter())
+      .cloned()
+      .collect::<Vec<_>>();
+
+    let (ey, y_ey) = residuals(&self.gp, &x, &y);
+    let kxx_inv_y = lkxx.potrs(y_ey.clone().col_mat())?;
+
+    Ok(Self {
+      gp: self.gp.clone(),
+      ey,
+      x: Arc::new(x),
+      y_ey,
+      theta: self.theta.clone(),
+      lkxx,
+      kxx_inv_y,
+    })
+  }
+
+  /// Caches ![tex](https://latex.codecogs.com/svg.latex?K_%7B*x%7D) for the fixed test inputs `xs`, so that predictive means for new targets at the same training inputs cost ![tex](https://latex.codecogs.com/svg.latex?O%28N%5E2%2BNM%29).
+  pub fn predictor_cache(&self, xs: &[T]) -> Result<PredictorCache<K, T>, DistributionError> {
+    let kxxs = cross_kernel_matrix(&self.gp.kernel, &self.theta, &self.x, xs)?;
+    let mean_fn_xs = self
+      .gp
+      .mean_fn()
+      .map(|mean_fn| xs.iter().map(|xsi| mean_fn.value(xsi)).collect());
+
+    let mut cache = PredictorCache {
+      gp: self.gp.clone(),
+      x: self.x.clone(),
+      lkxx: self.lkxx.clone(),
+      kxxs,
+      mean_fn_xs,
+      kxx_inv_y: self.kxx_inv_y.clone(),
+      mean: vec![],
+    };
+    cache.update_mean(self.ey);
+
+    Ok(cache)
+  }
+
+  /// Draws the posterior function values at `xs` jointly.
+  pub fn sample_posterior(
+    &self,
+    xs: &[T],
+    rng: &mut dyn RngCore,
+  ) -> Result<Vec<f64>, DistributionError> {
+    let params = self.predict_multivariate(xs)?;
+
+    MultivariateNormal.sample(&params, rng)
+  }
+}
+
+impl<K> ExactGPRegressor<K, Vec<f64>>
+where
+  K: Kernel<Vec<f64>>,
+{
+  /// Gradient of the predictive mean ![tex](https://latex.codecogs.com/svg.latex?\nabla_%7Bx_*%7D\mu_*%3D\sum_i\nabla_%7Bx_*%7Dk%28x_*%2Cx_i%29\left%5BK%5E%7B-1%7D%28y-\bar%7By%7D%29\right%5D_i) with respect to the test input.
+  /// The kernel crate only differentiates with respect to the kernel parameters, so the kernel and the mean function are differentiated by central differences.
+  pub fn predict_mean_gradient(&self, xs: &[f64]) -> Result<Vec<f64>, DistributionError> {
+    let alpha = self.kxx_inv_y.clone().vec();
+    // the weighted sum of kernel values, plus the mean function when there is one
+    let mean = |x: &Vec<f64>| {
+      let k = self
+        .x
+        .iter()
+        .zip(alpha.iter())
+        .map(|(xi, ai)| Ok(self.gp.kernel.value(&self.theta, x, xi)? * ai))
+        .sum::<Result<f64, DistributionError>>()?;
+      let m = self.gp.mean_fn().map_or(0.0, |mean_fn| mean_fn.value(x));
+
+      Ok::<_, DistributionError>(k + m)
+    };
+
+    (0..xs.len())
+      .map(|j| {
+        let h = 1e-6 * xs[j].abs().max(1.0);
+        let mut forward = xs.to_vec();
+        forward[j] += h;
+        let mut backward = xs.to_vec();
+        backward[j] -= h;
+
+        Ok((mean(&forward)? - mean(&backward)?) / (2.0 * h))
+      })
+      .collect()
+  }
+}
+
+/// Predictive means at fixed test inputs, made by `ExactGPRegressor::predictor_cache`.
+#[derive(Clone, Debug)]
+pub struct PredictorCache<K, T>
+where
+  K: Kernel<T>,
+  T: RandomVariable,
+{
+  gp: ExactGP<K, T>,
+  x: Arc<Vec<T>>,
+  lkxx: KernelFactor,
+  kxxs: Matrix,
+  mean_fn_xs: Option<Vec<f64>>,
+  kxx_inv_y: Matrix,
+  mean: Vec<f64>,
+}
+
+impl<K, T> PredictorCache<K, T>
+where
+  K: Kernel<T>,
+  T: RandomVariable,
+{
+  /// Replaces the targets at the training inputs, re-solving only ![tex](https://latex.codecogs.com/svg.latex?K_%7Bxx%7D%5E%7B-1%7D%28y-\bar%7By%7D%29) with the cached factor.
+  pub fn update_targets(&mut self, y: &[f64]) -> Result<(), DistributionError> {
+    if y.len() != self.x.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::DimensionMismatch.into(),
+      ));
+    }
+
+    let (ey, y_ey) = residuals(&self.gp, &self.x, y);
+    self.kxx_inv_y = self.lkxx.potrs(y_ey.col_mat())?;
+    self.update_mean(ey);
+
+    Ok(())
+  }
+
+  /// Predictive means at the cached test inputs.
+  pub fn mean(&self) -> &[f64] {
+    &self.mean
+  }
+
+  fn update_mean(&mut self, ey: f64) {
+    let mut mean = (ey + (&self.kxx_inv_y.t() * &self.kxxs).t()).vec();
+    if let Some(mean_fn_xs) = self.mean_fn_xs.as_ref() {
+      for (mi, m) in mean.iter_mut().zip(mean_fn_xs.iter()) {
+        *mi += m;
+      }
+    }
+
+    self.mean = mean;
+  }
+}
+
+/// `(ey, y - ey)`, or `(0, y - m(x))` with a mean function, which the GP fits instead of `y`.
+fn residuals<K, T>(gp: &ExactGP<K, T>, x: &[T], y: &[f64]) -> (f64, Vec<f64>)
+where
+  K: Kernel<T>,
+  T: RandomVariable,
+{
+  match gp.mean_fn() {
+    Some(mean_fn) => (
+      0.0,
+      y.iter()
+        .zip(x.iter())
+        .map(|(yi, xi)| yi - mean_fn.value(xi))
+        .collect(),
+    ),
+    None => {
+      let ey = ey(y);
+      (ey, y_ey(y, ey))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{ExactGPRegressor, KernelFactor};
+  use crate::nonparametric::{
+    kernel_matrix, ExactGP, GaussianProcess, GaussianProcessParams, GaussianProcessRegressor,
+  };
+  use crate::opensrdk_linear_algebra::*;
+  use crate::{Distribution, MultivariateNormal, MultivariateNormalParams};
+  use opensrdk_kernel_method::RBF;
+  use rand::prelude::*;
+  use std::sync::Arc;
+
+  #[test]
+  fn sample_posterior() {
+    let x = (0..6).map(|i| vec![i as f64]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let regressor = ExactGPRegressor::new(
+      ExactGP::new(RBF),
+      &y,
+      GaussianProcessParams::new(x.clone(), vec![1.0, 1.0]),
+    )
+    .unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    // 2.05 is next to a training input, 10.0 is far from all of them
+    let xs = vec![vec![2.05], vec![10.0]];
+    let samples = (0..1000)
+      .map(|_| regressor.sample_posterior(&xs, &mut rng).unwrap())
+      .collect::<Vec<_>>();
+
+    let sd = |i: usize| {
+      let mean = samples.iter().map(|s| s[i]).sum::<f64>() / samples.len() as f64;
+
+      (samples.iter().map(|s| (s[i] - mean).powi(2)).sum::<f64>() / samples.len() as f64).sqrt()
+    };
+
+    assert!(sd(0) < 0.05);
+    assert!(sd(1) > 0.8);
+
+    // without noise the posterior interpolates the training targets
+    let sample = regressor.sample_posterior(&x, &mut rng).unwrap();
+    assert!(sample
+      .iter()
+      .zip(y.iter())
+      .all(|(s, y)| (s - y).abs() < 1e-3));
+    let (lower, _, upper) = regressor.predict_interval(&vec![2.0], 0.95).unwrap();
+    assert!(upper - lower < 1e-3);
+    assert!(regressor
+      .predictive_entropy(&vec![2.0])
+      .unwrap()
+      .is_finite());
+    assert!(regressor.max_entropy_point(&x).is_ok());
+  }
+
+  #[test]
+  fn sample_posterior_dense() {
+    let x = (0..6).map(|i| vec![i as f64]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let regressor = ExactGPRegressor::new(
+      ExactGP::new(RBF).with_sigma(1.0),
+      &y,
+      GaussianProcessParams::new(x, vec![1.0, 1.0]),
+    )
+    .unwrap();
+
+    let xs = (0..200).map(|i| vec![i as f64 * 0.025]).collect::<Vec<_>>();
+    let sample = regressor
+      .sample_posterior(&xs, &mut StdRng::from_seed([1; 32]))
+      .unwrap();
+    assert_eq!(sample.len(), 200);
+    assert!(sample.iter().all(|s| s.is_finite()));
+  }
+
+  #[test]
+  fn with_mean_fn() {
+    let f = |x: f64| 2.0 * x + x.sin();
+    let x = (0..20).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| f(xi[0])).collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x, vec![1.0, 1.0]);
+
+    let constant = ExactGPRegressor::new(ExactGP::new(RBF), &y, params.clone()).unwrap();
+    let linear = ExactGPRegressor::new(
+      ExactGP::new(RBF).with_mean_fn(Box::new(|xi: &Vec<f64>| 2.0 * xi[0])),
+      &y,
+      params,
+    )
+    .unwrap();
+
+    // away from the data the constant-mean model falls back to the empirical mean
+    let xs = vec![15.0];
+    let constant_error = (constant.predict(&xs).unwrap().mu() - f(xs[0])).abs();
+    let linear_error = (linear.predict(&xs).unwrap().mu() - f(xs[0])).abs();
+
+    assert!(linear_error < 1.0);
+    assert!(linear_error < constant_error);
+  }
+
+  #[test]
+  fn predict_interval() {
+    let theta = vec![1.0, 1.0];
+    let x = (0..40).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let n = x.len();
+    let l = (kernel_matrix(&RBF, &theta, &x, &x).unwrap() + 1e-10 * Matrix::identity(n))
+      .potrf()
+      .unwrap();
+    let prior = MultivariateNormalParams::new(vec![0.0; n], l).unwrap();
+    let mut rng = StdRng::from_seed([1; 32]);
+
+    // train on every 4th input and hold out the rest
+    let mut covered = 0;
+    let mut held_out = 0;
+    for _ in 0..20 {
+      let f = MultivariateNormal.sample(&prior, &mut rng).unwrap();
+      let (train_x, train_y): (Vec<_>, Vec<_>) = x
+        .iter()
+        .zip(f.iter())
+        .step_by(4)
+        .map(|(xi, &fi)| (xi.clone(), fi))
+        .unzip();
+      let regressor = ExactGPRegressor::new(
+        ExactGP::new(RBF),
+        &train_y,
+        GaussianProcessParams::new(train_x, theta.clone()),
+      )
+      .unwrap();
+
+      for i in (0..n).filter(|i| i % 4 != 0) {
+        let (lower, mean, upper) = regressor.predict_interval(&x[i], 0.95).unwrap();
+        assert!(lower < mean && mean < upper);
+
+        if lower < f[i] && f[i] < upper {
+          covered += 1;
+        }
+        held_out += 1;
+      }
+    }
+
+    let coverage = covered as f64 / held_out as f64;
+    assert!((coverage - 0.95).abs() < 0.04);
+
+    let regressor = ExactGPRegressor::new(
+      ExactGP::new(RBF),
+      &[0.0, 1.0],
+      GaussianProcessParams::new(vec![vec![0.0], vec![1.0]], theta),
+    )
+    .unwrap();
+    assert!(regressor.predict_interval(&vec![0.5], 1.0).is_err());
+    assert!(regressor.predict_interval(&vec![0.5], 0.0).is_err());
+  }
+
+  #[test]
+  fn predict_y() {
+    let sigma = 0.3;
+    let x = (0..10).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let regressor = ExactGPRegressor::new(
+      ExactGP::new(RBF).with_sigma(sigma),
+      &y,
+      GaussianProcessParams::new(x, vec![1.0, 1.0]),
+    )
+    .unwrap();
+
+    for xs in [vec![0.5], vec![2.25], vec![10.0]].iter() {
+      let f = regressor.predict_f(xs).unwrap();
+      let y = regressor.predict_y(xs).unwrap();
+
+      assert_eq!(f.mu(), y.mu());
+      assert!((y.sigma().powi(2) - f.sigma().powi(2) - sigma.powi(2)).abs() < 1e-12);
+    }
+
+    // noise keeps the latent variance above 0 on the training inputs
+    assert!(regressor.predict_f(&vec![0.5]).unwrap().sigma() > 0.01);
+  }
+
+  #[test]
+  fn posterior_after() {
+    let x = (0..12).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x.clone(), vec![1.0, 1.0]);
+    let gp = ExactGP::new(RBF).with_sigma(0.1);
+
+    let batched = ExactGPRegressor::new(gp.clone(), &y, params).unwrap();
+    let first = ExactGPRegressor::new(
+      gp,
+      &y[..8],
+      GaussianProcessParams::new(x[..8].to_vec(), vec![1.0, 1.0]),
+    )
+    .unwrap();
+    let incremental = first
+      .posterior_after(&x[8..10], &y[8..10])
+      .unwrap()
+      .posterior_after(&x[10..], &y[10..])
+      .unwrap();
+
+    assert_eq!(incremental.n(), batched.n());
+    assert!((incremental.ey() - batched.ey()).abs() < 1e-12);
+    for xs in [vec![0.25], vec![4.2], vec![5.7], vec![9.0]].iter() {
+      let a = batched.predict(xs).unwrap();
+      let b = incremental.predict(xs).unwrap();
+
+      assert!((a.mu() - b.mu()).abs() < 1e-8);
+      assert!((a.sigma() - b.sigma()).abs() < 1e-8);
+    }
+
+    assert!(first.posterior_after(&x[8..10], &y[8..9]).is_err());
+    assert!(first.posterior_after(&[], &[]).is_err());
+  }
+
+  #[test]
+  fn posterior_after_duplicated_input() {
+    let x = (0..6).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let regressor = ExactGPRegressor::new(
+      ExactGP::new(RBF),
+      &y,
+      GaussianProcessParams::new(x.clone(), vec![1.0, 1.0]),
+    )
+    .unwrap();
+
+    // without noise the Schur complement of a repeated input is singular
+    let extended = regressor.posterior_after(&x[2..3], &y[2..3]).unwrap();
+    assert_eq!(extended.n(), 7);
+
+    // repeating a noiseless observation leaves the posterior unchanged
+    let xs = vec![1.25];
+    let a = regressor.predict(&xs).unwrap();
+    let b = extended.predict(&xs).unwrap();
+    assert!((a.mu() - b.mu()).abs() < 1e-3);
+  }
+
+  #[test]
+  fn packed_storage() {
+    let x = (0..15).map(|i| vec![i as f64 * 0.4]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].cos()).collect::<Vec<_>>();
+    let gp = ExactGP::new(RBF).with_sigma(0.1);
+
+    let dense = ExactGPRegressor::new(
+      gp.clone(),
+      &y,
+      GaussianProcessParams::new(x.clone(), vec![1.0, 1.0]),
+    )
+    .unwrap();
+    let packed = ExactGPRegressor::new(
+      gp.with_packed_storage(),
+      &y,
+      GaussianProcessParams::new(x.clone(), vec![1.0, 1.0]),
+    )
+    .unwrap();
+
+    let xs = vec![vec![0.3], vec![2.9], vec![7.0]];
+    let a = dense.predict_multivariate(&xs).unwrap();
+    let b = packed.predict_multivariate(&xs).unwrap();
+    assert!(a.approx_eq(&b, 1e-10));
+
+    let new_x = vec![vec![6.1]];
+    let a = dense.posterior_after(&new_x, &[0.2]).unwrap();
+    let b = packed.posterior_after(&new_x, &[0.2]).unwrap();
+    assert!(matches!(b.lkxx, KernelFactor::Packed(_)));
+    assert!(a
+      .predict_multivariate(&xs)
+      .unwrap()
+      .approx_eq(&b.predict_multivariate(&xs).unwrap(), 1e-10));
+  }
+
+  #[test]
+  fn shared_inputs() {
+    let x = (0..10).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x, vec![1.0, 1.0]);
+    let gp = ExactGP::new(RBF).with_sigma(0.1);
+
+    // every candidate of a hyperparameter search fits on the same inputs without copying them
+    for &l in [0.5, 1.0, 2.0].iter() {
+      let regressor =
+        ExactGPRegressor::new(gp.clone(), &y, params.with_theta(vec![1.0, l]).unwrap()).unwrap();
+      assert!(Arc::ptr_eq(&regressor.x, &params.x));
+      assert!(Arc::ptr_eq(
+        &regressor.predictor_cache(&[vec![0.3]]).unwrap().x,
+        &params.x
+      ));
+    }
+  }
+
+  #[test]
+  fn predict_mean_gradient() {
+    let x = (0..20).map(|i| vec![i as f64 * 0.3]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let regressor = ExactGPRegressor::new(
+      ExactGP::new(RBF).with_sigma(0.01),
+      &y,
+      GaussianProcessParams::new(x, vec![1.0, 1.0]),
+    )
+    .unwrap();
+
+    let h = 1e-4;
+    for &xs in [0.45, 2.0, 3.7, 5.1].iter() {
+      let gradient = regressor.predict_mean_gradient(&[xs]).unwrap();
+      let numerical = (regressor.predict(&vec![xs + h]).unwrap().mu()
+        - regressor.predict(&vec![xs - h]).unwrap().mu())
+        / (2.0 * h);
+
+      assert_eq!(gradient.len(), 1);
+      assert!((gradient[0] - numerical).abs() < 1e-5);
+      // the mean follows sin closely inside the data
+      assert!((gradient[0] - xs.cos()).abs() < 0.05);
+    }
+  }
+
+  #[test]
+  fn predictor_cache() {
+    let x = (0..300).map(|i| vec![i as f64 * 0.05]).collect::<Vec<_>>();
+    let xs = (0..50)
+      .map(|i| vec![i as f64 * 0.3 + 0.01])
+      .collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x.clone(), vec![1.0, 1.0]);
+    let gp = ExactGP::new(RBF).with_sigma(0.1);
+    let frames = (0..5)
+      .map(|t| {
+        x.iter()
+          .map(|xi| (xi[0] + t as f64 * 0.5).sin())
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+
+    let regressor = ExactGPRegressor::new(gp.clone(), &frames[0], params.clone()).unwrap();
+    let mut cache = regressor.predictor_cache(&xs).unwrap();
+
+    for y in frames.iter() {
+      cache.update_targets(y).unwrap();
+      let fresh = ExactGPRegressor::new(gp.clone(), y, params.clone()).unwrap();
+
+      // the cached factor re-solves the same weights as a fresh fit
+      for (a, b) in cache
+        .kxx_inv_y
+        .clone()
+        .vec()
+        .iter()
+        .zip(fresh.kxx_inv_y.clone().vec().iter())
+      {
+        assert!((a - b).abs() < 1e-8 * b.abs().max(1.0));
+      }
+      let mean = fresh.predict_multivariate(&xs).unwrap();
+      for (ai, bi) in cache.mean().iter().zip(mean.mu().iter()) {
+        assert!((ai - bi).abs() < 1e-8);
+      }
+    }
 
-    MultivariateNormalParams::new(mean.vec(), covariance.potrf()?)
+    assert!(cache.update_targets(&frames[0][..10]).is_err());
   }
 }
@@ -1,4 +1,6 @@
+use super::{GaussianProcessError, SymmetricPackedMatrix};
 use crate::opensrdk_linear_algebra::*;
+use crate::DistributionError;
 use opensrdk_kernel_method::*;
 use rayon::prelude::*;
 
@@ -27,3 +29,79 @@ where
 
   Ok(k)
 }
+
+/// ![tex](https://latex.codecogs.com/svg.latex?K_%7BXX%7D) evaluated only on its lower triangle, straight into packed storage.
+pub fn packed_kernel_matrix<T>(
+  kernel: &impl Kernel<T>,
+  params: &[f64],
+  x: &[T],
+) -> Result<SymmetricPackedMatrix, DistributionError>
+where
+  T: Value,
+{
+  let n = x.len();
+
+  let elems = (0..n)
+    .into_par_iter()
+    .flat_map(|c| {
+      (c..n)
+        .into_par_iter()
+        .map(move |r| kernel.value(params, &x[r], &x[c]))
+    })
+    .collect::<Result<Vec<_>, KernelError>>()?;
+
+  SymmetricPackedMatrix::from(n, elems)
+}
+
+/// `|a| x |b|` cross-covariance matrix ![tex](https://latex.codecogs.com/svg.latex?K_%7BAB%7D) between two input sets.
+pub fn cross_kernel_matrix<T>(
+  kernel: &impl Kernel<T>,
+  params: &[f64],
+  a: &[T],
+  b: &[T],
+) -> Result<Matrix, DistributionError>
+where
+  T: Value,
+{
+  if a.is_empty() || b.is_empty() {
+    return Err(DistributionError::InvalidParameters(
+      GaussianProcessError::Empty.into(),
+    ));
+  }
+
+  Ok(kernel_matrix(kernel, params, a, b)?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{cross_kernel_matrix, kernel_matrix, packed_kernel_matrix};
+  use opensrdk_kernel_method::RBF;
+
+  #[test]
+  fn it_works() {
+    let theta = vec![1.0, 2.0];
+    let x = (0..5).map(|i| vec![i as f64 * 0.3]).collect::<Vec<_>>();
+    let xs = vec![vec![0.1], vec![2.0]];
+
+    assert_eq!(
+      cross_kernel_matrix(&RBF, &theta, &x, &x).unwrap(),
+      kernel_matrix(&RBF, &theta, &x, &x).unwrap()
+    );
+
+    let kxxs = cross_kernel_matrix(&RBF, &theta, &x, &xs).unwrap();
+    assert_eq!((kxxs.rows(), kxxs.cols()), (5, 2));
+    assert_eq!(
+      cross_kernel_matrix(&RBF, &theta, &xs, &x).unwrap(),
+      kxxs.t()
+    );
+
+    assert!(cross_kernel_matrix(&RBF, &theta, &x, &[]).is_err());
+
+    let packed = packed_kernel_matrix(&RBF, &theta, &x).unwrap();
+    assert_eq!(packed.elems().len(), 15);
+    assert_eq!(
+      packed.to_dense(),
+      kernel_matrix(&RBF, &theta, &x, &x).unwrap()
+    );
+  }
+}
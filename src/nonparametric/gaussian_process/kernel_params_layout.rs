@@ -0,0 +1,109 @@
+use super::GaussianProcessError;
+use crate::DistributionError;
+use opensrdk_kernel_method::*;
+use std::ops::Range;
+
+/// Offsets of each component kernel's parameters in the concatenated `theta` of a composed kernel.
+///
+/// `Kernel` sums and products concatenate `lhs` then `rhs` parameters, so components are pushed in the order they appear in the expression.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KernelParamsLayout {
+  ranges: Vec<(String, Range<usize>)>,
+}
+
+impl KernelParamsLayout {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn push<T>(mut self, name: &str, kernel: &impl Kernel<T>) -> Self
+  where
+    T: Value,
+  {
+    let start = self.params_len();
+    self
+      .ranges
+      .push((name.to_string(), start..start + kernel.params_len()));
+
+    self
+  }
+
+  pub fn params_len(&self) -> usize {
+    self.ranges.last().map_or(0, |(_, range)| range.end)
+  }
+
+  pub fn ranges(&self) -> &[(String, Range<usize>)] {
+    &self.ranges
+  }
+
+  pub fn range(&self, name: &str) -> Option<Range<usize>> {
+    self
+      .ranges
+      .iter()
+      .find(|(n, _)| n == name)
+      .map(|(_, range)| range.clone())
+  }
+
+  pub fn get<'a>(&self, theta: &'a [f64], name: &str) -> Option<&'a [f64]> {
+    theta.get(self.range(name)?)
+  }
+
+  /// Overwrites the parameters of the component `name` in `theta`.
+  pub fn set(
+    &self,
+    theta: &mut [f64],
+    name: &str,
+    params: &[f64],
+  ) -> Result<(), DistributionError> {
+    let range = match self.range(name) {
+      Some(range) => range,
+      None => {
+        return Err(DistributionError::InvalidParameters(
+          GaussianProcessError::DimensionMismatch.into(),
+        ))
+      }
+    };
+    if theta.len() != self.params_len() || range.len() != params.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::DimensionMismatch.into(),
+      ));
+    }
+
+    theta[range].copy_from_slice(params);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::nonparametric::KernelParamsLayout;
+  use opensrdk_kernel_method::*;
+
+  #[test]
+  fn it_works() {
+    let kernel = RBF + Periodic;
+    let layout = KernelParamsLayout::new()
+      .push("rbf", &RBF)
+      .push("periodic", &Periodic);
+
+    assert_eq!(layout.params_len(), kernel.params_len());
+    assert_eq!(layout.range("rbf"), Some(0..RBF.params_len()));
+    assert_eq!(
+      layout.range("periodic"),
+      Some(RBF.params_len()..RBF.params_len() + Periodic.params_len())
+    );
+
+    let mut theta = vec![1.0; kernel.params_len()];
+    let periodic = vec![2.0; Periodic.params_len()];
+    layout.set(&mut theta, "periodic", &periodic).unwrap();
+
+    assert_eq!(layout.get(&theta, "periodic"), Some(periodic.as_slice()));
+    assert_eq!(
+      layout.get(&theta, "rbf"),
+      Some(vec![1.0; RBF.params_len()].as_slice())
+    );
+    assert!(layout.get(&theta, "linear").is_none());
+    assert!(layout.set(&mut theta, "rbf", &[1.0]).is_err());
+  }
+}
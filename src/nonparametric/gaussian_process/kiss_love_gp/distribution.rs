@@ -37,7 +37,7 @@ where
   fn sample(
     &self,
     theta: &Self::U,
-    rng: &mut rand::prelude::StdRng,
+    rng: &mut dyn rand::RngCore,
   ) -> Result<Self::T, DistributionError> {
     let n = theta.x.len();
     let z = (0..n)
@@ -11,6 +11,28 @@ where
   T: RandomVariable + Convolutable,
 {
   pub(crate) fn wx_u(x: &Vec<T>) -> Result<(Vec<SparseMatrix>, Grid), DistributionError> {
+    let points = Self::grid_points(x)?;
+
+    Self::wx_u_with_grid(x, &points)
+  }
+
+  pub(crate) fn grid_points(x: &[T]) -> Result<Vec<usize>, DistributionError> {
+    let n = x.len();
+    if n == 0 {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::Empty.into(),
+      ));
+    }
+
+    let data_len = x[0].data_len();
+
+    Ok(vec![(n / 2usize.pow(data_len as u32)).max(2); data_len])
+  }
+
+  pub(crate) fn wx_u_with_grid(
+    x: &Vec<T>,
+    points: &[usize],
+  ) -> Result<(Vec<SparseMatrix>, Grid), DistributionError> {
     let n = x.len();
     if n == 0 {
       return Err(DistributionError::InvalidParameters(
@@ -26,27 +48,36 @@ where
       ));
     }
 
-    let points = vec![(n / 2usize.pow(data_len as u32)).max(2); data_len];
-    let u = Grid::from(&x, &points)?;
+    if points.len() != data_len {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::DimensionMismatch.into(),
+      ));
+    }
+
+    let u = Grid::from(&x, points)?;
     let wx = u.interpolation_weight(&x)?;
 
     return Ok((wx, u));
   }
 
+  /// `(wxt * kuu * wx + sigma^2 * I) * v`
   pub(crate) fn wxt_kuu_wx_vec_mul(
     v: &Vec<f64>,
     wx: &Vec<SparseMatrix>,
     kuu: &KroneckerMatrices,
+    sigma: f64,
   ) -> Result<Vec<f64>, DistributionError> {
+    let sigma_v = v.iter().map(|vi| sigma.powi(2) * vi).collect::<Vec<_>>();
+
     wx.iter()
       .map(|wxpi| {
         let v = v.clone().col_mat();
-        let wx_v = wxpi * &v;
+        let wx_v = sparse_mul(wxpi, &v);
         let kuu_wx_v = kuu.vec_mul(wx_v.vec())?.col_mat();
-        let wxt_kuu_wx_v = wxpi.t() * kuu_wx_v;
+        let wxt_kuu_wx_v = sparse_t_mul(wxpi, &kuu_wx_v);
         Ok(wxt_kuu_wx_v.vec())
       })
-      .try_fold(vec![0.0; v.len()], |a, b: Result<_, DistributionError>| {
+      .try_fold(sigma_v, |a, b: Result<_, DistributionError>| {
         Ok((a.col_mat() + b?.col_mat()).vec())
       })
   }
@@ -65,6 +96,7 @@ where
   pub(crate) fn det_kxx(
     kuu: &KroneckerMatrices,
     wx: &Vec<SparseMatrix>,
+    sigma: f64,
   ) -> Result<f64, DistributionError> {
     let m = wx[0].rows;
     let n = wx[0].cols;
@@ -103,7 +135,7 @@ where
 
     let det = lambda
       .par_iter()
-      .map(|lmd| ((n as f64 / m as f64) * lmd.re))
+      .map(|lmd| (n as f64 / m as f64) * lmd.re + sigma.powi(2))
       .product::<f64>();
 
     Ok(det)
@@ -119,3 +151,112 @@ where
     ))
   }
 }
+
+/// `lhs * rhs`.
+/// The `SparseMatrix` products of opensrdk-linear-algebra index the dense side as row-major, so they are done here.
+pub(crate) fn sparse_mul(lhs: &SparseMatrix, rhs: &Matrix) -> Matrix {
+  let mut new_matrix = Matrix::new(lhs.rows, rhs.cols());
+
+  for (&(i, j), &value) in lhs.elems.iter() {
+    for k in 0..rhs.cols() {
+      new_matrix[(i, k)] += value * rhs[(j, k)];
+    }
+  }
+
+  new_matrix
+}
+
+/// `lhs^T * rhs`.
+pub(crate) fn sparse_t_mul(lhs: &SparseMatrix, rhs: &Matrix) -> Matrix {
+  let mut new_matrix = Matrix::new(lhs.cols, rhs.cols());
+
+  for (&(i, j), &value) in lhs.elems.iter() {
+    for k in 0..rhs.cols() {
+      new_matrix[(j, k)] += value * rhs[(i, k)];
+    }
+  }
+
+  new_matrix
+}
+
+/// The first standard basis vector of length `n`, the probe `Matrix::sytrd_k` also starts from.
+pub(crate) fn unit(n: usize) -> Vec<f64> {
+  let mut e = vec![0.0; n];
+  e[0] = 1.0;
+
+  e
+}
+
+/// `k` Lanczos steps `A ≈ Q T Q^T` from `probe`, reorthogonalizing every new vector twice against all previous ones.
+/// `Matrix::sytrd_k` skips the reorthogonalization, and on an ill-conditioned kernel matrix its `Q` loses orthogonality so that `Q T Q^T` overestimates `A`.
+/// Once the Krylov subspace is invariant, the steps restart from a unit vector orthogonal to it with a zero off-diagonal, so that `n` steps factorize `A` exactly.
+pub(crate) fn lanczos<E>(
+  n: usize,
+  k: usize,
+  vec_mul: &dyn Fn(Vec<f64>) -> Result<Vec<f64>, E>,
+  probe: &[f64],
+) -> Result<(Matrix, SymmetricTridiagonalMatrix), DistributionError>
+where
+  DistributionError: From<E>,
+{
+  let dot = |a: &[f64], b: &[f64]| a.iter().zip(b.iter()).map(|(ai, bi)| ai * bi).sum::<f64>();
+  // one pass loses orthogonality once the residual is small against `A`
+  let orthogonalize = |w: &mut Vec<f64>, v: &[Vec<f64>]| {
+    for _ in 0..2 {
+      for vj in v.iter() {
+        let wj = dot(w, vj);
+        w.iter_mut()
+          .zip(vj.iter())
+          .for_each(|(wi, vji)| *wi -= wj * vji);
+      }
+    }
+  };
+
+  let norm = dot(probe, probe).sqrt();
+  let mut v = vec![probe.iter().map(|pi| pi / norm).collect::<Vec<_>>()];
+  let mut d = vec![];
+  let mut e = vec![];
+
+  for i in 0..k.min(n) {
+    let mut w = vec_mul(v[i].clone())?;
+    d.push(dot(&w, &v[i]));
+
+    if i + 1 == k.min(n) {
+      break;
+    }
+
+    orthogonalize(&mut w, &v);
+
+    let scale = d.iter().fold(0.0f64, |a, di| a.max(di.abs()));
+    let beta = dot(&w, &w).sqrt();
+    if beta > 1e-8 * scale {
+      e.push(beta);
+      v.push(w.into_iter().map(|wi| wi / beta).collect());
+      continue;
+    }
+
+    // the Krylov subspace is invariant, so the next vector is the unit vector with the largest part orthogonal to it
+    let mut restart = (0.0, vec![]);
+    for j in 0..n {
+      let mut u = vec![0.0; n];
+      u[j] = 1.0;
+      orthogonalize(&mut u, &v);
+      let norm = dot(&u, &u).sqrt();
+      if norm > restart.0 {
+        restart = (norm, u);
+      }
+      if restart.0 > 0.5 {
+        break;
+      }
+    }
+
+    let (norm, u) = restart;
+    e.push(0.0);
+    v.push(u.into_iter().map(|ui| ui / norm).collect());
+  }
+
+  Ok((
+    Matrix::from(n, v.concat()),
+    SymmetricTridiagonalMatrix::new(d, e)?,
+  ))
+}
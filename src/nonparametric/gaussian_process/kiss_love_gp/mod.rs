@@ -6,6 +6,7 @@ pub mod regressor;
 use super::{GaussianProcess, GaussianProcessError, GaussianProcessParams};
 use crate::DistributionError;
 use crate::{opensrdk_linear_algebra::*, RandomVariable};
+use internal::sparse_t_mul;
 use opensrdk_kernel_method::{Convolutable, Convolutional, Kernel};
 use rayon::prelude::*;
 use std::marker::PhantomData;
@@ -24,6 +25,7 @@ where
   T: RandomVariable + Convolutable,
 {
   kernel: Convolutional<K>,
+  sigma: f64,
   phantom: PhantomData<T>,
 }
 
@@ -35,6 +37,17 @@ where
   pub fn from(kernel: K) -> Self {
     Self::new(Convolutional::new(kernel))
   }
+
+  /// Adds the observation noise ![tex](https://latex.codecogs.com/svg.latex?\sigma^2I) to the kernel matrix.
+  pub fn with_sigma(mut self, sigma: f64) -> Self {
+    self.sigma = sigma;
+
+    self
+  }
+
+  pub fn sigma(&self) -> f64 {
+    self.sigma
+  }
 }
 
 impl<K, T> GaussianProcess<Convolutional<K>, T> for KissLoveGP<K, T>
@@ -60,6 +73,7 @@ where
   fn new(kernel: Convolutional<K>) -> Self {
     Self {
       kernel,
+      sigma: 0.0,
       phantom: PhantomData::<T>,
     }
   }
@@ -74,14 +88,15 @@ where
     let (wx, kuu) = self.handle_temporal_params(params)?;
 
     let det = if with_det_lkxx {
-      Some(Self::det_kxx(&kuu, &wx)?.sqrt())
+      Some(Self::det_kxx(&kuu, &wx, self.sigma)?.sqrt())
     } else {
       None
     };
-    let wxt_kuu_wx_vec_mul = move |v: Vec<f64>| match Self::wxt_kuu_wx_vec_mul(&v, &wx, &kuu) {
-      Ok(v) => Ok(v),
-      Err(e) => Err(e.into()),
-    };
+    let wxt_kuu_wx_vec_mul =
+      move |v: Vec<f64>| match Self::wxt_kuu_wx_vec_mul(&v, &wx, &kuu, self.sigma) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(e.into()),
+      };
 
     let wxt_kuu_wx_inv_vec = Matrix::posv_cgm(&wxt_kuu_wx_vec_mul, vec, K)?;
 
@@ -101,12 +116,48 @@ where
 
     let wxt_lkuu_vec = wx
       .par_iter()
-      .map(|wxpi| {
-        let wxpit = wxpi.t();
-        wxpit * &lkuu_vec
-      })
+      .map(|wxpi| sparse_t_mul(wxpi, &lkuu_vec))
       .reduce(|| Matrix::new(n, 1), |a, b| a + b);
 
     Ok(wxt_lkuu_vec.vec())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::nonparametric::{GaussianProcess, GaussianProcessParams, KissLoveGP};
+  use opensrdk_kernel_method::RBF;
+  use rand::prelude::*;
+  use rand_distr::StandardNormal;
+  use std::f64::consts::PI;
+
+  #[test]
+  fn with_sigma() {
+    let n = 32;
+    let mut rng = StdRng::from_seed([1; 32]);
+    let x = (0..n)
+      .map(|i| vec![2.0 * PI * i as f64 / (n - 1) as f64])
+      .collect::<Vec<_>>();
+    let y = x
+      .iter()
+      .map(|xi| xi[0].sin() + 0.3 * rng.sample::<f64, _>(StandardNormal))
+      .collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x, vec![1.0, 1.0]);
+
+    // the posterior mean on the training inputs is y - sigma^2 * kxx^{-1} * y,
+    // summed squared differences between neighbours measure how rough it is
+    let roughness = |sigma: f64| {
+      let gp = KissLoveGP::from(RBF).with_sigma(sigma);
+      let (kxx_inv_y, _) = gp.kxx_inv_vec(y.clone(), &params, false).unwrap();
+      let mu = y
+        .iter()
+        .zip(kxx_inv_y.iter())
+        .map(|(yi, kxx_inv_yi)| yi - sigma.powi(2) * kxx_inv_yi)
+        .collect::<Vec<_>>();
+
+      mu.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum::<f64>()
+    };
+
+    assert!(roughness(1.0) < roughness(0.1));
+  }
+}
@@ -1,14 +1,18 @@
 use super::{
   super::{ey::ey, ey::y_ey},
   grid::Grid,
+  internal::{lanczos, sparse_mul, sparse_t_mul, unit},
   KissLoveGP,
 };
 use crate::DistributionError;
+use crate::{nonparametric::GaussianProcessParams, MultivariateNormalParams, NormalParams};
 use crate::{
-  nonparametric::regressor::{GaussianProcessRegressor, GaussianProcessRegressorError},
+  nonparametric::{
+    exact_gp::internal::potrf_jittered,
+    regressor::{GaussianProcessRegressor, GaussianProcessRegressorError},
+  },
   RandomVariable,
 };
-use crate::{nonparametric::GaussianProcessParams, MultivariateNormalParams};
 use opensrdk_kernel_method::{Convolutable, Convolutional, Kernel};
 use opensrdk_linear_algebra::*;
 use std::marker::PhantomData;
@@ -26,16 +30,17 @@ where
   phantom: PhantomData<(K, T)>,
 }
 
-impl<K, T> GaussianProcessRegressor<KissLoveGP<K, T>, Convolutional<K>, T>
-  for KissLoveGPregressor<K, T>
+impl<K, T> KissLoveGPregressor<K, T>
 where
   K: Kernel<Vec<f64>>,
   T: RandomVariable + Convolutable,
 {
-  fn new(
+  /// Same as `new`, but with `points[d]` inducing points along the `d`-th input dimension instead of the default grid.
+  pub fn with_grid(
     gp: KissLoveGP<K, T>,
     y: &[f64],
     params: GaussianProcessParams<T>,
+    points: &[usize],
   ) -> Result<Self, DistributionError> {
     let (x, theta) = params.eject();
 
@@ -52,7 +57,7 @@ where
       ));
     }
 
-    let (wx, u) = KissLoveGP::<K, T>::wx_u(&x)?;
+    let (wx, u) = KissLoveGP::<K, T>::wx_u_with_grid(&x, points)?;
     let wx = &wx;
     let kuu = &u.kuu(&gp.kernel, &theta)?;
 
@@ -63,14 +68,15 @@ where
 
     let ey = ey(y);
     let y_ey = &y_ey(y, ey);
+    let sigma = gp.sigma;
 
     let wxt_kuu_wx_vec_mul =
-      move |v: Vec<f64>| match KissLoveGP::<K, T>::wxt_kuu_wx_vec_mul(&v, wx, kuu) {
+      move |v: Vec<f64>| match KissLoveGP::<K, T>::wxt_kuu_wx_vec_mul(&v, wx, kuu, sigma) {
         Ok(v) => Ok(v),
         Err(e) => Err(e.into()),
       };
 
-    let wxt_kuu_wx_inv_y = Matrix::posv_cgm(&wxt_kuu_wx_vec_mul, y_ey.to_vec(), K)?.col_mat();
+    let wxt_kuu_wx_inv_y = Matrix::posv_cgm(&wxt_kuu_wx_vec_mul, y_ey.to_vec(), k)?.col_mat();
 
     let a = (0..p)
       .into_iter()
@@ -78,7 +84,9 @@ where
         let wxpi = &wx[pi];
 
         // a = kuu * wx * (wxt * kuu *wx)^{-1} * y
-        let a = kuu.vec_mul((wxpi * &wxt_kuu_wx_inv_y).vec())?.col_mat();
+        let a = kuu
+          .vec_mul(sparse_mul(wxpi, &wxt_kuu_wx_inv_y).vec())?
+          .col_mat();
         Ok(a)
       })
       .collect::<Result<Vec<_>, DistributionError>>()?;
@@ -86,7 +94,7 @@ where
     // (wxt * kuu * wx)^{-1} = q * t^{-1} * qt
     // q: n×k
     // t: k×k
-    let (q, t) = Matrix::sytrd_k(n, k, &wxt_kuu_wx_vec_mul, None)?;
+    let (q, t) = lanczos(n, k, &wxt_kuu_wx_vec_mul, &unit(n))?;
 
     // t = l * d * lt
     let (l, d) = t.pttrf()?;
@@ -94,44 +102,41 @@ where
     let s = (0..p)
       .into_iter()
       .map(|pi| {
-        let wxpi = &wx[pi];
-        // let wxpit = &wxpi.t();
-
-        let wx_q = wxpi * &q;
+        let wx_q = sparse_mul(&wx[pi], &q);
 
         // rt = kuu * wx * q
         // rt: m,
-        let kuu_wx_r_cols = (0..k)
+        let kuu_wx_r_cols = (0..q.cols())
           .into_iter()
           .map(|ki| &wx_q[ki])
           .map(|wx_q_col| Ok(kuu.vec_mul(wx_q_col.to_owned())?))
           .collect::<Result<Vec<_>, DistributionError>>()?;
-        // r = qt * wxt * kuu
-        let r = Matrix::from(m, kuu_wx_r_cols.concat());
+        let rt = Matrix::from(m, kuu_wx_r_cols.concat());
 
         // kuu - rt * (l * d * lt)^{-1} * r = q2 * t2 * q2t
-        let (q2, t2) = Matrix::sytrd_k(
+        let (q2, t2) = lanczos(
           m,
-          100,
-          &|v| {
+          K,
+          &|v: Vec<f64>| -> Result<Vec<f64>, DistributionError> {
             Ok(
               (kuu.vec_mul(v.clone())?.col_mat()
-                - r.t() * l.pttrs(&d, &r * v.col_mat())?.vec().col_mat())
+                - &rt * l.pttrs(&d, rt.t() * v.col_mat())?.vec().col_mat())
               .vec(),
             )
           },
-          None,
+          &unit(m),
         )?;
 
-        // t2 = l2 * d2 * l2t
-        let (l2, d2) = t2.pttrf()?;
-        let d2_sqrt = Matrix::diag(&d2).dipowf(0.5);
-        // l2' = l2 * \sqrt{d2}
-        // kuu - rt * l * d * lt * r = q2 * l2' * l2't * q2t
-        let l2_prime = l2.mat(false) * d2_sqrt;
+        // t2 = v2 * λ2 * v2t
+        // the posterior covariance is only semidefinite once the data are dense, so eigenvalues rounded below 0 are clamped instead of factorizing t2
+        let (lambda2, v2) = t2.stev()?;
+        let lambda2_sqrt = lambda2
+          .iter()
+          .map(|li| li.max(0.0).sqrt())
+          .collect::<Vec<_>>();
 
-        // s = q2 * l2'
-        let s = q2 * l2_prime;
+        // s = q2 * v2 * \sqrt{λ2}
+        let s = q2 * v2 * Matrix::diag(&lambda2_sqrt);
 
         Ok(s)
       })
@@ -147,6 +152,60 @@ where
     })
   }
 
+  /// LOVE variances of each point of `xs`, without forming their covariance.
+  /// Each point only touches its nonzero interpolation weights, so this is ![tex](https://latex.codecogs.com/svg.latex?O%28K%29) per point.
+  pub fn predict_variances(&self, xs: &[T]) -> Result<Vec<f64>, DistributionError> {
+    let len = xs.len();
+    if len == 0 {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::Empty.into(),
+      ));
+    }
+
+    let wxs = &self.u.interpolation_weight(xs)?;
+    if self.s.len() != wxs.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::DimensionMismatch.into(),
+      ));
+    }
+
+    let k = self.s[0].cols();
+    let mut l_sigma = vec![vec![0.0; k]; len];
+
+    for (wxspi, spi) in wxs.iter().zip(self.s.iter()) {
+      for (&(j, i), &w) in wxspi.elems.iter() {
+        // sigma_i^2 = wxs_it * s * st * wxs_i
+        for (ki, l_sigma_ki) in l_sigma[i].iter_mut().enumerate() {
+          *l_sigma_ki += w * spi[(j, ki)];
+        }
+      }
+    }
+
+    Ok(
+      l_sigma
+        .iter()
+        .map(|li| li.iter().map(|lij| lij.powi(2)).sum::<f64>())
+        .collect(),
+    )
+  }
+}
+
+impl<K, T> GaussianProcessRegressor<KissLoveGP<K, T>, Convolutional<K>, T>
+  for KissLoveGPregressor<K, T>
+where
+  K: Kernel<Vec<f64>>,
+  T: RandomVariable + Convolutable,
+{
+  fn new(
+    gp: KissLoveGP<K, T>,
+    y: &[f64],
+    params: GaussianProcessParams<T>,
+  ) -> Result<Self, DistributionError> {
+    let points = KissLoveGP::<K, T>::grid_points(&params.x)?;
+
+    Self::with_grid(gp, y, params, &points)
+  }
+
   fn n(&self) -> usize {
     self.n
   }
@@ -155,6 +214,38 @@ where
     self.ey
   }
 
+  /// Predictive mean and LOVE variance of a single test point.
+  /// Only the nonzero interpolation weights of `xs` are touched, so this is ![tex](https://latex.codecogs.com/svg.latex?O%28K%29).
+  fn predict(&self, xs: &T) -> Result<NormalParams, DistributionError> {
+    let wxs = &self.u.interpolation_weight(std::slice::from_ref(xs))?;
+    let p = self.a.len();
+
+    if p != wxs.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::DimensionMismatch.into(),
+      ));
+    }
+
+    let k = self.s[0].cols();
+    let mut mu = self.ey;
+    let mut l_sigma = vec![0.0; k];
+
+    for ((wxspi, api), spi) in wxs.iter().zip(self.a.iter()).zip(self.s.iter()) {
+      for (&(j, _), &w) in wxspi.elems.iter() {
+        // mu = wxst * a
+        mu += w * api[(j, 0)];
+        // sigma^2 = wxst * s * st * wxs
+        for (ki, l_sigma_ki) in l_sigma.iter_mut().enumerate() {
+          *l_sigma_ki += w * spi[(j, ki)];
+        }
+      }
+    }
+
+    let sigma = l_sigma.iter().map(|li| li.powi(2)).sum::<f64>().sqrt();
+
+    NormalParams::new(mu, sigma)
+  }
+
   fn predict_multivariate(&self, xs: &[T]) -> Result<MultivariateNormalParams, DistributionError> {
     let len = xs.len();
     if len == 0 {
@@ -176,24 +267,190 @@ where
       .into_iter()
       .map(|pi| {
         let wxspi = &wxs[pi];
-        let wxspit = &wxspi.t();
 
         let api = &self.a[pi];
         let spi = &self.s[pi];
 
-        let mupi = (wxspit * api).vec();
-        let l_sigma_pi = wxspit * spi;
+        let mupi = sparse_t_mul(wxspi, api).vec();
+        let l_sigma_pi = sparse_t_mul(wxspi, spi);
 
         Ok((mupi, l_sigma_pi))
       })
       .try_fold::<(Vec<f64>, Matrix), _, Result<(Vec<f64>, Matrix), DistributionError>>(
-        (vec![self.ey; len], Matrix::new(len, len)),
+        (vec![self.ey; len], Matrix::new(len, self.s[0].cols())),
         |a, b: Result<(Vec<f64>, Matrix), DistributionError>| {
           let b = b?;
           Ok(((a.0.col_mat() + b.0.col_mat()).vec(), a.1 + b.1))
         },
       )?;
 
-    MultivariateNormalParams::new(mu, l_sigma)
+    // l_sigma is len×k, so the covariance it represents has rank at most k and needs jitter once len > k
+    let covariance = &l_sigma * l_sigma.t();
+    let scale = covariance.tr() / len as f64;
+
+    MultivariateNormalParams::new(mu, potrf_jittered(covariance, scale)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{super::grid::Axis, Grid, KissLoveGPregressor};
+  use crate::continuous::multivariate::elliptical::lower_triangle;
+  use crate::nonparametric::{
+    exact_gp::regressor::ExactGPRegressor, kernel_matrix, ExactGP, GaussianProcess,
+    GaussianProcessParams, GaussianProcessRegressor, KissLoveGP,
+  };
+  use opensrdk_kernel_method::RBF;
+  use opensrdk_linear_algebra::*;
+  use std::{f64::consts::PI, marker::PhantomData};
+
+  #[test]
+  fn predict() {
+    let theta = vec![1.0, 1.0];
+    let m = 17;
+    let u = (0..m)
+      .map(|i| vec![2.0 * PI * i as f64 / (m - 1) as f64])
+      .collect::<Vec<_>>();
+    // observe the sine on every other inducing point
+    let x = u.iter().step_by(2).cloned().collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let n = x.len();
+
+    let exact = ExactGPRegressor::new(
+      ExactGP::new(RBF),
+      &y,
+      GaussianProcessParams::new(x.clone(), theta.clone()),
+    )
+    .unwrap();
+
+    // exact posterior on the grid, stored the way `KissLoveGPregressor` keeps it
+    let ey = y.iter().sum::<f64>() / n as f64;
+    let y_ey = y.iter().map(|yi| yi - ey).collect::<Vec<_>>();
+    let kuu = kernel_matrix(&RBF, &theta, &u, &u).unwrap();
+    let kux = kernel_matrix(&RBF, &theta, &u, &x).unwrap();
+    let lkxx = kernel_matrix(&RBF, &theta, &x, &x)
+      .unwrap()
+      .potrf()
+      .unwrap();
+    let a = &kux * lkxx.potrs(y_ey.col_mat()).unwrap();
+    let sigma_uu = kuu - &kux * lkxx.potrs(kux.t()).unwrap() + 1e-9 * Matrix::identity(m);
+    let s = lower_triangle(sigma_uu.potrf().unwrap());
+
+    let kiss_love = KissLoveGPregressor::<RBF, Vec<f64>> {
+      n,
+      ey,
+      u: Grid::new(vec![Axis::new(0.0, 2.0 * PI, m).unwrap()]),
+      a: vec![a],
+      s: vec![s],
+      phantom: PhantomData,
+    };
+
+    // on the grid the interpolation is exact
+    for xs in u.iter().skip(1).step_by(2) {
+      let exact = exact.predict(xs).unwrap();
+      let kiss_love = kiss_love.predict(xs).unwrap();
+
+      assert!((exact.mu() - kiss_love.mu()).abs() < 1e-6);
+      assert!((exact.sigma() - kiss_love.sigma()).abs() < 1e-3);
+    }
+
+    for &xs in [0.3, 1.7, 2.9, 4.1, 5.5].iter() {
+      let exact = exact.predict(&vec![xs]).unwrap();
+      let kiss_love = kiss_love.predict(&vec![xs]).unwrap();
+
+      assert!((exact.mu() - kiss_love.mu()).abs() < 0.05);
+      assert!((exact.sigma() - kiss_love.sigma()).abs() < 0.05);
+    }
+
+    let xs = [0.3, 1.7, 2.9, 4.1, 5.5]
+      .iter()
+      .map(|&xs| vec![xs])
+      .collect::<Vec<_>>();
+    let variances = kiss_love.predict_variances(&xs).unwrap();
+    for (xs, variance) in xs.iter().zip(variances.iter()) {
+      assert!((kiss_love.predict(xs).unwrap().sigma().powi(2) - variance).abs() < 1e-12);
+      assert!((exact.predict(xs).unwrap().sigma() - variance.sqrt()).abs() < 0.05);
+    }
+    assert!(kiss_love.predict_variances(&[]).is_err());
+
+    // fitted through `new`, whose default grid has N / 2 points, and the LOVE cache
+    let x = (0..64)
+      .map(|i| vec![2.0 * PI * i as f64 / 63.0])
+      .collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x, theta);
+    let exact =
+      ExactGPRegressor::new(ExactGP::new(RBF).with_sigma(0.1), &y, params.clone()).unwrap();
+    let kiss_love =
+      KissLoveGPregressor::new(KissLoveGP::from(RBF).with_sigma(0.1), &y, params).unwrap();
+    for &xs in [0.3, 1.7, 2.9, 4.1, 5.5].iter() {
+      let exact = exact.predict(&vec![xs]).unwrap();
+      let kiss_love = kiss_love.predict(&vec![xs]).unwrap();
+      // within the linear interpolation error of the 32-point grid
+      assert!((exact.mu() - kiss_love.mu()).abs() < 3e-3);
+      assert!((exact.sigma() - kiss_love.sigma()).abs() < 5e-3);
+    }
+  }
+
+  #[test]
+  fn with_grid() {
+    let theta = vec![1.0, 1.0];
+    let n = 16;
+    let x = (0..n)
+      .map(|i| vec![2.0 * PI * i as f64 / (n - 1) as f64])
+      .collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let kxx = kernel_matrix(&RBF, &theta, &x, &x).unwrap();
+
+    // squared error of wxt * kuu * wx against kxx
+    let error = |points: usize| {
+      let (wx, u) = KissLoveGP::<RBF, Vec<f64>>::wx_u_with_grid(&x, &[points]).unwrap();
+      assert_eq!(u.axes()[0].points(), points);
+      let kuu = u.kuu(&RBF, &theta).unwrap();
+
+      (0..n)
+        .map(|i| {
+          let mut e = vec![0.0; n];
+          e[i] = 1.0;
+          let kxx_i = KissLoveGP::<RBF, Vec<f64>>::wxt_kuu_wx_vec_mul(&e, &wx, &kuu, 0.0).unwrap();
+
+          (0..n)
+            .map(|j| (kxx_i[j] - kxx[(j, i)]).powi(2))
+            .sum::<f64>()
+        })
+        .sum::<f64>()
+    };
+
+    assert!(error(64) < error(8));
+
+    let gp_params = GaussianProcessParams::new(x.clone(), theta.clone());
+    let exact =
+      ExactGPRegressor::new(ExactGP::new(RBF).with_sigma(0.1), &y, gp_params.clone()).unwrap();
+    let kiss_love =
+      KissLoveGPregressor::with_grid(KissLoveGP::from(RBF).with_sigma(0.1), &y, gp_params, &[64])
+        .unwrap();
+    // more test points than the rank of the LOVE factor
+    for &len in [80, 200].iter() {
+      let xs = (0..len)
+        .map(|i| vec![2.0 * PI * i as f64 / (len - 1) as f64])
+        .collect::<Vec<_>>();
+      let a = exact.predict_multivariate(&xs).unwrap();
+      let b = kiss_love.predict_multivariate(&xs).unwrap();
+      let (a_covariance, b_covariance) = (a.covariance(), b.covariance());
+
+      // within the interpolation error of the 64-point grid
+      for i in 0..len {
+        assert!((a.mu()[i] - b.mu()[i]).abs() < 5e-3);
+        assert!((a_covariance[(i, i)] - b_covariance[(i, i)]).abs() < 1e-3);
+      }
+    }
+
+    assert!(KissLoveGPregressor::with_grid(
+      KissLoveGP::from(RBF),
+      &y,
+      GaussianProcessParams::new(x, theta),
+      &[8, 8],
+    )
+    .is_err());
   }
 }
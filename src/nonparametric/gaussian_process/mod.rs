@@ -1,20 +1,26 @@
 pub mod exact_gp;
 pub mod ey;
 pub mod kernel_matrix;
+pub mod kernel_params_layout;
 pub mod kiss_love_gp;
 pub mod regressor;
+pub mod standardizer;
 pub mod student_tp;
+pub mod symmetric_packed;
 
 use crate::DistributionError;
 use crate::{Distribution, RandomVariable};
 pub use exact_gp::*;
 pub use ey::*;
 pub use kernel_matrix::*;
+pub use kernel_params_layout::*;
 pub use kiss_love_gp::*;
 use opensrdk_kernel_method::Kernel;
 pub use regressor::*;
-use std::fmt::Debug;
+pub use standardizer::*;
+use std::{fmt::Debug, sync::Arc};
 pub use student_tp::*;
+pub use symmetric_packed::*;
 
 #[derive(thiserror::Error, Debug)]
 pub enum GaussianProcessError {
@@ -24,6 +30,8 @@ pub enum GaussianProcessError {
   DimensionMismatch,
   #[error("NaN contaminated.")]
   NaNContamination,
+  #[error("Kernel matrix is not positive definite even with jitter.")]
+  NotPositiveDefinite,
 }
 
 ///
@@ -57,7 +65,7 @@ pub struct GaussianProcessParams<T>
 where
   T: RandomVariable,
 {
-  x: Vec<T>,
+  x: Arc<Vec<T>>,
   theta: Vec<f64>,
 }
 
@@ -66,10 +74,60 @@ where
   T: RandomVariable,
 {
   pub fn new(x: Vec<T>, theta: Vec<f64>) -> Self {
-    Self { x, theta }
+    Self {
+      x: Arc::new(x),
+      theta,
+    }
   }
 
+  /// Replaces `theta` while sharing the inputs `x` instead of copying them.
+  pub fn with_theta(&self, theta: Vec<f64>) -> Result<Self, DistributionError> {
+    if theta.len() != self.theta.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::DimensionMismatch.into(),
+      ));
+    }
+
+    Ok(Self {
+      x: self.x.clone(),
+      theta,
+    })
+  }
+
+  pub fn x(&self) -> &[T] {
+    &self.x
+  }
+
+  pub fn theta(&self) -> &[f64] {
+    &self.theta
+  }
+
+  /// Copies `x` only if it is still shared.
   pub fn eject(self) -> (Vec<T>, Vec<f64>) {
+    let x = Arc::try_unwrap(self.x).unwrap_or_else(|x| (*x).clone());
+
+    (x, self.theta)
+  }
+
+  /// Same as `eject`, but keeps `x` shared instead of copying it.
+  pub(crate) fn eject_shared(self) -> (Arc<Vec<T>>, Vec<f64>) {
     (self.x, self.theta)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn with_theta() {
+    let params = GaussianProcessParams::new(vec![vec![0.0], vec![1.0]], vec![1.0, 1.0]);
+
+    let updated = params.with_theta(vec![2.0, 0.5]).unwrap();
+
+    assert!(Arc::ptr_eq(&params.x, &updated.x));
+    assert_eq!(updated.theta(), &[2.0, 0.5]);
+    assert_eq!(updated.x(), params.x());
+    assert!(params.with_theta(vec![1.0]).is_err());
+  }
+}
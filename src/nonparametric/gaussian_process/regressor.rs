@@ -1,8 +1,15 @@
-use super::{GaussianProcess, GaussianProcessParams};
+use super::{
+  exact_gp::regressor::ExactGPRegressor, kiss_love_gp::regressor::KissLoveGPregressor, ExactGP,
+  GaussianProcess, GaussianProcessParams, KissLoveGP,
+};
 use crate::DistributionError;
 use crate::{MultivariateNormalParams, NormalParams, RandomVariable};
-use opensrdk_kernel_method::Kernel;
-use std::fmt::Debug;
+use opensrdk_kernel_method::{Convolutable, Convolutional, Kernel};
+use special::Error;
+use std::{
+  f64::consts::{E, PI, SQRT_2},
+  fmt::Debug,
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum GaussianProcessRegressorError {
@@ -12,6 +19,8 @@ pub enum GaussianProcessRegressorError {
   DimensionMismatch,
   #[error("NaN contaminated.")]
   NaNContamination,
+  #[error("Level must be in (0, 1).")]
+  LevelMustBeInUnitInterval,
 }
 
 fn ref_to_slice<T>(v: &T) -> &[T] {
@@ -35,5 +44,246 @@ where
     NormalParams::new(mul_n.mu()[0], mul_n.lsigma()[0][0])
   }
 
+  /// Returns `(lower, mean, upper)` of the central `level` credible interval.
+  fn predict_interval(&self, xs: &T, level: f64) -> Result<(f64, f64, f64), DistributionError> {
+    if !(0.0 < level && level < 1.0) {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::LevelMustBeInUnitInterval.into(),
+      ));
+    }
+
+    let params = self.predict(xs)?;
+    let mu = params.mu();
+    // Φ⁻¹((1 + level) / 2)
+    let z = SQRT_2 * level.inv_error();
+    let width = z * params.sigma();
+
+    Ok((mu - width, mu, mu + width))
+  }
+
   fn predict_multivariate(&self, xs: &[T]) -> Result<MultivariateNormalParams, DistributionError>;
+
+  /// Differential entropy of the prediction ![tex](https://latex.codecogs.com/svg.latex?\frac%7B1%7D%7B2%7D\ln%282\pi%20e\sigma_*%5E2%29).
+  fn predictive_entropy(&self, xs: &T) -> Result<f64, DistributionError> {
+    let sigma = self.predict(xs)?.sigma();
+
+    Ok(0.5 * (2.0 * PI * E * sigma.powi(2)).ln())
+  }
+
+  /// Index of the candidate with the highest `predictive_entropy`, the next query of uncertainty sampling.
+  fn max_entropy_point(&self, candidates: &[T]) -> Result<usize, DistributionError> {
+    if candidates.is_empty() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessRegressorError::Empty.into(),
+      ));
+    }
+
+    let mut best = (0, f64::NEG_INFINITY);
+    for (i, xs) in candidates.iter().enumerate() {
+      let entropy = self.predictive_entropy(xs)?;
+      if best.1 < entropy {
+        best = (i, entropy);
+      }
+    }
+
+    Ok(best.0)
+  }
+}
+
+/// Object-safe view of a fitted regressor, so that the approximation can be chosen at runtime.
+/// The methods are suffixed with `_dyn` so that they do not clash with `GaussianProcessRegressor`'s when both traits are in scope.
+pub trait GaussianProcessPredictor<T>
+where
+  T: RandomVariable,
+{
+  fn n_dyn(&self) -> usize;
+  fn predict_dyn(&self, xs: &T) -> Result<NormalParams, DistributionError>;
+  fn predict_multivariate_dyn(
+    &self,
+    xs: &[T],
+  ) -> Result<MultivariateNormalParams, DistributionError>;
+}
+
+impl<K, T> GaussianProcessPredictor<T> for ExactGPRegressor<K, T>
+where
+  K: Kernel<T>,
+  T: RandomVariable,
+{
+  fn n_dyn(&self) -> usize {
+    self.n()
+  }
+
+  fn predict_dyn(&self, xs: &T) -> Result<NormalParams, DistributionError> {
+    self.predict(xs)
+  }
+
+  fn predict_multivariate_dyn(
+    &self,
+    xs: &[T],
+  ) -> Result<MultivariateNormalParams, DistributionError> {
+    self.predict_multivariate(xs)
+  }
+}
+
+impl<K, T> GaussianProcessPredictor<T> for KissLoveGPregressor<K, T>
+where
+  K: Kernel<Vec<f64>>,
+  T: RandomVariable + Convolutable,
+{
+  fn n_dyn(&self) -> usize {
+    self.n()
+  }
+
+  fn predict_dyn(&self, xs: &T) -> Result<NormalParams, DistributionError> {
+    self.predict(xs)
+  }
+
+  fn predict_multivariate_dyn(
+    &self,
+    xs: &[T],
+  ) -> Result<MultivariateNormalParams, DistributionError> {
+    self.predict_multivariate(xs)
+  }
+}
+
+/// Approximation used by `GaussianProcessParams::fit`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GaussianProcessMethod {
+  /// `ExactGPRegressor`, for small `N`.
+  Exact,
+  /// `KissLoveGPregressor` with `points[d]` inducing points along the `d`-th input dimension, or the default grid.
+  KissLove { points: Option<Vec<usize>> },
+}
+
+impl GaussianProcessParams<Vec<f64>> {
+  /// Fits a regressor of `y` with the observation noise `sigma` by the approximation `method`.
+  pub fn fit<K>(
+    self,
+    y: &[f64],
+    kernel: K,
+    sigma: f64,
+    method: &GaussianProcessMethod,
+  ) -> Result<Box<dyn GaussianProcessPredictor<Vec<f64>>>, DistributionError>
+  where
+    K: Kernel<Vec<f64>> + 'static,
+  {
+    Ok(match method {
+      GaussianProcessMethod::Exact => Box::new(ExactGPRegressor::new(
+        ExactGP::new(kernel).with_sigma(sigma),
+        y,
+        self,
+      )?),
+      GaussianProcessMethod::KissLove { points } => {
+        let gp = KissLoveGP::<K, Vec<f64>>::new(Convolutional::new(kernel)).with_sigma(sigma);
+
+        Box::new(match points {
+          Some(points) => KissLoveGPregressor::with_grid(gp, y, self, points)?,
+          None => KissLoveGPregressor::new(gp, y, self)?,
+        })
+      }
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::GaussianProcessMethod;
+  use crate::nonparametric::{
+    exact_gp::regressor::ExactGPRegressor, ExactGP, GaussianProcess, GaussianProcessParams,
+    GaussianProcessRegressor,
+  };
+  use opensrdk_kernel_method::RBF;
+  use std::f64::consts::PI;
+
+  #[test]
+  fn fit() {
+    let n = 10;
+    let x = (0..n)
+      .map(|i| vec![2.0 * PI * i as f64 / (n - 1) as f64])
+      .collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x, vec![1.0, 1.0]);
+
+    let methods = [
+      GaussianProcessMethod::Exact,
+      GaussianProcessMethod::KissLove {
+        points: Some(vec![64]),
+      },
+    ];
+    let regressors = methods
+      .iter()
+      .map(|method| params.clone().fit(&y, RBF, 1.0, method).unwrap())
+      .collect::<Vec<_>>();
+
+    for &xs in [0.5, 1.5, 3.0, 4.5].iter() {
+      let predictions = regressors
+        .iter()
+        .map(|r| r.predict_dyn(&vec![xs]).unwrap())
+        .collect::<Vec<_>>();
+
+      // both within the interpolation error of the 64-point grid
+      assert!((predictions[0].mu() - predictions[1].mu()).abs() < 1e-3);
+      assert!((predictions[0].sigma() - predictions[1].sigma()).abs() < 2e-3);
+    }
+    assert!(regressors.iter().all(|r| r.n_dyn() == n));
+  }
+
+  #[test]
+  fn glob_import() {
+    // as the README imports it, with both regressor traits in scope
+    use crate::nonparametric::kiss_love_gp::regressor::KissLoveGPregressor;
+    use crate::nonparametric::*;
+    use opensrdk_kernel_method::Convolutional;
+
+    let x = (0..10).map(|i| vec![i as f64 * 0.5]).collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x, vec![1.0, 1.0]);
+
+    let exact =
+      ExactGPRegressor::new(ExactGP::new(RBF).with_sigma(0.1), &y, params.clone()).unwrap();
+    let kiss_love = KissLoveGPregressor::new(
+      KissLoveGP::<_, Vec<f64>>::new(Convolutional::new(RBF)).with_sigma(0.1),
+      &y,
+      params.clone(),
+    )
+    .unwrap();
+    let fitted = params
+      .fit(&y, RBF, 0.1, &GaussianProcessMethod::Exact)
+      .unwrap();
+
+    let xs = vec![1.25];
+    assert_eq!(exact.n(), kiss_love.n());
+    assert_eq!(exact.n(), fitted.n_dyn());
+    assert!(kiss_love.predict(&xs).is_ok());
+    assert_eq!(
+      exact.predict(&xs).unwrap(),
+      fitted.predict_dyn(&xs).unwrap()
+    );
+  }
+
+  #[test]
+  fn max_entropy_point() {
+    // observations on [0, 2] and [4, 6], leaving a gap around 3
+    let x = (0..=4)
+      .chain(8..=12)
+      .map(|i| vec![i as f64 * 0.5])
+      .collect::<Vec<_>>();
+    let y = x.iter().map(|xi| xi[0].sin()).collect::<Vec<_>>();
+    let regressor = ExactGPRegressor::new(
+      ExactGP::new(RBF).with_sigma(0.1),
+      &y,
+      GaussianProcessParams::new(x, vec![1.0, 1.0]),
+    )
+    .unwrap();
+
+    let candidates = (0..=24).map(|i| vec![i as f64 * 0.25]).collect::<Vec<_>>();
+    let i = regressor.max_entropy_point(&candidates).unwrap();
+    assert!((2.5..=3.5).contains(&candidates[i][0]));
+
+    let params = regressor.predict(&candidates[i]).unwrap();
+    let entropy = regressor.predictive_entropy(&candidates[i]).unwrap();
+    assert!((entropy - (0.5 + 0.5 * (2.0 * PI).ln() + params.sigma().ln())).abs() < 1e-12);
+
+    assert!(regressor.max_entropy_point(&[]).is_err());
+  }
 }
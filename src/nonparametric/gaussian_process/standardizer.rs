@@ -0,0 +1,143 @@
+use super::{GaussianProcessError, GaussianProcessParams};
+use crate::DistributionError;
+
+/// Per-dimension standardization ![tex](https://latex.codecogs.com/svg.latex?%28x_d-\mu_d%29/\sigma_d) of GP inputs.
+///
+/// Fitted on the training inputs so that test inputs are transformed consistently.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standardizer {
+  mean: Vec<f64>,
+  std: Vec<f64>,
+}
+
+impl Standardizer {
+  pub fn fit(x: &[Vec<f64>]) -> Result<Self, DistributionError> {
+    let n = x.len();
+    if n == 0 {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::Empty.into(),
+      ));
+    }
+    let dim = x[0].len();
+    if x.iter().any(|xi| xi.len() != dim) {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::DimensionMismatch.into(),
+      ));
+    }
+
+    let mean = (0..dim)
+      .map(|d| x.iter().map(|xi| xi[d]).sum::<f64>() / n as f64)
+      .collect::<Vec<_>>();
+    // constant dimensions are only centered
+    let std = (0..dim)
+      .map(|d| {
+        let std = (x.iter().map(|xi| (xi[d] - mean[d]).powi(2)).sum::<f64>() / n as f64).sqrt();
+        if std > 0.0 {
+          std
+        } else {
+          1.0
+        }
+      })
+      .collect::<Vec<_>>();
+
+    Ok(Self { mean, std })
+  }
+
+  pub fn mean(&self) -> &[f64] {
+    &self.mean
+  }
+
+  pub fn std(&self) -> &[f64] {
+    &self.std
+  }
+
+  pub fn transform(&self, x: &[f64]) -> Result<Vec<f64>, DistributionError> {
+    self.check_len(x)?;
+
+    Ok(
+      x.iter()
+        .zip(self.mean.iter().zip(self.std.iter()))
+        .map(|(xi, (mi, si))| (xi - mi) / si)
+        .collect(),
+    )
+  }
+
+  pub fn inverse_transform(&self, x: &[f64]) -> Result<Vec<f64>, DistributionError> {
+    self.check_len(x)?;
+
+    Ok(
+      x.iter()
+        .zip(self.mean.iter().zip(self.std.iter()))
+        .map(|(xi, (mi, si))| xi * si + mi)
+        .collect(),
+    )
+  }
+
+  fn check_len(&self, x: &[f64]) -> Result<(), DistributionError> {
+    if x.len() != self.mean.len() {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::DimensionMismatch.into(),
+      ));
+    }
+
+    Ok(())
+  }
+}
+
+impl GaussianProcessParams<Vec<f64>> {
+  /// Standardizes `x` and returns the fitted `Standardizer` to be applied to test inputs.
+  pub fn standardize_inputs(self) -> Result<(Self, Standardizer), DistributionError> {
+    let (x, theta) = self.eject();
+    let standardizer = Standardizer::fit(&x)?;
+    let x = x
+      .iter()
+      .map(|xi| standardizer.transform(xi))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((Self::new(x, theta), standardizer))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::nonparametric::*;
+  use crate::opensrdk_linear_algebra::*;
+  use opensrdk_kernel_method::RBF;
+
+  fn min_pivot(params: &GaussianProcessParams<Vec<f64>>) -> f64 {
+    let (x, theta) = params.clone().eject();
+    let n = x.len();
+    let kxx = kernel_matrix(&RBF, &theta, &x, &x).unwrap() + 1e-8 * Matrix::identity(n);
+    let l = kxx.potrf().unwrap();
+
+    (0..n).map(|i| l[(i, i)]).fold(f64::INFINITY, f64::min)
+  }
+
+  #[test]
+  fn it_works() {
+    // inputs on a scale of 1e-3 make every pair of points look identical to the kernel
+    let x = (0..10)
+      .map(|i| vec![i as f64 * 1e-4, 5.0 + i as f64 * 1e-4])
+      .collect::<Vec<_>>();
+    let params = GaussianProcessParams::new(x.clone(), vec![1.0, 1.0]);
+
+    let (standardized, standardizer) = params.clone().standardize_inputs().unwrap();
+
+    assert!(min_pivot(&params) * 100.0 < min_pivot(&standardized));
+
+    let (sx, _) = standardized.eject();
+    for (xi, sxi) in x.iter().zip(sx.iter()) {
+      let back = standardizer.inverse_transform(sxi).unwrap();
+      assert!(xi
+        .iter()
+        .zip(back.iter())
+        .all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+    for d in 0..2 {
+      assert!(sx.iter().map(|xi| xi[d]).sum::<f64>().abs() < 1e-10);
+    }
+
+    assert!(standardizer.transform(&[1.0]).is_err());
+    assert!(Standardizer::fit(&[]).is_err());
+  }
+}
@@ -57,10 +57,7 @@ where
 
     let (kxx_inv_y_ey, det) = self.gp.kxx_inv_vec(
       y_ey.vec(),
-      &GaussianProcessParams {
-        x: theta.x.clone(),
-        theta: theta.theta.clone(),
-      },
+      &GaussianProcessParams::new(theta.x.clone(), theta.theta.clone()),
       true,
     )?;
     let (kxx_inv_y_ey, det) = (kxx_inv_y_ey.col_mat(), det.unwrap());
@@ -75,7 +72,7 @@ where
   fn sample(
     &self,
     theta: &Self::U,
-    rng: &mut rand::prelude::StdRng,
+    rng: &mut dyn rand::RngCore,
   ) -> Result<Self::T, DistributionError> {
     let n = theta.x.len();
 
@@ -92,10 +89,7 @@ where
       .gp
       .lkxx_vec(
         z,
-        &GaussianProcessParams {
-          x: theta.x.clone(),
-          theta: theta.theta.clone(),
-        },
+        &GaussianProcessParams::new(theta.x.clone(), theta.theta.clone()),
       )?
       .col_mat();
 
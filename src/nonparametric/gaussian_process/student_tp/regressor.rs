@@ -33,7 +33,7 @@ where
     params: StudentTPParams<T>,
   ) -> Result<Self, Box<dyn Error>> {
     let (x, theta, nu) = params.eject();
-    let params = GaussianProcessParams { x, theta };
+    let params = GaussianProcessParams::new(x, theta);
     let kxx_inv_y = tp.gp.kxx_inv_vec(y.to_vec(), &params, false)?.0.col_mat();
     let yt = y.to_vec().row_mat();
 
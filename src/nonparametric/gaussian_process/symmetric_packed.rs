@@ -0,0 +1,222 @@
+use super::GaussianProcessError;
+use crate::opensrdk_linear_algebra::*;
+use crate::DistributionError;
+use rayon::prelude::*;
+use std::ops::{Index, IndexMut};
+
+/// Symmetric `n x n` matrix holding only its lower triangle, packed column by column into `n (n + 1) / 2` elements as LAPACK's `'L'` packed storage does.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymmetricPackedMatrix {
+  n: usize,
+  elems: Vec<f64>,
+}
+
+/// Lower Cholesky factor `L` of a `SymmetricPackedMatrix`, kept in the same packed storage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackedCholesky {
+  n: usize,
+  elems: Vec<f64>,
+}
+
+fn packed_index(n: usize, r: usize, c: usize) -> usize {
+  let (r, c) = if r < c { (c, r) } else { (r, c) };
+
+  r + c * (2 * n - c - 1) / 2
+}
+
+impl SymmetricPackedMatrix {
+  pub fn new(n: usize) -> Self {
+    Self {
+      n,
+      elems: vec![0.0; n * (n + 1) / 2],
+    }
+  }
+
+  /// `elems` is the lower triangle in column-major order.
+  pub fn from(n: usize, elems: Vec<f64>) -> Result<Self, DistributionError> {
+    if elems.len() != n * (n + 1) / 2 {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::DimensionMismatch.into(),
+      ));
+    }
+
+    Ok(Self { n, elems })
+  }
+
+  pub fn n(&self) -> usize {
+    self.n
+  }
+
+  pub fn elems(&self) -> &[f64] {
+    &self.elems
+  }
+
+  pub fn add_diag(&mut self, value: f64) {
+    for i in 0..self.n {
+      self[(i, i)] += value;
+    }
+  }
+
+  pub fn to_dense(&self) -> Matrix {
+    let mut a = Matrix::new(self.n, self.n);
+    for c in 0..self.n {
+      for r in 0..self.n {
+        a[(r, c)] = self[(r, c)];
+      }
+    }
+
+    a
+  }
+
+  /// Cholesky factorization overwriting the packed elements, so no second matrix is allocated.
+  pub fn pptrf(self) -> Result<PackedCholesky, DistributionError> {
+    let n = self.n;
+    let mut l = self.elems;
+
+    for j in 0..n {
+      let d = l[packed_index(n, j, j)]
+        - (0..j)
+          .map(|k| l[packed_index(n, j, k)].powi(2))
+          .sum::<f64>();
+      if d <= 0.0 || d.is_nan() {
+        return Err(DistributionError::InvalidParameters(
+          GaussianProcessError::NotPositiveDefinite.into(),
+        ));
+      }
+      let ljj = d.sqrt();
+      l[packed_index(n, j, j)] = ljj;
+
+      for i in j + 1..n {
+        let s = (0..j)
+          .map(|k| l[packed_index(n, i, k)] * l[packed_index(n, j, k)])
+          .sum::<f64>();
+        l[packed_index(n, i, j)] = (l[packed_index(n, i, j)] - s) / ljj;
+      }
+    }
+
+    Ok(PackedCholesky { n, elems: l })
+  }
+}
+
+impl Index<(usize, usize)> for SymmetricPackedMatrix {
+  type Output = f64;
+
+  fn index(&self, (r, c): (usize, usize)) -> &f64 {
+    &self.elems[packed_index(self.n, r, c)]
+  }
+}
+
+impl IndexMut<(usize, usize)> for SymmetricPackedMatrix {
+  fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut f64 {
+    &mut self.elems[packed_index(self.n, r, c)]
+  }
+}
+
+impl PackedCholesky {
+  /// `elems` is the lower triangle of `L` in column-major order.
+  pub(crate) fn from(n: usize, elems: Vec<f64>) -> Result<Self, DistributionError> {
+    if elems.len() != n * (n + 1) / 2 {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::DimensionMismatch.into(),
+      ));
+    }
+
+    Ok(Self { n, elems })
+  }
+
+  pub fn n(&self) -> usize {
+    self.n
+  }
+
+  /// `L[(r, c)]`, zero above the diagonal.
+  pub fn get(&self, r: usize, c: usize) -> f64 {
+    if r < c {
+      return 0.0;
+    }
+
+    self.elems[packed_index(self.n, r, c)]
+  }
+
+  pub fn to_dense(&self) -> Matrix {
+    let mut l = Matrix::new(self.n, self.n);
+    for c in 0..self.n {
+      for r in c..self.n {
+        l[(r, c)] = self.get(r, c);
+      }
+    }
+
+    l
+  }
+
+  /// ![tex](https://latex.codecogs.com/svg.latex?%28LL%5ET%29%5E%7B-1%7DB) by forward and back substitution, column by column in parallel.
+  pub fn pptrs(&self, b: Matrix) -> Result<Matrix, DistributionError> {
+    let n = self.n;
+    if b.rows() != n {
+      return Err(DistributionError::InvalidParameters(
+        GaussianProcessError::DimensionMismatch.into(),
+      ));
+    }
+    let cols = b.cols();
+
+    let b = b.vec();
+    let elems = b
+      .par_chunks(n.max(1))
+      .take(cols)
+      .flat_map(|bc| {
+        let mut x = bc.to_vec();
+        for r in 0..n {
+          let s = (0..r).map(|k| self.get(r, k) * x[k]).sum::<f64>();
+          x[r] = (x[r] - s) / self.get(r, r);
+        }
+        for r in (0..n).rev() {
+          let s = (r + 1..n).map(|k| self.get(k, r) * x[k]).sum::<f64>();
+          x[r] = (x[r] - s) / self.get(r, r);
+        }
+        x
+      })
+      .collect::<Vec<_>>();
+
+    Ok(Matrix::from(n, elems))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::SymmetricPackedMatrix;
+  use crate::opensrdk_linear_algebra::*;
+
+  #[test]
+  fn it_works() {
+    let a = mat!(
+      4.0, 1.0, 0.5;
+      1.0, 3.0, -0.2;
+      0.5, -0.2, 2.0
+    );
+    let packed = SymmetricPackedMatrix::from(3, vec![4.0, 1.0, 0.5, 3.0, -0.2, 2.0]).unwrap();
+    assert_eq!(packed.to_dense(), a);
+    assert_eq!(packed[(0, 2)], packed[(2, 0)]);
+    assert!(SymmetricPackedMatrix::from(3, vec![0.0; 5]).is_err());
+
+    let l = packed.clone().pptrf().unwrap();
+    let dense_l = a.clone().potrf().unwrap();
+    for c in 0..3 {
+      for r in c..3 {
+        assert!((l.get(r, c) - dense_l[(r, c)]).abs() < 1e-12);
+      }
+    }
+    assert_eq!(l.get(0, 1), 0.0);
+
+    let b = Matrix::from(3, vec![1.0, 2.0, 3.0, -1.0, 0.0, 0.5]);
+    let x = l.pptrs(b.clone()).unwrap();
+    let expected = dense_l.potrs(b).unwrap();
+    for c in 0..2 {
+      for r in 0..3 {
+        assert!((x[(r, c)] - expected[(r, c)]).abs() < 1e-12);
+      }
+    }
+
+    let mut singular = packed;
+    singular.add_diag(-10.0);
+    assert!(singular.pptrf().is_err());
+  }
+}
@@ -0,0 +1,139 @@
+use crate::{point_process::check_interval, Distribution, DistributionError, Exp, ExpParams};
+use rand::prelude::*;
+
+/// # HawkesProcess
+/// ![tex](https://latex.codecogs.com/svg.latex?\lambda%28t%29%3D\mu%2B\sum_%7Bt_i%3Ct%7D\alpha%20e%5E%7B-\beta%28t-t_i%29%7D)
+///
+/// Self-exciting point process: every event raises the intensity by `alpha`, decaying at rate `beta`.
+/// The process is stationary with mean rate `mu / (1 - alpha / beta)` when `alpha < beta`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HawkesProcess {
+  mu: f64,
+  alpha: f64,
+  beta: f64,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum HawkesProcessError {
+  #[error("'mu' must be non-negative")]
+  MuMustBeNonNegative,
+  #[error("'alpha' must be non-negative")]
+  AlphaMustBeNonNegative,
+  #[error("'beta' must be positive")]
+  BetaMustBePositive,
+}
+
+impl HawkesProcess {
+  pub fn new(mu: f64, alpha: f64, beta: f64) -> Result<Self, DistributionError> {
+    if mu < 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        HawkesProcessError::MuMustBeNonNegative.into(),
+      ));
+    }
+    if alpha < 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        HawkesProcessError::AlphaMustBeNonNegative.into(),
+      ));
+    }
+    if beta <= 0.0 {
+      return Err(DistributionError::InvalidParameters(
+        HawkesProcessError::BetaMustBePositive.into(),
+      ));
+    }
+
+    Ok(Self { mu, alpha, beta })
+  }
+
+  pub fn mu(&self) -> f64 {
+    self.mu
+  }
+
+  pub fn alpha(&self) -> f64 {
+    self.alpha
+  }
+
+  pub fn beta(&self) -> f64 {
+    self.beta
+  }
+
+  /// λ(t) given the past events `history`; events at or after `t` are ignored.
+  pub fn intensity(&self, t: f64, history: &[f64]) -> f64 {
+    self.mu
+      + history
+        .iter()
+        .filter(|&&ti| ti < t)
+        .map(|ti| self.alpha * (-self.beta * (t - ti)).exp())
+        .sum::<f64>()
+  }
+
+  /// Event times on `[0, t_max]` in increasing order, by Ogata's thinning.
+  /// Between events the intensity only decays, so its value right after the current time bounds it until the next event.
+  pub fn sample(&self, t_max: f64, rng: &mut dyn RngCore) -> Result<Vec<f64>, DistributionError> {
+    check_interval(self.mu, t_max)?;
+
+    let mut times = vec![];
+    let mut t = 0.0;
+    // Σ α exp(-β (t - t_i)) over the accepted events
+    let mut excitation = 0.0;
+    loop {
+      let bound = self.mu + excitation;
+      if bound == 0.0 {
+        return Ok(times);
+      }
+
+      let w = Exp.sample(&ExpParams::new(bound)?, rng)?;
+      t += w;
+      if t > t_max {
+        return Ok(times);
+      }
+
+      excitation *= (-self.beta * w).exp();
+      if rng.gen_range(0.0..1.0) * bound < self.mu + excitation {
+        times.push(t);
+        excitation += self.alpha;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::point_process::HawkesProcess;
+  use rand::prelude::*;
+
+  /// Variance-to-mean ratio of the counts in unit bins, about 1 for a Poisson process.
+  fn dispersion(times: &[f64], t_max: f64) -> f64 {
+    let bins = t_max as usize;
+    let mut counts = vec![0.0; bins];
+    for &t in times {
+      counts[(t as usize).min(bins - 1)] += 1.0;
+    }
+    let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+    let var = counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / counts.len() as f64;
+
+    var / mean
+  }
+
+  #[test]
+  fn it_works() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let t_max = 5000.0;
+
+    // without excitation it is a homogeneous Poisson process
+    let poisson = HawkesProcess::new(2.0, 0.0, 1.0).unwrap();
+    let times = poisson.sample(t_max, &mut rng).unwrap();
+    assert!((times.len() as f64 - 2.0 * t_max).abs() < 300.0);
+    assert!((dispersion(&times, t_max) - 1.0).abs() < 0.1);
+
+    // stationary rate 0.5 / (1 - 0.8) = 2.5
+    let hawkes = HawkesProcess::new(0.5, 0.8, 1.0).unwrap();
+    let times = hawkes.sample(t_max, &mut rng).unwrap();
+    assert!((times.len() as f64 / t_max - 2.5).abs() < 0.3);
+    assert!(times.windows(2).all(|w| w[0] < w[1]));
+    assert!(dispersion(&times, t_max) > 2.0);
+
+    let t = times[10] + 0.1;
+    assert!(hawkes.intensity(t, &times) > hawkes.mu());
+    assert!(HawkesProcess::new(0.5, 0.8, 0.0).is_err());
+  }
+}
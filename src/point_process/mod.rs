@@ -0,0 +1,5 @@
+pub mod hawkes_process;
+pub mod poisson_process;
+
+pub use hawkes_process::*;
+pub use poisson_process::*;
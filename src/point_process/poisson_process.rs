@@ -0,0 +1,120 @@
+use crate::{Distribution, DistributionError, Exp, ExpParams};
+use rand::prelude::*;
+
+#[derive(thiserror::Error, Debug)]
+pub enum PoissonProcessError {
+  #[error("'rate' must be non-negative")]
+  RateMustBeNonNegative,
+  #[error("'t_max' must be non-negative")]
+  TMaxMustBeNonNegative,
+  #[error("Rate function exceeds its upper bound")]
+  RateExceedsBound,
+}
+
+/// Event times of a homogeneous Poisson process with intensity `rate` on `[0, t_max]`, in increasing order.
+/// Inter-arrival times are drawn from `Exp(rate)`.
+pub fn sample_poisson_process(
+  rate: f64,
+  t_max: f64,
+  rng: &mut dyn RngCore,
+) -> Result<Vec<f64>, DistributionError> {
+  check_interval(rate, t_max)?;
+  if rate == 0.0 {
+    return Ok(vec![]);
+  }
+
+  let inter_arrival = ExpParams::new(rate)?;
+  let mut times = vec![];
+  let mut t = 0.0;
+  loop {
+    t += Exp.sample(&inter_arrival, rng)?;
+    if t > t_max {
+      return Ok(times);
+    }
+    times.push(t);
+  }
+}
+
+/// Event times of an inhomogeneous Poisson process with intensity `rate(t)` on `[0, t_max]`, by Lewis-Shedler thinning.
+/// `rate_max` must bound `rate(t)` over the interval; a candidate exceeding it is reported as an error.
+pub fn sample_inhomogeneous_poisson_process<F>(
+  rate: F,
+  rate_max: f64,
+  t_max: f64,
+  rng: &mut dyn RngCore,
+) -> Result<Vec<f64>, DistributionError>
+where
+  F: Fn(f64) -> f64,
+{
+  let candidates = sample_poisson_process(rate_max, t_max, rng)?;
+
+  let mut times = vec![];
+  for t in candidates {
+    let rate_t = rate(t);
+    if !(0.0..=rate_max).contains(&rate_t) {
+      return Err(DistributionError::InvalidParameters(
+        PoissonProcessError::RateExceedsBound.into(),
+      ));
+    }
+    if rng.gen_range(0.0..1.0) * rate_max < rate_t {
+      times.push(t);
+    }
+  }
+
+  Ok(times)
+}
+
+pub(crate) fn check_interval(rate: f64, t_max: f64) -> Result<(), DistributionError> {
+  if rate < 0.0 {
+    return Err(DistributionError::InvalidParameters(
+      PoissonProcessError::RateMustBeNonNegative.into(),
+    ));
+  }
+  if t_max < 0.0 {
+    return Err(DistributionError::InvalidParameters(
+      PoissonProcessError::TMaxMustBeNonNegative.into(),
+    ));
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::point_process::{sample_inhomogeneous_poisson_process, sample_poisson_process};
+  use rand::prelude::*;
+
+  #[test]
+  fn it_works() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let (rate, t_max) = (2.0, 5000.0);
+
+    let times = sample_poisson_process(rate, t_max, &mut rng).unwrap();
+    // the count is Poisson(rate * t_max) with standard deviation 100
+    assert!((times.len() as f64 - rate * t_max).abs() < 300.0);
+    assert!(times.windows(2).all(|w| w[0] < w[1]));
+    assert!(times.iter().all(|&t| 0.0 <= t && t <= t_max));
+
+    assert!(sample_poisson_process(0.0, t_max, &mut rng)
+      .unwrap()
+      .is_empty());
+    assert!(sample_poisson_process(-1.0, t_max, &mut rng).is_err());
+  }
+
+  #[test]
+  fn inhomogeneous() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let t_max = 1000.0;
+    // ∫_0^1000 t / 500 dt = 1000
+    let rate = |t: f64| t / 500.0;
+
+    let times = sample_inhomogeneous_poisson_process(rate, 2.0, t_max, &mut rng).unwrap();
+    assert!((times.len() as f64 - 1000.0).abs() < 100.0);
+
+    // a quarter of the mass lies in the first half of the interval
+    let first_half = times.iter().filter(|&&t| t < t_max / 2.0).count() as f64;
+    assert!((first_half / times.len() as f64 - 0.25).abs() < 0.05);
+
+    assert!(sample_inhomogeneous_poisson_process(rate, 1.0, t_max, &mut rng).is_err());
+  }
+}